@@ -1,9 +1,6 @@
-use std::{collections::HashMap, sync::atomic::Ordering};
+use std::collections::HashMap;
 
-use crate::{
-    error::Error,
-    parse::{App, APP_CORES},
-};
+use crate::{error::Error, parse::App};
 
 pub fn run(parsed_app: &mut App) -> syn::Result<()> {
     // create a mapping for a resource name and the core that it belongs to
@@ -20,10 +17,10 @@ pub fn run(parsed_app: &mut App) -> syn::Result<()> {
     // shared resource structs are always less than task structs, so it is reasonable to make the outer loop
     // iterate over tasks structs instead of iterating over shared resources structs
     for task in parsed_app.tasks.iter_mut() {
-        let task_name = &task.task_struct.ident;
+        let task_name = &task.name;
         if task.core.is_some() {
             continue;
-        } else if APP_CORES.load(Ordering::Relaxed) == 1 {
+        } else if parsed_app.cores == 1 {
             task.assign_core(0);
             continue;
         } else if task.shared_items.is_empty() {
@@ -1,27 +1,45 @@
 mod auto_assign;
-mod codegen;
 mod error;
 mod parse;
 
-use crate::codegen::CodeGen;
 use crate::parse::App;
 use proc_macro2::TokenStream;
-use rtic_core::parse_utils::RticAttr;
-use rtic_core::RticPass;
+use rtic_core::parse_utils::{AppModule, RticAttr};
+use rtic_core::diagnostics::Phase;
+use rtic_core::{PassContext, PassDependencies, RticPass};
 use syn::ItemMod;
 
 pub struct AutoAssignPass;
 
 impl RticPass for AutoAssignPass {
-    fn run_pass(&self, args: TokenStream, app_mod: ItemMod) -> syn::Result<(TokenStream, ItemMod)> {
+    fn run_pass(
+        &self,
+        ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
         let params = RticAttr::parse_from_tokens(args.clone())?;
-        let mut parsed = App::parse(&params, app_mod)?;
+        let mut module = AppModule::parse(app_mod)?;
+        let mut parsed = App::parse(&params, &mut module)?;
+        ctx.set_phase(Phase::Analysis);
         auto_assign::run(&mut parsed)?;
-        let code = CodeGen::new(parsed).run();
-        Ok((args, code))
+        ctx.set_phase(Phase::Codegen);
+        Ok((args, module.into_item_mod()))
     }
 
     fn pass_name(&self) -> &str {
         "AutoAssign"
     }
+
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::new()
+            .consumes([
+                "shared",
+                "shared.core",
+                "task.shared",
+                "sw_task.shared",
+                "idle.shared",
+            ])
+            .produces(["task.core", "sw_task.core", "idle.core"])
+    }
 }
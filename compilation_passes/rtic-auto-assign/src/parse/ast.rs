@@ -1,19 +1,13 @@
-use std::sync::atomic::Ordering;
-
-use quote::{format_ident, ToTokens};
-use rtic_core::parse_utils::RticAttr;
+use rtic_core::parse_utils::{parse_shared_resource, RticAttr, RticItem};
 use syn::{parse_quote, Expr, ItemStruct, Lit};
 
 use crate::error::Error;
 
-use super::APP_CORES;
-
 #[derive(Debug)]
-pub struct RticTask {
-    pub params: RticAttr,
-    pub attr_idx: usize,
+pub struct RticTask<'a> {
+    pub name: syn::Ident,
+    pub params: &'a mut RticAttr,
     pub shared_items: Vec<syn::Ident>,
-    pub task_struct: ItemStruct,
     /// Core to be assigned during automatic core assignment, if not set already by user
     ///
     /// Note that this may still be None after the auto assignment part if the
@@ -21,10 +15,9 @@ pub struct RticTask {
     pub core: Option<u32>,
 }
 
-impl RticTask {
-    pub fn from_struct((task_struct, attr_idx): (ItemStruct, usize)) -> syn::Result<Self> {
-        let params = RticAttr::parse_from_attr(&task_struct.attrs[attr_idx])?;
-
+impl<'a> RticTask<'a> {
+    pub fn from_item(task: &'a mut RticItem<ItemStruct>) -> Self {
+        let params = &mut task.attr;
         let core = if let Some(Expr::Lit(syn::ExprLit {
             lit: Lit::Int(int), ..
         })) = params.elements.get("core")
@@ -36,21 +29,22 @@ impl RticTask {
         };
 
         let shared_items = if let Some(Expr::Array(arr)) = params.elements.get("shared") {
+            // read-only (`&resource`) and exclusive accesses pin the task to the core of the resource alike
             arr.elems
                 .iter()
-                .map(|item| format_ident!("{}", item.to_token_stream().to_string()))
+                .filter_map(|item| parse_shared_resource(item).ok())
+                .map(|(ident, _)| ident)
                 .collect()
         } else {
             Vec::new()
         };
 
-        Ok(Self {
+        Self {
+            name: task.item.ident.clone(),
             core,
             params,
-            attr_idx,
             shared_items,
-            task_struct,
-        })
+        }
     }
 
     pub fn assign_core(&mut self, core: u32) {
@@ -64,13 +58,12 @@ impl RticTask {
 pub struct SharedResources {
     pub core: u32,
     pub shared_items: Vec<syn::Ident>,
-    pub shared_struct: ItemStruct,
 }
 
 impl SharedResources {
-    pub fn from_struct((shared_struct, attr_idx): (ItemStruct, usize)) -> syn::Result<Self> {
-        let params = RticAttr::parse_from_attr(&shared_struct.attrs[attr_idx])?;
-        let shared_items = shared_struct
+    pub fn from_item(shared: &RticItem<ItemStruct>, app_cores: u32) -> syn::Result<Self> {
+        let shared_items = shared
+            .item
             .fields
             .iter()
             .map(|f| {
@@ -82,19 +75,15 @@ impl SharedResources {
 
         let core = if let Some(Expr::Lit(syn::ExprLit {
             lit: Lit::Int(int), ..
-        })) = params.elements.get("core")
+        })) = shared.attr.elements.get("core")
         {
             int.base10_parse().unwrap_or_default()
-        } else if APP_CORES.load(Ordering::Relaxed) == 1 {
+        } else if app_cores == 1 {
             0
         } else {
-            return Err(Error::NoCoreArgShared(shared_struct.ident.to_string()).into());
+            return Err(Error::NoCoreArgShared(shared.name().to_string()).into());
         };
 
-        Ok(Self {
-            shared_items,
-            shared_struct,
-            core,
-        })
+        Ok(Self { shared_items, core })
     }
 }
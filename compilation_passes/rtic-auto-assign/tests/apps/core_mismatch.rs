@@ -0,0 +1,14 @@
+mod app {
+    #[shared(core = 0)]
+    struct SharedCore0 {
+        led: u32,
+    }
+
+    #[shared(core = 1)]
+    struct SharedCore1 {
+        counter: u32,
+    }
+
+    #[task(binds = UART0, shared = [led, counter])]
+    struct Confused;
+}
@@ -0,0 +1,21 @@
+mod app {
+    #[shared(core = 0)]
+    struct SharedCore0 {
+        led: u32,
+    }
+
+    #[shared(core = 1)]
+    struct SharedCore1 {
+        counter: u32,
+        buffer: [u8; 4],
+    }
+
+    #[task(binds = UART0, shared = [led])]
+    struct Blinker;
+
+    #[sw_task(shared = [counter, &buffer])]
+    struct Worker;
+
+    #[task(binds = UART1, core = 1)]
+    struct Pinned;
+}
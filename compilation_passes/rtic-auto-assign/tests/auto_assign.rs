@@ -0,0 +1,22 @@
+use rtic_auto_assign::AutoAssignPass;
+use rtic_testkit::{assert_golden, expand_pass};
+
+#[test]
+fn assigns_cores_from_shared_resources() {
+    let output = expand_pass(
+        &AutoAssignPass,
+        "device = pac, cores = 2",
+        include_str!("apps/two_cores.rs"),
+    );
+    assert_golden("assigns_cores_from_shared_resources", &output);
+}
+
+#[test]
+fn rejects_resources_of_different_cores() {
+    let output = expand_pass(
+        &AutoAssignPass,
+        "device = pac, cores = 2",
+        include_str!("apps/core_mismatch.rs"),
+    );
+    assert_golden("core_mismatch", &output);
+}
@@ -0,0 +1,18 @@
+#[app(device = pac, cores = 2)]
+mod app {
+    #[shared(core = 0)]
+    struct SharedCore0 {
+        led: u32,
+    }
+    #[shared(core = 1)]
+    struct SharedCore1 {
+        counter: u32,
+        buffer: [u8; 4],
+    }
+    #[task(binds = UART0, shared = [led], core = 0u32)]
+    struct Blinker;
+    #[sw_task(shared = [counter, &buffer], core = 1u32)]
+    struct Worker;
+    #[task(binds = UART1, core = 1)]
+    struct Pinned;
+}
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[AutoAssign, analysis] at 1:0
+//   The task `Confused` is only allowed to use resources from core 0.
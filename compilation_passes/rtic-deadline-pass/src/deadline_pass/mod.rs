@@ -1,12 +1,10 @@
-mod codegen;
 // mod error;
 mod parse;
 
-use codegen::CodeGen;
-use parse::App;
+use parse::ast::RticTask;
 use proc_macro2::TokenStream;
-use rtic_core::parse_utils::RticAttr;
-use rtic_core::RticPass;
+use rtic_core::parse_utils::AppModule;
+use rtic_core::{ArgKind, AttrSchema, PassContext, PassDependencies, RticPass};
 use syn::{parse_quote, ItemMod};
 
 pub struct DeadlineToPriorityPass {
@@ -21,16 +19,20 @@ impl DeadlineToPriorityPass {
 }
 
 impl RticPass for DeadlineToPriorityPass {
-    fn run_pass(&self, args: TokenStream, app_mod: ItemMod) -> syn::Result<(TokenStream, ItemMod)> {
-        let params = RticAttr::parse_from_tokens(args.clone())?;
+    fn run_pass(
+        &self,
+        _ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
+        let mut app = AppModule::parse(app_mod)?;
+        let mut tasks = parse::tasks(&mut app)?;
 
-        let mut parsed = App::parse(&params, app_mod)?;
+        self.analyze(&mut tasks);
 
-        self.analyze(&mut parsed);
-
-        for task in parsed.tasks.iter_mut() {
+        for task in tasks.iter_mut() {
             if let Some(deadline) = task.deadline {
-                task.params.elements.remove("deadline");
+                task.params.elements.shift_remove("deadline");
                 let expr: syn::Expr = parse_quote! { #deadline };
                 let _ = task.params.elements.insert("priority".into(), expr);
             } else {
@@ -38,24 +40,31 @@ impl RticPass for DeadlineToPriorityPass {
             }
         }
 
-        let code = CodeGen::new(parsed).run();
-        Ok((args, code))
+        Ok((args, app.into_item_mod()))
     }
 
     fn pass_name(&self) -> &str {
         "deadline_pass"
     }
+
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::new()
+            .consumes(["task.deadline", "sw_task.deadline"])
+            .produces(["task.priority", "sw_task.priority"])
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        AttrSchema::new()
+            .arg("task", "deadline", ArgKind::Int)
+            .arg("sw_task", "deadline", ArgKind::Int)
+    }
 }
 
 impl DeadlineToPriorityPass {
-    fn analyze(&self, app: &mut App) {
-        let mut deadlines: Vec<_> = app
-            .tasks
+    fn analyze(&self, tasks: &mut [RticTask]) {
+        let mut deadlines: Vec<_> = tasks
             .iter()
-            .map(|task| match task.deadline {
-                Some(v) => v,
-                None => u32::MAX,
-            })
+            .map(|task| task.deadline.unwrap_or(u32::MAX))
             .collect();
         eprintln!("task deadlines {:?}", deadlines);
 
@@ -70,7 +79,7 @@ impl DeadlineToPriorityPass {
             panic!("Exceeded number of priorities for this platform ({}), please coerce deadlines manually.", self.max_priority);
         }
 
-        for t in app.tasks.iter_mut() {
+        for t in tasks.iter_mut() {
             match t.deadline {
                 None => {}
                 Some(v) => {
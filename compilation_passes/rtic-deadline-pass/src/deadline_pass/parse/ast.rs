@@ -1,18 +1,14 @@
 use rtic_core::parse_utils::RticAttr;
-use syn::{Expr, ItemStruct, Lit};
+use syn::{Expr, Lit};
 
 #[derive(Debug)]
-pub struct RticTask {
-    pub params: RticAttr,
-    pub attr_idx: usize,
-    pub task_struct: ItemStruct,
+pub struct RticTask<'a> {
+    pub params: &'a mut RticAttr,
     pub deadline: Option<u32>, // explicit deadline
 }
 
-impl RticTask {
-    pub fn from_struct((task_struct, attr_idx): (ItemStruct, usize)) -> syn::Result<Self> {
-        let params = RticAttr::parse_from_attr(&task_struct.attrs[attr_idx])?;
-
+impl<'a> RticTask<'a> {
+    pub fn from_attr(params: &'a mut RticAttr) -> syn::Result<Self> {
         let deadline = if let Some(Expr::Lit(syn::ExprLit {
             lit: Lit::Int(int), ..
         })) = params.elements.get("deadline")
@@ -31,11 +27,6 @@ impl RticTask {
             panic!("'priority' found, please use 'deadlines' only or compile with --no-default-features.")
         }
 
-        Ok(Self {
-            params,
-            attr_idx,
-            task_struct,
-            deadline,
-        })
+        Ok(Self { params, deadline })
     }
 }
@@ -0,0 +1,16 @@
+mod app {
+    #[init]
+    fn init() {}
+
+    #[task(binds = UART0, deadline = 100)]
+    struct Slow;
+
+    #[task(binds = UART1, deadline = 10)]
+    struct Fast;
+
+    #[sw_task(deadline = 100)]
+    struct AlsoSlow;
+
+    #[task(binds = UART2)]
+    struct NoDeadline;
+}
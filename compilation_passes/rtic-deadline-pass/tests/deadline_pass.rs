@@ -0,0 +1,12 @@
+use rtic_deadline_pass::DeadlineToPriorityPass;
+use rtic_testkit::{assert_golden, expand_pass};
+
+#[test]
+fn deadlines_to_priorities() {
+    let output = expand_pass(
+        &DeadlineToPriorityPass::new(8),
+        "device = pac",
+        include_str!("apps/deadlines.rs"),
+    );
+    assert_golden("deadlines_to_priorities", &output);
+}
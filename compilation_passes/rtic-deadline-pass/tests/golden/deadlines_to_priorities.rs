@@ -0,0 +1,13 @@
+#[app(device = pac)]
+mod app {
+    #[init]
+    fn init() {}
+    #[task(binds = UART0, priority = 2u32)]
+    struct Slow;
+    #[task(binds = UART1, priority = 3u32)]
+    struct Fast;
+    #[sw_task(priority = 2u32)]
+    struct AlsoSlow;
+    #[task(binds = UART2)]
+    struct NoDeadline;
+}
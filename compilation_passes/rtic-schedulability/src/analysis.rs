@@ -0,0 +1,246 @@
+use std::cmp::Reverse;
+use std::fmt::Display;
+
+use rtic_core::diagnostics::combine_errors;
+use rtic_core::{App, PassContext, SubApp};
+use syn::Ident;
+
+use crate::parse::{Timing, TimingAnnotations};
+
+/// Fact published by the post-core [crate::SchedulabilityPass]: the response times of the analyzed tasks
+#[derive(Debug, Clone, Default)]
+pub struct SchedulabilityReport {
+    /// Analyzed cores, and the response times of their tasks from the highest priority to the lowest
+    pub cores: Vec<(u32, Vec<TaskResponse>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskResponse {
+    pub task: Ident,
+    pub priority: u16,
+    pub timing: Timing,
+    /// Worst-case time the task waits for lower priority tasks holding a resource
+    pub blocking: u64,
+    /// Worst-case response time. When the task misses its deadline, this is the first value of the analysis
+    /// above the deadline.
+    pub response_time: u64,
+}
+
+impl TaskResponse {
+    pub fn meets_deadline(&self) -> bool {
+        self.response_time <= self.timing.period
+    }
+}
+
+/// An annotated task, together with the ceilings of the resources it locks
+struct AnalyzedTask<'a> {
+    task: &'a Ident,
+    priority: u16,
+    timing: Timing,
+    /// Highest ceiling among the resources locked by the task
+    max_ceiling: u16,
+}
+
+impl SchedulabilityReport {
+    /// Runs the response-time analysis of every core with annotated tasks. `app` holds the resource ceilings computed
+    /// by the analysis of the **Core Pass**.
+    pub fn run(ctx: &PassContext, app: &App, annotations: &TimingAnnotations) -> syn::Result<Self> {
+        let mut errors = Vec::new();
+        let mut cores = Vec::new();
+        for sub_app in app.sub_apps.iter() {
+            match analyzed_tasks(sub_app, annotations) {
+                Ok(tasks) if tasks.is_empty() => continue,
+                Ok(tasks) => {
+                    warn_about_idle(ctx, sub_app);
+                    cores.push((sub_app.core, response_times(&tasks)));
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        match combine_errors(errors) {
+            Some(error) => Err(error),
+            None => Ok(Self { cores }),
+        }
+    }
+
+    /// Reports every task that misses its deadline
+    pub fn check(&self) -> syn::Result<()> {
+        let errors = self
+            .cores
+            .iter()
+            .flat_map(|(core, tasks)| tasks.iter().map(move |task| (core, task)))
+            .filter(|(_, task)| !task.meets_deadline())
+            .map(|(core, task)| {
+                syn::Error::new(
+                    task.task.span(),
+                    format!(
+                        "The task `{}` misses its deadline on core {core}: its worst-case response time exceeds its \
+                        period of {} (wcet {}, blocking {}).",
+                        task.task, task.timing.period, task.timing.wcet, task.blocking
+                    ),
+                )
+            });
+        combine_errors(errors).map_or(Ok(()), Err)
+    }
+}
+
+impl Display for SchedulabilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Schedulability analysis (response times in the unit of the timing annotations)"
+        )?;
+        for (core, tasks) in self.cores.iter() {
+            write!(f, "\ncore {core}:")?;
+            for task in tasks.iter() {
+                let response_time = match task.meets_deadline() {
+                    true => task.response_time.to_string(),
+                    false => format!("> {} (deadline missed)", task.timing.period),
+                };
+                write!(
+                    f,
+                    "\n  {}: priority {}, wcet {}, period {}, blocking {}, response time {response_time}",
+                    task.task, task.priority, task.timing.wcet, task.timing.period, task.blocking
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The annotated tasks of `sub_app`. Once a task of a core is annotated, all the tasks of the core the annotations
+/// were collected from must be.
+fn analyzed_tasks<'a>(
+    sub_app: &'a SubApp,
+    annotations: &TimingAnnotations,
+) -> syn::Result<Vec<AnalyzedTask<'a>>> {
+    let tasks: Vec<_> = sub_app
+        .tasks
+        .iter()
+        .filter_map(|task| Some((task, annotations.get(&task.name().to_string())?)))
+        .collect();
+    if tasks.iter().all(|(_, timing)| timing.is_none()) {
+        return Ok(Vec::new());
+    }
+
+    let errors = tasks.iter().filter(|(_, timing)| timing.is_none()).map(|(task, _)| {
+        syn::Error::new(
+            task.name().span(),
+            format!(
+                "The task `{}` has no timing annotations, but other tasks of core {} are analyzed. Add `wcet` and \
+                `period` (or `min_interarrival`) to the task.",
+                task.name(),
+                sub_app.core
+            ),
+        )
+    });
+    if let Some(error) = combine_errors(errors) {
+        return Err(error);
+    }
+
+    let ceiling_of = |resource: &Ident, reads_only: bool| {
+        sub_app
+            .shared
+            .iter()
+            .find_map(|shared| shared.get_field(resource))
+            .map_or(0, |r| {
+                if reads_only {
+                    r.reader_ceiling
+                } else {
+                    r.priority
+                }
+            })
+    };
+    Ok(tasks
+        .into_iter()
+        .filter_map(|(task, timing)| {
+            let max_ceiling = task
+                .args
+                .shared
+                .iter()
+                .map(|resource| ceiling_of(resource, task.reads_only(resource)))
+                .max()
+                .unwrap_or(0);
+            Some(AnalyzedTask {
+                task: task.name(),
+                priority: task.args.priority,
+                timing: timing?,
+                max_ceiling,
+            })
+        })
+        .collect())
+}
+
+/// Response-time analysis under the Stack Resource Policy, see the [crate level documentation](crate)
+fn response_times(tasks: &[AnalyzedTask]) -> Vec<TaskResponse> {
+    let mut responses: Vec<_> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            // a lower priority task blocks at most once, while holding a resource that can delay this task
+            let blocking = tasks
+                .iter()
+                .filter(|other| {
+                    other.priority < task.priority && other.max_ceiling >= task.priority
+                })
+                .map(|other| other.timing.wcet)
+                .max()
+                .unwrap_or(0);
+            let interfering: Vec<_> = tasks
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && other.priority >= task.priority)
+                .map(|(_, other)| other.timing)
+                .collect();
+
+            let base = task.timing.wcet.saturating_add(blocking);
+            let mut response_time = base;
+            loop {
+                let interference = interfering.iter().fold(0_u64, |sum, other| {
+                    sum.saturating_add(
+                        response_time
+                            .div_ceil(other.period)
+                            .saturating_mul(other.wcet),
+                    )
+                });
+                let next = base.saturating_add(interference);
+                if next == response_time || next > task.timing.period {
+                    response_time = next;
+                    break;
+                }
+                response_time = next;
+            }
+
+            TaskResponse {
+                task: task.task.clone(),
+                priority: task.priority,
+                timing: task.timing,
+                blocking,
+                response_time,
+            }
+        })
+        .collect();
+    responses.sort_by_key(|response| Reverse(response.priority));
+    responses
+}
+
+/// The critical sections of idle can block the analyzed tasks, but idle has no WCET
+fn warn_about_idle(ctx: &PassContext, sub_app: &SubApp) {
+    let Some(idle) = sub_app.idle.as_ref() else {
+        return;
+    };
+    if idle.args.shared.is_empty() {
+        return;
+    }
+    let resources: Vec<_> = idle.args.shared.iter().map(|r| format!("`{r}`")).collect();
+    ctx.warning(
+        idle.name().span(),
+        format!(
+            "`{}` accesses the shared resource(s) {}, its critical sections are not part of the schedulability \
+            analysis of core {}.",
+            idle.name(),
+            resources.join(", "),
+            sub_app.core
+        ),
+    );
+}
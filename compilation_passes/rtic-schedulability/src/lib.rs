@@ -0,0 +1,128 @@
+//! Compile-time schedulability analysis of RTIC applications.
+//!
+//! Tasks are annotated with their worst-case execution time and their period (or minimum inter-arrival time), in
+//! a time unit of the application's choice (e.g. µs or clock cycles), the same for all the tasks:
+//!
+//! ```rust,ignore
+//! #[task(binds = UART0, priority = 3, shared = [buffer], wcet = 20, min_interarrival = 500)]
+//! struct Receive;
+//!
+//! #[sw_task(priority = 1, shared = [buffer], wcet = 150, period = 1000)]
+//! struct Process;
+//! ```
+//!
+//! For every core with annotated tasks, the response time of each task is computed with the classic fixed-priority
+//! response-time analysis under the Stack Resource Policy:
+//!
+//! `R = C + B + Σ ⌈R / Tj⌉ · Cj` over the other tasks `j` with a priority at least as high
+//!
+//! where the blocking term `B` is the WCET of the longest lower priority task that locks a resource whose ceiling
+//! (as computed by the **Core Pass**) is at least the priority of the task. The WCET of the whole task is used as a
+//! bound of the length of its critical sections. Deadlines are implicit: a task misses its deadline when its response
+//! time exceeds its period. Every task missing its deadline is reported as an error, followed by the response times
+//! of all the analyzed tasks. Use `#[app(..., schedulability_report = true)]` to also get the report (as a warning)
+//! when all the deadlines are met.
+//!
+//! The analysis needs the resource ceilings of the **Core Pass**, so [SchedulabilityPass] is bound twice: as a
+//! pre-core pass, which collects and removes the timing annotations, and as a post-core pass, which runs the analysis.
+//!
+//! ```rust,ignore
+//! builder.bind_pre_core_pass(SchedulabilityPass);
+//! builder.bind_post_core_pass(SchedulabilityPass);
+//! ```
+//!
+//! Tasks introduced by other passes after the annotations were collected (e.g. the dispatchers of the software pass)
+//! are not part of the analysis, and neither are the critical sections of `#[idle]`.
+
+mod analysis;
+mod parse;
+
+use proc_macro2::TokenStream;
+use rtic_core::attr_schema::APP_ATTR;
+use rtic_core::diagnostics::Phase;
+use rtic_core::parse_utils::{AppModule, RticAttr};
+use rtic_core::{
+    Analysis, App, ArgKind, AttrSchema, PassContext, PassDependencies, RticPass, RticPostPass,
+};
+use syn::ItemMod;
+
+pub use analysis::{SchedulabilityReport, TaskResponse};
+pub use parse::{Timing, TimingAnnotations};
+
+/// See the [crate level documentation](crate)
+pub struct SchedulabilityPass;
+
+impl RticPass for SchedulabilityPass {
+    fn run_pass(
+        &self,
+        ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
+        let params = RticAttr::parse_from_tokens(args.clone())?;
+        let mut module = AppModule::parse(app_mod)?;
+        let annotations = TimingAnnotations::take(&params, &mut module)?;
+        ctx.insert(annotations);
+        Ok((args, module.into_item_mod()))
+    }
+
+    fn pass_name(&self) -> &str {
+        "Schedulability"
+    }
+
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::new()
+            .consumes([
+                "task.wcet",
+                "task.period",
+                "task.min_interarrival",
+                "sw_task.wcet",
+                "sw_task.period",
+                "sw_task.min_interarrival",
+            ])
+            // the annotations are collected on the tasks written by the user, before software tasks are turned into
+            // hardware tasks
+            .run_before(["SoftwareTasks"])
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        let mut schema = AttrSchema::new().arg(APP_ATTR, "schedulability_report", ArgKind::Bool);
+        for attr in ["task", "sw_task"] {
+            schema = schema
+                .arg(attr, "wcet", ArgKind::Int)
+                .arg(attr, "period", ArgKind::Int)
+                .arg(attr, "min_interarrival", ArgKind::Int);
+        }
+        schema
+    }
+}
+
+impl RticPostPass for SchedulabilityPass {
+    fn run_post_pass(
+        &self,
+        ctx: &PassContext,
+        app: &App,
+        _analysis: &Analysis,
+        expanded: ItemMod,
+    ) -> syn::Result<ItemMod> {
+        ctx.set_phase(Phase::Analysis);
+        let Some(annotations) = ctx.get::<TimingAnnotations>() else {
+            ctx.warning(
+                app.app_name.span(),
+                "The schedulability analysis did not run: `SchedulabilityPass` must also be bound as a pre-core pass.",
+            );
+            return Ok(expanded);
+        };
+        let report = SchedulabilityReport::run(ctx, app, &annotations)?;
+        let result = report.check();
+        if result.is_err() || annotations.report {
+            ctx.warning(app.app_name.span(), &report);
+        }
+        ctx.insert(report);
+        result.map(|_| expanded)
+    }
+
+    fn pass_name(&self) -> &str {
+        "Schedulability"
+    }
+}
@@ -0,0 +1,118 @@
+use proc_macro2::Span;
+use rtic_core::diagnostics::combine_errors;
+use rtic_core::parse_utils::{AppItem, AppModule, RticAttr};
+use syn::{Expr, ExprLit, Lit, spanned::Spanned};
+
+/// Timing annotations of a task, in the time unit chosen by the application
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    /// Worst-case execution time
+    pub wcet: u64,
+    /// Period, or minimum inter-arrival time. Also the relative deadline of the task.
+    pub period: u64,
+}
+
+/// Fact published by the pre-core [crate::SchedulabilityPass]: the tasks of the application as written by the user,
+/// with their timing annotations
+#[derive(Debug, Clone, Default)]
+pub struct TimingAnnotations {
+    /// Name of the task struct, and its timing if annotated
+    pub tasks: Vec<(String, Option<Timing>)>,
+    /// Whether the report is requested even if all the deadlines are met
+    pub report: bool,
+}
+
+impl TimingAnnotations {
+    /// Collects the timing annotations of the `#[task]` and `#[sw_task]` structs of `module` and removes them
+    pub fn take(params: &RticAttr, module: &mut AppModule) -> syn::Result<Self> {
+        let report = match params.elements.get("schedulability_report") {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Bool(report),
+                ..
+            })) => report.value,
+            _ => false,
+        };
+
+        let mut errors = Vec::new();
+        let mut tasks = Vec::new();
+        let attributes = module.items.iter_mut().filter_map(|item| match item {
+            AppItem::Task(task) | AppItem::SwTask(task) => {
+                Some((task.name().to_string(), &mut task.attr))
+            }
+            _ => None,
+        });
+        for (name, attr) in attributes {
+            match Timing::take(&name, attr) {
+                Ok(timing) => tasks.push((name, timing)),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        match combine_errors(errors) {
+            Some(error) => Err(error),
+            None => Ok(Self { tasks, report }),
+        }
+    }
+
+    /// Returns `None` for tasks that were not in the application when the annotations were collected
+    pub fn get(&self, task: &str) -> Option<Option<Timing>> {
+        self.tasks
+            .iter()
+            .find(|(name, _)| name == task)
+            .map(|(_, timing)| *timing)
+    }
+}
+
+impl Timing {
+    fn take(task: &str, attr: &mut RticAttr) -> syn::Result<Option<Self>> {
+        let wcet = take_int(attr, "wcet")?;
+        let period = take_int(attr, "period")?;
+        let min_interarrival = take_int(attr, "min_interarrival")?;
+
+        let period = match (period, min_interarrival) {
+            (Some(_), Some((_, span))) => {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "The task `{task}` has both a `period` and a `min_interarrival`, only one is allowed."
+                    ),
+                ));
+            }
+            (period, min_interarrival) => period.or(min_interarrival),
+        };
+        match (wcet, period) {
+            (None, None) => Ok(None),
+            (Some((wcet, _)), Some((period, span))) => {
+                if period == 0 {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("The period of the task `{task}` must be greater than 0."),
+                    ));
+                }
+                Ok(Some(Self { wcet, period }))
+            }
+            (Some((_, span)), None) => Err(syn::Error::new(
+                span,
+                format!("The task `{task}` has a `wcet`, but no `period` or `min_interarrival`."),
+            )),
+            (None, Some((_, span))) => Err(syn::Error::new(
+                span,
+                format!("The task `{task}` has a period, but no `wcet`."),
+            )),
+        }
+    }
+}
+
+/// Removes the argument `name` from `attr`, and returns its value and span
+fn take_int(attr: &mut RticAttr, name: &str) -> syn::Result<Option<(u64, Span)>> {
+    match attr.elements.shift_remove(name) {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        })) => Ok(Some((int.base10_parse()?, int.span()))),
+        Some(other) => Err(syn::Error::new(
+            other.span(),
+            format!("`{name}` expects an integer literal"),
+        )),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,13 @@
+mod app {
+    #[init]
+    fn init() {}
+
+    #[task(binds = ADC, priority = 3, wcet = 10)]
+    struct NoPeriod;
+
+    #[task(binds = TIMER0, priority = 2, wcet = 20, period = 200, min_interarrival = 100)]
+    struct TwoPeriods;
+
+    #[task(binds = UART0, priority = 1, period = 0, wcet = 5)]
+    struct ZeroPeriod;
+}
@@ -0,0 +1,26 @@
+mod app {
+    #[init]
+    fn init() {}
+
+    #[task(binds = ADC, priority = 3, wcet = 10, period = 100)]
+    struct Timed;
+
+    impl RticTask for Timed {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    #[task(binds = UART0, priority = 1)]
+    struct Untimed;
+
+    impl RticTask for Untimed {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+}
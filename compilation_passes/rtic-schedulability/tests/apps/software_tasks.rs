@@ -0,0 +1,53 @@
+mod app {
+    #[shared]
+    struct Shared {
+        samples: [u32; 8],
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { samples: [0; 8] }
+    }
+
+    #[task(binds = ADC, priority = 3, shared = [samples], wcet = 10, min_interarrival = 100)]
+    struct Sample;
+
+    impl RticTask for Sample {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().samples.lock(|samples| samples[0] = read());
+            let _ = Filter::spawn(());
+        }
+    }
+
+    #[sw_task(priority = 2, shared = [samples], wcet = 30, period = 100)]
+    struct Filter;
+
+    impl RticSwTask for Filter {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {
+            self.shared().samples.lock(|samples| filter(samples));
+        }
+    }
+
+    #[sw_task(priority = 1, wcet = 200, period = 1000)]
+    struct Report;
+
+    impl RticSwTask for Report {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {}
+    }
+}
@@ -0,0 +1,55 @@
+mod app {
+    #[shared]
+    struct Shared {
+        buffer: [u8; 16],
+        counter: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared {
+            buffer: [0; 16],
+            counter: 0,
+        }
+    }
+
+    #[task(binds = ADC, priority = 3, shared = [buffer], wcet = 10, period = PERIOD)]
+    struct Sample;
+
+    impl RticTask for Sample {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().buffer.lock(|buffer| buffer[0] = 1);
+        }
+    }
+
+    #[task(binds = TIMER0, priority = 2, shared = [counter], wcet = 20, period = 200)]
+    struct Tick;
+
+    impl RticTask for Tick {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().counter.lock(|counter| *counter += 1);
+        }
+    }
+
+    #[task(binds = UART0, priority = 1, shared = [buffer, counter], wcet = 50, min_interarrival = 1000)]
+    struct Transmit;
+
+    impl RticTask for Transmit {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let counter = self.shared().counter.lock(|counter| *counter);
+            self.shared().buffer.lock(|buffer| buffer[1] = counter as u8);
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// no code was generated
+
+// warning[Schedulability, analysis] at 1:4
+//   Schedulability analysis (response times in the unit of the timing annotations)
+//   core 0:
+//     Sample: priority 3, wcet 10, period 55, blocking 50, response time > 55 (deadline missed)
+//     Tick: priority 2, wcet 20, period 200, blocking 50, response time 90
+//     Transmit: priority 1, wcet 50, period 1000, blocking 0, response time 90
+
+// error[Schedulability, analysis] at 17:11
+//   The task `Sample` misses its deadline on core 0: its worst-case response time exceeds its period of 55 (wcet 10, blocking 50).
@@ -0,0 +1,10 @@
+// no code was generated
+
+// error[Schedulability, parse] at 5:45
+//   The task `NoPeriod` has a `wcet`, but no `period` or `min_interarrival`.
+
+// error[Schedulability, parse] at 8:85
+//   The task `TwoPeriods` has both a `period` and a `min_interarrival`, only one is allowed.
+
+// error[Schedulability, parse] at 11:49
+//   The period of the task `ZeroPeriod` must be greater than 0.
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[Schedulability, analysis] at 17:11
+//   The task `Untimed` has no timing annotations, but other tasks of core 0 are analyzed. Add `wcet` and `period` (or `min_interarrival`) to the task.
@@ -0,0 +1,519 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    use pac as _;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rtic::export::interrupt_free(f)
+    }
+    /// # CORE 0
+    static mut SHARED: core::mem::MaybeUninit<Shared> = core::mem::MaybeUninit::uninit();
+    struct Shared {
+        buffer: [u8; 16],
+        counter: u32,
+    }
+    fn init() -> Shared {
+        Shared {
+            buffer: [0; 16],
+            counter: 0,
+        }
+    }
+    static mut SAMPLE: core::mem::MaybeUninit<Sample> = core::mem::MaybeUninit::uninit();
+    struct Sample;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Sample>();
+    };
+    impl RticTask for Sample {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            self.shared().buffer.lock(|buffer| buffer[0] = 1);
+        }
+        type InitArgs = ();
+    }
+    impl Sample {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl Sample {
+        pub fn shared(&self) -> __sample_shared_resources {
+            const TASK_PRIORITY: u16 = 3u16;
+            __sample_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __sample_shared_resources {
+        pub buffer: __buffer_mutex,
+    }
+    impl __sample_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                buffer: __buffer_mutex::new(priority),
+            }
+        }
+    }
+    impl Sample {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut TICK: core::mem::MaybeUninit<Tick> = core::mem::MaybeUninit::uninit();
+    struct Tick;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Tick>();
+    };
+    impl RticTask for Tick {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            self.shared().counter.lock(|counter| *counter += 1);
+        }
+        type InitArgs = ();
+    }
+    impl Tick {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Tick {
+        pub fn shared(&self) -> __tick_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __tick_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __tick_shared_resources {
+        pub counter: __counter_mutex,
+    }
+    impl __tick_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                counter: __counter_mutex::new(priority),
+            }
+        }
+    }
+    impl Tick {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut TRANSMIT: core::mem::MaybeUninit<Transmit> = core::mem::MaybeUninit::uninit();
+    struct Transmit;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Transmit>();
+    };
+    impl RticTask for Transmit {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            let counter = self.shared().counter.lock(|counter| *counter);
+            self.shared().buffer.lock(|buffer| buffer[1] = counter as u8);
+        }
+        type InitArgs = ();
+    }
+    impl Transmit {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl Transmit {
+        pub fn shared(&self) -> __transmit_shared_resources {
+            const TASK_PRIORITY: u16 = 1u16;
+            __transmit_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __transmit_shared_resources {
+        pub buffer: __buffer_mutex,
+        pub counter: __counter_mutex,
+    }
+    impl __transmit_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                buffer: __buffer_mutex::new(priority),
+                counter: __counter_mutex::new(priority),
+            }
+        }
+    }
+    impl Transmit {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn ADC() {
+        unsafe { SAMPLE.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn TIMER0() {
+        unsafe { TICK.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn UART0() {
+        unsafe { TRANSMIT.assume_init_mut().exec() };
+    }
+    pub struct __buffer_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __buffer_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __buffer_mutex {
+        type ResourceType = [u8; 16];
+        const CEILING: u16 = 3u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 3u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().buffer } as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().buffer as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    pub struct __counter_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __counter_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __counter_mutex {
+        type ResourceType = u32;
+        const CEILING: u16 = 2u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 2u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().counter }
+                as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().counter as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    /// Type representing tasks that need explicit user initialization
+    /// Entry of
+    /// # CORE 0
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __shared_resources_0: Shared = init();
+            unsafe {
+                SHARED.write(__shared_resources_0);
+            }
+            unsafe {
+                SAMPLE.write(Sample::init(()));
+                TICK.write(Tick::init(()));
+                TRANSMIT.write(Transmit::init(()));
+            }
+            rtic::export::enable(pac::Interrupt::ADC, 3u16);
+            rtic::export::enable(pac::Interrupt::TIMER0, 2u16);
+            rtic::export::enable(pac::Interrupt::UART0, 1u16);
+        });
+        loop {
+            rtic::export::wfi();
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+    }
+}
+
+// warning[Schedulability, analysis] at 1:4
+//   Schedulability analysis (response times in the unit of the timing annotations)
+//   core 0:
+//     Sample: priority 3, wcet 10, period 100, blocking 50, response time 60
+//     Tick: priority 2, wcet 20, period 200, blocking 50, response time 80
+//     Transmit: priority 1, wcet 50, period 1000, blocking 0, response time 80
@@ -0,0 +1,638 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    use pac as _;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rtic::export::interrupt_free(f)
+    }
+    static mut __rtic_internal__Filter__INPUTS: rtic::export::Queue<
+        <Filter as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Filter {
+        pub fn spawn(
+            input: <Filter as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Filter as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Filter__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Filter as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Filter) };
+                __rtic_local_irq_pend(pac::Interrupt::UART0);
+                Ok(())
+            })
+        }
+    }
+    static mut __rtic_internal__Report__INPUTS: rtic::export::Queue<
+        <Report as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Report {
+        pub fn spawn(
+            input: <Report as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Report as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Report__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Report as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Report) };
+                __rtic_local_irq_pend(pac::Interrupt::UART1);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio1Tasks {
+        Report,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio1Tasks__RQ: rtic::export::Queue<
+        Core0Prio1Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio2Tasks {
+        Filter,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio2Tasks__RQ: rtic::export::Queue<
+        Core0Prio2Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        rtic::export::cross_core_pend(irq_nbr);
+    }
+    /// # CORE 0
+    static mut SHARED: core::mem::MaybeUninit<Shared> = core::mem::MaybeUninit::uninit();
+    struct Shared {
+        samples: [u32; 8],
+    }
+    fn init() -> Shared {
+        Shared { samples: [0; 8] }
+    }
+    static mut SAMPLE: core::mem::MaybeUninit<Sample> = core::mem::MaybeUninit::uninit();
+    struct Sample;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Sample>();
+    };
+    impl RticTask for Sample {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            self.shared().samples.lock(|samples| samples[0] = read());
+            let _ = Filter::spawn(());
+        }
+        type InitArgs = ();
+    }
+    impl Sample {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl Sample {
+        pub fn shared(&self) -> __sample_shared_resources {
+            const TASK_PRIORITY: u16 = 3u16;
+            __sample_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __sample_shared_resources {
+        pub samples: __samples_mutex,
+    }
+    impl __sample_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                samples: __samples_mutex::new(priority),
+            }
+        }
+    }
+    impl Sample {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut FILTER: core::mem::MaybeUninit<Filter> = core::mem::MaybeUninit::uninit();
+    /// Software tasks of
+    /// Core 0
+    struct Filter;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Filter>();
+    };
+    impl RticSwTask for Filter {
+        type SpawnInput = ();
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {
+            self.shared().samples.lock(|samples| filter(samples));
+        }
+        type InitArgs = ();
+    }
+    impl Filter {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Filter {
+        pub fn shared(&self) -> __filter_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __filter_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __filter_shared_resources {
+        pub samples: __samples_mutex,
+    }
+    impl __filter_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                samples: __samples_mutex::new(priority),
+            }
+        }
+    }
+    impl Filter {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut REPORT: core::mem::MaybeUninit<Report> = core::mem::MaybeUninit::uninit();
+    struct Report;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Report>();
+    };
+    impl RticSwTask for Report {
+        type SpawnInput = ();
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {}
+        type InitArgs = ();
+    }
+    impl Report {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl Report {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut CORE0_PRIORITY1_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority1Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[doc(hidden)]
+    pub struct Core0Priority1Dispatcher;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority1Dispatcher>();
+    };
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio1Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio1Tasks::Report => {
+                            let mut input_consumer = __rtic_internal__Report__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            REPORT.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority1Dispatcher {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl Core0Priority1Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut CORE0_PRIORITY2_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority2Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[doc(hidden)]
+    pub struct Core0Priority2Dispatcher;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority2Dispatcher>();
+    };
+    impl RticTask for Core0Priority2Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio2Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio2Tasks::Filter => {
+                            let mut input_consumer = __rtic_internal__Filter__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            FILTER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority2Dispatcher {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Core0Priority2Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn ADC() {
+        unsafe { SAMPLE.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn UART1() {
+        unsafe { CORE0_PRIORITY1_DISPATCHER.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn UART0() {
+        unsafe { CORE0_PRIORITY2_DISPATCHER.assume_init_mut().exec() };
+    }
+    pub struct __samples_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __samples_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __samples_mutex {
+        type ResourceType = [u32; 8];
+        const CEILING: u16 = 3u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 3u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().samples }
+                as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().samples as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    /// Type representing tasks that need explicit user initialization
+    /// Entry of
+    /// # CORE 0
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __shared_resources_0: Shared = init();
+            unsafe {
+                SHARED.write(__shared_resources_0);
+            }
+            unsafe {
+                SAMPLE.write(Sample::init(()));
+                FILTER.write(Filter::init(()));
+                REPORT.write(Report::init(()));
+                CORE0_PRIORITY1_DISPATCHER.write(Core0Priority1Dispatcher::init(()));
+                CORE0_PRIORITY2_DISPATCHER.write(Core0Priority2Dispatcher::init(()));
+            }
+            rtic::export::enable(pac::Interrupt::ADC, 3u16);
+            rtic::export::enable(pac::Interrupt::UART1, 1u16);
+            rtic::export::enable(pac::Interrupt::UART0, 2u16);
+        });
+        loop {
+            rtic::export::wfi();
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+        pub fn implements_rtic_sw_task<T: RticSwTask>() {}
+    }
+}
+
+// warning[Schedulability, analysis] at 1:4
+//   Schedulability analysis (response times in the unit of the timing annotations)
+//   core 0:
+//     Sample: priority 3, wcet 10, period 100, blocking 30, response time 40
+//     Filter: priority 2, wcet 30, period 100, blocking 0, response time 40
+//     Report: priority 1, wcet 200, period 1000, blocking 0, response time 360
@@ -0,0 +1,82 @@
+use rtic_core::RticMacroBuilder;
+use rtic_schedulability::SchedulabilityPass;
+use rtic_sw_pass::SoftwarePass;
+use rtic_testkit::{MockBackend, MockSwBackend, assert_golden, expand_app};
+
+fn builder() -> RticMacroBuilder {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder
+        .bind_pre_core_pass(SchedulabilityPass)
+        .bind_post_core_pass(SchedulabilityPass);
+    builder
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn reports_response_times() {
+    let app = include_str!("apps/timed_tasks.rs").replace("PERIOD", "100");
+    let output = expand_app(
+        builder(),
+        "device = pac, schedulability_report = true",
+        &app,
+    );
+    assert_golden("response_times", &output);
+}
+
+#[test]
+fn rejects_deadline_misses() {
+    let app = include_str!("apps/timed_tasks.rs").replace("PERIOD", "55");
+    let output = expand_app(builder(), "device = pac", &app);
+    assert_golden("deadline_misses", &output);
+}
+
+#[test]
+fn rejects_invalid_timing_annotations() {
+    let output = expand_app(
+        builder(),
+        "device = pac",
+        include_str!("apps/invalid_timings.rs"),
+    );
+    assert_golden("invalid_timings", &output);
+}
+
+#[test]
+fn rejects_untimed_tasks_next_to_timed_ones() {
+    let output = expand_app(
+        builder(),
+        "device = pac",
+        include_str!("apps/partially_timed.rs"),
+    );
+    assert_golden("partially_timed", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn analyzes_software_tasks() {
+    // bound before the schedulability pass, which must still collect the annotations of the software tasks first
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder
+        .bind_pre_core_pass(SoftwarePass::new(MockSwBackend::default()))
+        .bind_pre_core_pass(SchedulabilityPass)
+        .bind_post_core_pass(SchedulabilityPass);
+    let output = expand_app(
+        builder,
+        "device = pac, dispatchers = [UART0, UART1], schedulability_report = true",
+        include_str!("apps/software_tasks.rs"),
+    );
+
+    let report = &output[output
+        .find("// warning[Schedulability")
+        .expect("no schedulability report")..];
+    assert!(report.contains("Filter: priority 2, wcet 30, period 100"));
+    assert!(report.contains("Report: priority 1, wcet 200, period 1000"));
+    // the dispatchers generated for the software tasks are not analyzed
+    assert!(!report.contains("Dispatcher"));
+    assert_golden("software_tasks", &output);
+}
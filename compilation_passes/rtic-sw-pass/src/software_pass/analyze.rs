@@ -1,7 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 
+use crate::SwPassBackend;
+use crate::software_pass::parse::ast::DispatcherParam;
 use crate::software_pass::parse::{App, SubApp};
 use proc_macro2::Span;
+use quote::ToTokens;
+use rtic_core::BackendCapabilities;
+use rtic_core::diagnostics::{collect_results, combine_errors};
+use rtic_core::model::Dispatcher;
+use syn::spanned::Spanned;
 
 pub struct Analysis {
     /// analysis for every sub-application (per-core analysis)
@@ -9,14 +16,89 @@ pub struct Analysis {
 }
 
 impl Analysis {
-    pub fn run(app: &App) -> syn::Result<Self> {
-        let sub_analysis = app
-            .sub_apps
-            .iter()
-            .map(SubAnalysis::analyse_subapp)
-            .collect::<syn::Result<_>>()?;
+    /// `backend` provides the interrupts that can be used as dispatchers by the cores whose dispatchers are not listed
+    pub fn run(
+        app: &App,
+        capabilities: &BackendCapabilities,
+        backend: &dyn SwPassBackend,
+    ) -> syn::Result<Self> {
+        if !capabilities.cross_core_pend {
+            reject_cross_core_spawns(app)?;
+        }
+        let sub_analysis = collect_results(app.sub_apps.iter().map(|sub_app| {
+            SubAnalysis::analyse_subapp(sub_app, backend.free_interrupts(sub_app.core))
+        }))?;
         Ok(Self { sub_analysis })
     }
+
+    /// The dispatchers of all the cores, described for the application model
+    pub fn dispatchers(&self) -> Vec<Dispatcher> {
+        let mut dispatchers = Vec::new();
+        for analysis in self.sub_analysis.iter() {
+            for (priority, interrupt) in analysis.dispatcher_priority_map.iter().rev() {
+                let tasks = analysis
+                    .tasks_priority_map
+                    .get(priority)
+                    .into_iter()
+                    .flatten();
+                dispatchers.push(Dispatcher {
+                    core: analysis.core,
+                    interrupt: interrupt_name(interrupt),
+                    priority: *priority,
+                    tasks: tasks.map(|(task, ..)| task.to_string()).collect(),
+                });
+            }
+        }
+        dispatchers
+    }
+
+    /// Human readable description of [Self::dispatchers], requested with `dispatchers_report = true`
+    pub fn dispatchers_report(&self) -> String {
+        let mut report = String::from("Dispatchers of the software tasks");
+        let mut core = None;
+        for dispatcher in self.dispatchers() {
+            if core != Some(dispatcher.core) {
+                core = Some(dispatcher.core);
+                report.push_str(&format!("\ncore {}:", dispatcher.core));
+            }
+            report.push_str(&format!(
+                "\n  {}: priority {}, tasks {}",
+                dispatcher.interrupt,
+                dispatcher.priority,
+                dispatcher.tasks.join(", ")
+            ));
+        }
+        report
+    }
+}
+
+fn interrupt_name(interrupt: &syn::Path) -> String {
+    interrupt.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Software tasks spawned by another core are pended from that core, which the target must support
+fn reject_cross_core_spawns(app: &App) -> syn::Result<()> {
+    let errors = app.sub_apps.iter().flat_map(|sub_app| {
+        sub_app.mc_sw_tasks.iter().map(|task| {
+            let span = task
+                .task
+                .attr
+                .elements
+                .get("spawn_by")
+                .map_or(task.name().span(), |spawn_by| spawn_by.span());
+            syn::Error::new(
+                span,
+                format!(
+                    "The software task `{}` of core {} is spawned by core {}, but this target can not pend interrupts \
+                    of another core.",
+                    task.name(),
+                    sub_app.core,
+                    task.params.spawn_by
+                ),
+            )
+        })
+    });
+    combine_errors(errors).map_or(Ok(()), Err)
 }
 
 /// Per-core/Sub application analysis
@@ -24,34 +106,35 @@ impl Analysis {
 pub struct SubAnalysis {
     pub core: u32,
     /// Maps every group of software tasks to some priority level
-    /// Tasks are identified by their `Ident` (the name of the task struct)
-    pub tasks_priority_map: HashMap<u16, Vec<(syn::Ident, u32)>>,
+    /// Tasks are identified by their `Ident` (the name of the task struct), in declaration order, with the core spawning
+    /// them and the capacity of their message queue
+    pub tasks_priority_map: BTreeMap<u16, Vec<(syn::Ident, u32, usize)>>,
     /// Maps every dispatcher to a priority level
-    pub dispatcher_priority_map: HashMap<u16, syn::Path>,
+    pub dispatcher_priority_map: BTreeMap<u16, syn::Path>,
 }
 
 impl SubAnalysis {
-    fn analyse_subapp(sub_app: &SubApp) -> syn::Result<Self> {
+    fn analyse_subapp(sub_app: &SubApp, free_interrupts: Vec<syn::Path>) -> syn::Result<Self> {
         // group sw tasks based on their associated priorities
-        let mut sw_tasks_pgroups: HashMap<u16, Vec<_>> =
-            HashMap::with_capacity(sub_app.dispatchers.len());
+        let mut sw_tasks_pgroups: BTreeMap<u16, Vec<_>> = BTreeMap::new();
         for task in sub_app.sw_tasks.iter() {
             let task_prio = task.params.priority;
-            sw_tasks_pgroups
-                .entry(task_prio)
-                .or_default()
-                .push((task.name().clone(), sub_app.core /* core local tasks*/));
+            sw_tasks_pgroups.entry(task_prio).or_default().push((
+                task.name().clone(),
+                sub_app.core, /* core local tasks*/
+                task.params.capacity,
+            ));
         }
 
         // group multicore sw tasks based on their associated priorities
-        let mut mc_tasks_pgroups: HashMap<u16, Vec<_>> =
-            HashMap::with_capacity(sub_app.dispatchers.len());
+        let mut mc_tasks_pgroups: BTreeMap<u16, Vec<_>> = BTreeMap::new();
         for task in sub_app.mc_sw_tasks.iter() {
             let task_prio = task.params.priority;
-            mc_tasks_pgroups
-                .entry(task_prio)
-                .or_default()
-                .push((task.name().clone(), task.params.spawn_by));
+            mc_tasks_pgroups.entry(task_prio).or_default().push((
+                task.name().clone(),
+                task.params.spawn_by,
+                task.params.capacity,
+            ));
         }
 
         // ensure that the multi-core tasks do not have overlapping priorities with core local software tasks
@@ -61,19 +144,24 @@ impl SubAnalysis {
         if !disjoint {
             return Err(syn::Error::new(
                 Span::call_site(),
-                format!("The priority of some tasks with `spawn_by` argument in core {} have overlapping priority with other core-local software tasks, which is forbidden.", sub_app.core),
+                format!(
+                    "The priority of some tasks with `spawn_by` argument in core {} have overlapping priority with other core-local software tasks, which is forbidden.",
+                    sub_app.core
+                ),
             ));
         }
 
         // need to further check that multi core tasks in the same priority group must all have the spawn_by index.
         for priority_group in mc_tasks_pgroups.values() {
             if priority_group.len() > 1 {
-                let (task_1, spawn_by1) = &priority_group[0];
-                for (task_x, spawn_byx) in priority_group.iter() {
+                let (task_1, spawn_by1, _) = &priority_group[0];
+                for (task_x, spawn_byx, _) in priority_group.iter() {
                     if spawn_by1 != spawn_byx {
                         return Err(syn::Error::new(
                             Span::call_site(),
-                            format!("{task_1} and {task_x} have the same priority but they are spawned by different cores which is forbidden."),
+                            format!(
+                                "{task_1} and {task_x} have the same priority but they are spawned by different cores which is forbidden."
+                            ),
                         ));
                     }
                 }
@@ -83,23 +171,60 @@ impl SubAnalysis {
         // now we can merge all priority groups together since we know they are disjoint and no overlap exists
         sw_tasks_pgroups.extend(mc_tasks_pgroups);
 
-        // check if the number of dispatchers meets the number of sw task priority groups
-        let n_dispatchers = sub_app.dispatchers.len();
+        // when the application does not list the dispatchers of the core, pick them among the free interrupts
         let n_priority_groups = sw_tasks_pgroups.len();
+        let auto_select = sub_app.dispatchers.is_empty() && !free_interrupts.is_empty();
+        let dispatchers = if auto_select {
+            free_interrupts
+                .into_iter()
+                .filter(|interrupt| !sub_app.bound_interrupts.contains(interrupt))
+                .take(n_priority_groups)
+                .map(|interrupt| DispatcherParam {
+                    interrupt,
+                    priority: None,
+                })
+                .collect()
+        } else {
+            // a dispatcher runs the software tasks from its interrupt handler, it can not be bound to a task as well
+            let errors: Vec<_> = sub_app
+                .dispatchers
+                .iter()
+                .filter(|dispatcher| sub_app.bound_interrupts.contains(&dispatcher.interrupt))
+                .map(|dispatcher| {
+                    let interrupt = interrupt_name(&dispatcher.interrupt);
+                    syn::Error::new(
+                        dispatcher.interrupt.span(),
+                        format!(
+                            "The dispatcher `{interrupt}` is also bound to a hardware task of core {}. Use an \
+                            interrupt that no task binds as dispatcher.",
+                            sub_app.core
+                        ),
+                    )
+                })
+                .collect();
+            if let Some(error) = combine_errors(errors) {
+                return Err(error);
+            }
+            sub_app.dispatchers.clone()
+        };
+
+        // check if the number of dispatchers meets the number of sw task priority groups
+        let n_dispatchers = dispatchers.len();
         if n_dispatchers < n_priority_groups {
-            return Err(syn::Error::new(
-                Span::call_site(),
-                format!("Expected {n_priority_groups} dispatchers, but found {n_dispatchers}."),
-            ));
+            let message = if auto_select {
+                format!(
+                    "Expected {n_priority_groups} dispatchers, but only {n_dispatchers} interrupts usable as dispatchers \
+                    on core {} are not bound to hardware tasks. List the dispatchers with `dispatchers = [...]`.",
+                    sub_app.core
+                )
+            } else {
+                format!("Expected {n_priority_groups} dispatchers, but found {n_dispatchers}.")
+            };
+            return Err(syn::Error::new(Span::call_site(), message));
         }
 
-        // map dispatchers to priorities
-        let mut dispatchers = sub_app.dispatchers.clone();
-        let dispatcher_priorities = sw_tasks_pgroups
-            .keys()
-            .copied()
-            .map(|p| (p, dispatchers.pop().unwrap()))
-            .collect();
+        let dispatcher_priorities =
+            assign_dispatchers(sub_app.core, &dispatchers, &sw_tasks_pgroups)?;
 
         Ok(Self {
             core: sub_app.core,
@@ -108,3 +233,57 @@ impl SubAnalysis {
         })
     }
 }
+
+/// Maps a dispatcher to every priority of `priority_groups`. The dispatchers explicitly assigned to a priority
+/// (`TIM3 = 2`) are used first, then the remaining priorities get the other dispatchers in declaration order, from the
+/// highest priority to the lowest, so that the mapping only depends on the application.
+fn assign_dispatchers(
+    core: u32,
+    dispatchers: &[DispatcherParam],
+    priority_groups: &BTreeMap<u16, Vec<(syn::Ident, u32, usize)>>,
+) -> syn::Result<BTreeMap<u16, syn::Path>> {
+    let mut errors = Vec::new();
+    let mut assigned: BTreeMap<u16, &DispatcherParam> = BTreeMap::new();
+    for dispatcher in dispatchers.iter() {
+        let Some((priority, span)) = dispatcher.priority else {
+            continue;
+        };
+        let interrupt = interrupt_name(&dispatcher.interrupt);
+        if !priority_groups.contains_key(&priority) {
+            errors.push(syn::Error::new(
+                span,
+                format!(
+                    "The dispatcher `{interrupt}` is assigned to priority {priority}, but no software task of core \
+                    {core} has this priority."
+                ),
+            ));
+        } else if let Some(other) = assigned.insert(priority, dispatcher) {
+            let other = interrupt_name(&other.interrupt);
+            errors.push(syn::Error::new(
+                span,
+                format!("Priority {priority} is assigned to both `{other}` and `{interrupt}`."),
+            ));
+        }
+    }
+    if let Some(error) = combine_errors(errors) {
+        return Err(error);
+    }
+
+    let mut unassigned = dispatchers
+        .iter()
+        .filter(|dispatcher| dispatcher.priority.is_none());
+    for priority in priority_groups.keys().rev() {
+        if assigned.contains_key(priority) {
+            continue;
+        }
+        // there are at least as many dispatchers as priority groups
+        let dispatcher = unassigned
+            .next()
+            .expect("the number of dispatchers was checked by the analysis");
+        assigned.insert(*priority, dispatcher);
+    }
+    Ok(assigned
+        .into_iter()
+        .map(|(priority, dispatcher)| (priority, dispatcher.interrupt.clone()))
+        .collect())
+}
@@ -1,14 +1,13 @@
 mod utils;
 
+use crate::SwPassBackend;
 use crate::software_pass::analyze::{Analysis, SubAnalysis};
 use crate::software_pass::parse::ast::SoftwareTask;
 use crate::software_pass::parse::{App, SWT_TRAIT_TY};
-use crate::SwPassBackend;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use rtic_core::multibin;
-use rtic_core::parse_utils::RticAttr;
-use syn::{parse_quote, ItemFn, ItemMod, LitInt, Path};
+use syn::{ItemFn, ItemMod, LitInt, Path, parse_quote};
 
 pub struct CodeGen<'a> {
     app: App,
@@ -103,25 +102,14 @@ impl<'a> CodeGen<'a> {
                 // We will rename the "sw_task" attribute to "task" so that the standard pass recognizes this as a task
                 // also, we will add the `task_trait = RticSwTask` argument.
 
-                // first find the index of the sw_task attribute
-                let attr_idx = task
-                    .task_struct
-                    .attrs
-                    .iter()
-                    .position(|attr| attr.path().is_ident("sw_task"))
-                    .expect("A sw task must have a sw_task attribute");
-
-                // Then remove the old attribute as we will reconstruct it
-                let attr = task.task_struct.attrs.remove(attr_idx);
-
-                // Now we parse and reconstruct the task attribute
-                let mut reconstructed_task_attr = RticAttr::parse_from_attr(&attr).unwrap(); // FIXME: propagate error
-                let _ = reconstructed_task_attr.name.insert(format_ident!("task"));
-                reconstructed_task_attr
+                let mut task_item = task.task.clone();
+                let _ = task_item.attr.name.insert(format_ident!("task"));
+                task_item.attr.elements.shift_remove("capacity");
+                task_item
+                    .attr
                     .elements
                     .insert("task_trait".into(), syn::parse_str(SWT_TRAIT_TY).unwrap());
-
-                let task_struct = &task.task_struct;
+                let task_struct = task_item.into_item();
                 let task_impl = &task.task_impl;
                 // generate the spawn() function for this software task
                 let dispatcher = sub_analysis
@@ -131,7 +119,6 @@ impl<'a> CodeGen<'a> {
                 let spawn_impl = task.generate_spawn_api(dispatcher, pac, self.backend);
 
                 quote! {
-                    #reconstructed_task_attr
                     #task_struct
                     #task_impl
                     #spawn_impl
@@ -170,7 +157,7 @@ fn generate_dispatcher_tasks(sub_analysis: &SubAnalysis) -> TokenStream {
         let prio_ty = utils::priority_ty_ident(*prio, core);
 
         // generate the branches of the match statement for the dispatcher task
-        let dispatch_match_branches = tasks.iter().map(|(task_ident, _)| {
+        let dispatch_match_branches = tasks.iter().map(|(task_ident, ..)| {
             let task_static_handle = utils::ident_uppercase(task_ident);
             let task_inputs_queue = utils::sw_task_inputs_ident(task_ident);
             let prio_ty = &prio_ty;
@@ -184,12 +171,14 @@ fn generate_dispatcher_tasks(sub_analysis: &SubAnalysis) -> TokenStream {
         });
 
         let ready_queue_name = utils::priority_queue_ident(&prio_ty);
-        let ready_queue_size = tasks.len() + 1; // queue size must always be one more than number of tasks
+        // every pending message of every task of the group is in the ready queue, and the queue size must always be one
+        // more than the number of elements it holds
+        let ready_queue_size = tasks.iter().map(|(_, _, capacity)| capacity).sum::<usize>() + 1;
         let dispatcher_irq_name = dispatchers.get(prio).unwrap(); // safe to unwrap due to guarantees from analysis
         let dispatcher_priority = prio;
         let dispatcher_task_ty = utils::dispatcher_ident(*prio, core);
         let core_nbr = LitInt::new(&core.to_string(), Span::call_site());
-        let tasks = tasks.iter().map(|(ident, _span_by)| ident);
+        let tasks = tasks.iter().map(|(ident, _span_by, _)| ident);
 
         quote! {
             #[derive(Clone, Copy)]
@@ -254,6 +243,8 @@ impl SoftwareTask {
         let inputs_ty = quote!(<#task_name as #task_trait_name>::SpawnInput);
         let prio_ty = utils::priority_ty_ident(self.params.priority, self.params.core);
         let ready_queue_name = utils::priority_queue_ident(&prio_ty);
+        // the queue size must always be one more than the number of pending messages
+        let inputs_queue_size = self.params.capacity + 1;
 
         let critical_section_fn = format_ident!("{}", rtic_core::rtic_functions::INTERRUPT_FREE_FN);
         let interrupt_ty = backend
@@ -265,7 +256,7 @@ impl SoftwareTask {
             let pend_fn = format_ident!("{SC_PEND_FN_NAME}");
             quote! {
                 #cfg_core
-                static mut #task_inputs_queue: rtic::export::Queue<#inputs_ty, 2> = rtic::export::Queue::new();
+                static mut #task_inputs_queue: rtic::export::Queue<#inputs_ty, #inputs_queue_size> = rtic::export::Queue::new();
 
                 #cfg_core
                 impl #task_name {
@@ -293,7 +284,7 @@ impl SoftwareTask {
             let core = self.params.core;
             quote! {
                 #multibin_shared
-                static mut #task_inputs_queue: rtic::export::Queue<#inputs_ty, 2> = rtic::export::Queue::new();
+                static mut #task_inputs_queue: rtic::export::Queue<#inputs_ty, #inputs_queue_size> = rtic::export::Queue::new();
 
                 impl #task_name {
                     pub fn spawn_from(_spawner: #spawner_ty , input : #inputs_ty) -> Result<(), #inputs_ty> {
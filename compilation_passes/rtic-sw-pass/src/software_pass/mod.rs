@@ -6,7 +6,13 @@ use crate::parse::App;
 use crate::software_pass::codegen::CodeGen;
 use analyze::Analysis;
 use proc_macro2::TokenStream;
-use rtic_core::RticPass;
+use rtic_core::attr_schema::APP_ATTR;
+use rtic_core::context::DefaultTaskPriority;
+use rtic_core::diagnostics::Phase;
+use rtic_core::model::Dispatchers;
+use rtic_core::{
+    ArgKind, AttrSchema, BackendCapabilities, PassContext, PassDependencies, RticPass,
+};
 use syn::ItemMod;
 
 pub struct SoftwarePass {
@@ -22,9 +28,23 @@ impl SoftwarePass {
 }
 
 impl RticPass for SoftwarePass {
-    fn run_pass(&self, args: TokenStream, app_mod: ItemMod) -> syn::Result<(TokenStream, ItemMod)> {
-        let parsed = App::parse(&args, app_mod)?;
-        let analysis = Analysis::run(&parsed)?;
+    fn run_pass(
+        &self,
+        ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
+        // software tasks without a `priority` get the same default priority as hardware tasks
+        let default_priority = ctx.get::<DefaultTaskPriority>().map_or(0, |p| p.0);
+        let parsed = App::parse(&args, app_mod, default_priority)?;
+        ctx.set_phase(Phase::Analysis);
+        let capabilities = ctx.get::<BackendCapabilities>().unwrap_or_default();
+        let analysis = Analysis::run(&parsed, &capabilities, self.backend.as_ref())?;
+        ctx.update(|dispatchers: &mut Dispatchers| dispatchers.0.extend(analysis.dispatchers()));
+        if parsed.app_params.report {
+            ctx.warning(parsed.mod_ident.span(), analysis.dispatchers_report());
+        }
+        ctx.set_phase(Phase::Codegen);
         let code = CodeGen::new(parsed, analysis, self.backend.as_ref()).run();
         Ok((args, code))
     }
@@ -32,6 +52,45 @@ impl RticPass for SoftwarePass {
     fn pass_name(&self) -> &str {
         "SoftwareTasks"
     }
+
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::new()
+            .consumes([
+                "sw_task",
+                "sw_task.priority",
+                "sw_task.core",
+                "sw_task.spawn_by",
+                "sw_task.capacity",
+            ])
+            // software tasks are turned into hardware tasks, and dispatchers are generated as hardware tasks
+            .produces([
+                "task",
+                "task.binds",
+                "task.priority",
+                "task.core",
+                "task.task_trait",
+            ])
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        // one list of dispatchers for all cores, or one list per core. A dispatcher can be assigned to a priority.
+        let dispatcher = ArgKind::OneOf(vec![
+            ArgKind::Ident,
+            ArgKind::assign(ArgKind::Ident, ArgKind::Int),
+        ]);
+        let dispatchers = ArgKind::OneOf(vec![
+            ArgKind::list(dispatcher.clone()),
+            ArgKind::list(ArgKind::list(dispatcher)),
+        ]);
+        AttrSchema::new()
+            .arg(APP_ATTR, "dispatchers", dispatchers)
+            .arg(APP_ATTR, "dispatchers_report", ArgKind::Bool)
+            .arg("sw_task", "priority", ArgKind::Int)
+            .arg("sw_task", "shared", ArgKind::shared_resources())
+            .arg("sw_task", "core", ArgKind::Int)
+            .arg("sw_task", "spawn_by", ArgKind::Int)
+            .arg("sw_task", "capacity", ArgKind::Int)
+    }
 }
 
 /// Interface for providing the hardware specific details (i.e backend) needed by the software pass
@@ -54,4 +113,12 @@ pub trait SwPassBackend {
     fn custom_interrupt_path(&self, _core: u32) -> Option<syn::Path> {
         None
     }
+
+    /// Use this method to provide the interrupts of `core` that can be used as dispatchers, in order of preference.
+    /// When the application does not list the dispatchers of a core, the software pass picks the ones it needs from
+    /// this list, skipping the interrupts bound by hardware tasks.
+    /// If this method is not implemented, the dispatchers must always be listed by the application
+    fn free_interrupts(&self, _core: u32) -> Vec<syn::Path> {
+        Vec::new()
+    }
 }
@@ -1,84 +1,40 @@
 use proc_macro2::{Span, TokenStream};
-use rtic_core::{errors::ParseError, parse_utils::RticAttr};
+use rtic_core::AppArgs;
+use rtic_core::parse_utils::{RticAttr, RticItem};
 use std::collections::HashMap;
-use syn::{Expr, Ident, ItemImpl, ItemStruct, Lit, Path, spanned::Spanned};
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, ItemImpl, ItemStruct, Lit, Path};
 
 pub struct AppParameters {
-    pub dispatchers: HashMap<u32, Vec<Path>>,
+    pub dispatchers: HashMap<u32, Vec<DispatcherParam>>,
     pub pacs: Vec<Path>,
     pub cores: u32,
+    /// Whether the mapping of the dispatchers to priorities is reported (`dispatchers_report = true`)
+    pub report: bool,
 }
 
 impl AppParameters {
     pub fn parse(args: &TokenStream) -> syn::Result<Self> {
-        let args_span = args.span();
-        let mut args = RticAttr::parse_from_tokens(args.clone())?;
-
-        // parse the number of cores
-        let cores = args.elements.remove("cores");
-        let cores = match cores {
-            Some(Expr::Lit(syn::ExprLit {
-                lit: Lit::Int(lit_int),
-                ..
-            })) => lit_int.base10_parse()?,
-            _ => 1_u32,
-        };
-
-        // parse the path(s) to PAC(s)
-        let device = args
-            .elements
-            .remove("device")
-            .ok_or(ParseError::DeviceArg.to_syn(args_span))?;
-
-        let pacs = match device {
-            Expr::Array(array_exp) => {
-                if array_exp.elems.len() != cores as usize {
-                    return Err(ParseError::DevicesCoresMismatch.to_syn(args_span));
-                }
-
-                let mut devices = Vec::with_capacity(cores as usize);
-                for exp in array_exp.elems {
-                    if let Expr::Path(p) = exp {
-                        devices.push(p.path)
-                    } else {
-                        return Err(ParseError::DeviceNotPath.to_syn(args_span));
-                    }
-                }
-                devices
-            }
-            Expr::Path(path_to_pac) => {
-                let mut devices = Vec::with_capacity(cores as usize);
-                for _ in 0..cores {
-                    devices.push(path_to_pac.path.clone())
-                }
-                devices
-            }
-            _ => return Err(ParseError::DeviceNotPath.to_syn(args_span)),
-        };
+        let AppArgs { pacs, cores, .. } = AppArgs::parse(args.clone())?;
+        let args = RticAttr::parse_from_tokens(args.clone())?;
 
         // dispatchers
         let mut dispatchers = HashMap::with_capacity(cores as usize);
         if let Some(Expr::Array(arr)) = args.elements.get("dispatchers") {
             for (i, element) in arr.elems.iter().enumerate() {
-                if let Expr::Path(path) = element {
-                    dispatchers
-                        .entry(0)
-                        .or_insert(Vec::new())
-                        .push(path.path.clone())
-                } else if let Expr::Array(arr) = element {
+                if let Expr::Array(arr) = element {
                     let core = i;
                     let a = arr
                         .elems
                         .iter()
-                        .map(|element| {
-                            if let Expr::Path(path) = element {
-                                path.path.clone()
-                            } else {
-                                panic!("wrong syntax")
-                            }
-                        })
-                        .collect::<Vec<Path>>();
+                        .map(DispatcherParam::parse)
+                        .collect::<syn::Result<Vec<_>>>()?;
                     dispatchers.insert(core as u32, a);
+                } else {
+                    dispatchers
+                        .entry(0)
+                        .or_insert(Vec::new())
+                        .push(DispatcherParam::parse(element)?)
                 }
             }
         }
@@ -93,24 +49,69 @@ impl AppParameters {
             ));
         }
 
+        let report = matches!(
+            args.elements.get("dispatchers_report"),
+            Some(Expr::Lit(syn::ExprLit { lit: Lit::Bool(report), .. })) if report.value
+        );
+
         Ok(Self {
             dispatchers,
             pacs,
             cores,
+            report,
         })
     }
 }
 
+/// An element of the `dispatchers` list: an interrupt, optionally assigned to a priority (e.g. `TIM3 = 2`)
+#[derive(Debug, Clone)]
+pub struct DispatcherParam {
+    pub interrupt: Path,
+    /// The priority explicitly assigned to the dispatcher, and its span
+    pub priority: Option<(u16, Span)>,
+}
+
+impl DispatcherParam {
+    fn parse(element: &Expr) -> syn::Result<Self> {
+        match element {
+            Expr::Path(path) => Ok(Self {
+                interrupt: path.path.clone(),
+                priority: None,
+            }),
+            Expr::Assign(assign) => match (&*assign.left, &*assign.right) {
+                (
+                    Expr::Path(path),
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Int(int), ..
+                    }),
+                ) => Ok(Self {
+                    interrupt: path.path.clone(),
+                    priority: Some((int.base10_parse()?, int.span())),
+                }),
+                _ => Err(syn::Error::new(
+                    element.span(),
+                    "Expected a dispatcher assigned to a priority, e.g. `TIM3 = 2`",
+                )),
+            },
+            _ => Err(syn::Error::new(
+                element.span(),
+                "Expected an interrupt, or an interrupt assigned to a priority (e.g. `TIM3 = 2`)",
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SoftwareTask {
     pub params: TaskParams,
-    pub task_struct: ItemStruct,
+    /// The task struct with its `#[sw_task]` attribute
+    pub task: RticItem<ItemStruct>,
     pub task_impl: Option<ItemImpl>,
 }
 
 impl SoftwareTask {
     pub fn name(&self) -> &Ident {
-        &self.task_struct.ident
+        self.task.name()
     }
 }
 
@@ -119,11 +120,14 @@ pub struct TaskParams {
     pub priority: u16,
     pub core: u32,
     pub spawn_by: u32,
+    /// Maximum number of pending messages of the task (`capacity = N`), 1 by default
+    pub capacity: usize,
 }
 
 impl TaskParams {
-    pub fn from_attr(attr: &RticAttr) -> Self {
-        let mut priority = 0;
+    /// `default_priority` is used when the task has no `priority` argument
+    pub fn from_attr(attr: &RticAttr, default_priority: u16) -> syn::Result<Self> {
+        let mut priority = default_priority;
         if let Some(Expr::Lit(syn::ExprLit {
             lit: Lit::Int(int), ..
         })) = attr.elements.get("priority")
@@ -147,10 +151,25 @@ impl TaskParams {
             spawn_by = int.base10_parse().unwrap_or_default();
         }
 
-        Self {
+        let mut capacity = 1;
+        if let Some(Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        })) = attr.elements.get("capacity")
+        {
+            capacity = int.base10_parse()?;
+            if capacity == 0 {
+                return Err(syn::Error::new(
+                    int.span(),
+                    "The capacity of a software task must be at least 1.",
+                ));
+            }
+        }
+
+        Ok(Self {
             priority,
             core,
             spawn_by,
-        }
+            capacity,
+        })
     }
 }
@@ -1,8 +1,8 @@
-use crate::parse::ast::{AppParameters, SoftwareTask, TaskParams};
+use crate::parse::ast::{AppParameters, DispatcherParam, SoftwareTask, TaskParams};
 use proc_macro2::{Ident, TokenStream};
-use rtic_core::parse_utils::RticAttr;
+use rtic_core::parse_utils::{AppItem, AppModule};
 use std::collections::HashMap;
-use syn::{Item, ItemImpl, ItemMod, ItemStruct, Type, Visibility};
+use syn::{Expr, Item, ItemImpl, ItemMod, Visibility};
 
 pub mod ast;
 
@@ -11,7 +11,9 @@ pub const SWT_TRAIT_TY: &str = "RticSwTask";
 /// Type to represent a sub application (application on a single core)
 pub struct SubApp {
     pub core: u32,
-    pub dispatchers: Vec<syn::Path>,
+    pub dispatchers: Vec<DispatcherParam>,
+    /// Interrupts bound by the hardware tasks of the core, which can not be used as dispatchers
+    pub bound_interrupts: Vec<syn::Path>,
     /// Single core/ Core-local software tasks
     pub sw_tasks: Vec<SoftwareTask>,
     /// Multi core/ software tasks to be spawned on this core from other cores
@@ -30,44 +32,42 @@ pub struct App {
 }
 
 impl App {
-    pub fn parse(args: &TokenStream, mut app_mod: ItemMod) -> syn::Result<Self> {
+    pub fn parse(args: &TokenStream, app_mod: ItemMod, default_priority: u16) -> syn::Result<Self> {
         let app_params = AppParameters::parse(args)?;
-        let app_mod_items = app_mod.content.take().unwrap_or_default().1;
-        let mut sw_task_structs = Vec::new();
-        let mut sw_task_impls = HashMap::new();
-        let mut rest_of_code = Vec::with_capacity(app_mod_items.len());
+        let mut module = AppModule::parse(app_mod)?;
 
-        for item in app_mod_items {
-            match item {
-                Item::Struct(struct_) => {
-                    if let Some(attr_idx) = Self::is_struct_with_attr(&struct_, "sw_task") {
-                        sw_task_structs.push((struct_, attr_idx))
-                    } else {
-                        rest_of_code.push(Item::Struct(struct_))
-                    }
-                }
-                Item::Impl(impl_) => {
-                    if let Some(implementor) = Self::get_sw_task_implementor(&impl_) {
-                        sw_task_impls.insert(implementor.clone(), impl_);
-                    } else {
-                        rest_of_code.push(Item::Impl(impl_))
-                    }
-                }
-                _ => rest_of_code.push(item),
-            }
-        }
+        // take the software tasks and their trait implementations out of the module, leave the rest as is
+        let sw_task_structs = module.extract_items(|item| matches!(item, AppItem::SwTask(_)));
+        let sw_task_names: Vec<_> = sw_task_structs
+            .iter()
+            .filter_map(|item| match item {
+                AppItem::SwTask(task) => Some(task.name().clone()),
+                _ => None,
+            })
+            .collect();
+        let mut sw_task_impls: HashMap<Ident, ItemImpl> = module
+            .extract_items(|item| {
+                matches!(item, AppItem::TaskImpl(task_impl) if sw_task_names.contains(&task_impl.implementor))
+            })
+            .into_iter()
+            .filter_map(|item| match item {
+                AppItem::TaskImpl(task_impl) => Some((task_impl.implementor, task_impl.item)),
+                _ => None,
+            })
+            .collect();
 
         let cores = app_params.cores;
         let mut sw_tasks = HashMap::with_capacity(cores as usize);
         let mut mc_sw_tasks = HashMap::with_capacity(cores as usize);
-        for (task_struct, attr_idx) in sw_task_structs {
-            let task_impl = sw_task_impls.remove(&task_struct.ident);
-
-            let attrs = RticAttr::parse_from_attr(&task_struct.attrs[attr_idx])?;
-            let params = TaskParams::from_attr(&attrs);
+        for item in sw_task_structs {
+            let AppItem::SwTask(task) = item else {
+                continue;
+            };
+            let task_impl = sw_task_impls.remove(task.name());
+            let params = TaskParams::from_attr(&task.attr, default_priority)?;
             let task = SoftwareTask {
                 params,
-                task_struct,
+                task,
                 task_impl,
             };
 
@@ -84,6 +84,20 @@ impl App {
             }
         }
 
+        let mut bound_interrupts: HashMap<u32, Vec<_>> = HashMap::with_capacity(cores as usize);
+        for item in module.items.iter() {
+            let AppItem::Task(task) = item else {
+                continue;
+            };
+            if let Some(Expr::Path(binds)) = task.attr.elements.get("binds") {
+                let core = TaskParams::from_attr(&task.attr, default_priority)?.core;
+                bound_interrupts
+                    .entry(core)
+                    .or_default()
+                    .push(binds.path.clone());
+            }
+        }
+
         let mut sub_apps = Vec::with_capacity(cores as usize);
         for core in 0..cores {
             let dispatchers = app_params
@@ -94,44 +108,18 @@ impl App {
             sub_apps.push(SubApp {
                 core,
                 dispatchers,
+                bound_interrupts: bound_interrupts.remove(&core).unwrap_or_default(),
                 sw_tasks: sw_tasks.remove(&core).unwrap_or_default(),
                 mc_sw_tasks: mc_sw_tasks.remove(&core).unwrap_or_default(),
             })
         }
 
         Ok(Self {
-            mod_ident: app_mod.ident,
-            mod_visibility: app_mod.vis,
+            mod_ident: module.ident,
+            mod_visibility: module.vis,
             app_params,
             sub_apps,
-            rest_of_code,
+            rest_of_code: module.items.into_iter().map(AppItem::into_item).collect(),
         })
     }
-
-    /// returns the index of the `attr_name` attribute if found in the attribute list of some struct
-    fn is_struct_with_attr(strct: &ItemStruct, attr_name: &str) -> Option<usize> {
-        for (i, attr) in strct.attrs.iter().enumerate() {
-            let path = attr.meta.path();
-            if path.segments.len() == 1 && path.segments[0].ident == attr_name {
-                return Some(i);
-            }
-        }
-        None
-    }
-
-    fn get_sw_task_implementor(impl_item: &ItemImpl) -> Option<&Ident> {
-        if let Some((_, ref path, _)) = impl_item.trait_ {
-            if path.segments.is_empty() {
-                return None;
-            }
-
-            if path.segments[0].ident.to_string().ends_with(SWT_TRAIT_TY) {
-                if let Type::Path(struct_type) = impl_item.self_ty.as_ref() {
-                    let implementor_name = &struct_type.path.segments[0].ident;
-                    return Some(implementor_name);
-                }
-            }
-        }
-        None
-    }
 }
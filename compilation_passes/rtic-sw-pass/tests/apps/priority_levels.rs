@@ -0,0 +1,70 @@
+mod app {
+    #[shared]
+    struct Shared {
+        samples: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { samples: 0 }
+    }
+
+    #[sw_task(priority = 1, shared = [samples])]
+    struct Reporter;
+
+    impl RticSwTask for Reporter {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {
+            self.shared().samples.lock(|samples| report(*samples));
+        }
+    }
+
+    #[sw_task(priority = 3, shared = [samples])]
+    struct Sampler;
+
+    impl RticSwTask for Sampler {
+        type SpawnInput = u32;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, sample: u32) {
+            self.shared().samples.lock(|samples| *samples += sample);
+            let _ = Filter::spawn(sample);
+        }
+    }
+
+    #[sw_task(priority = 2)]
+    struct Filter;
+
+    impl RticSwTask for Filter {
+        type SpawnInput = u32;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: u32) {
+            let _ = Reporter::spawn(());
+        }
+    }
+
+    #[sw_task(priority = 2)]
+    struct Logger;
+
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {}
+    }
+}
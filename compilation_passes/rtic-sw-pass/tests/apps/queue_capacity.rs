@@ -0,0 +1,52 @@
+mod app {
+    #[shared]
+    struct Shared {}
+
+    #[init]
+    fn init() -> Shared {
+        Shared {}
+    }
+
+    #[task(binds = UART0, priority = 2)]
+    struct UartRx;
+
+    impl RticTask for UartRx {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let _ = Parser::spawn(receive());
+        }
+    }
+
+    #[sw_task(priority = 1, capacity = 8)]
+    struct Parser;
+
+    impl RticSwTask for Parser {
+        type SpawnInput = u8;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, byte: u8) {
+            if byte == b'\n' {
+                let _ = Logger::spawn(());
+            }
+        }
+    }
+
+    #[sw_task(priority = 1)]
+    struct Logger;
+
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {}
+    }
+}
@@ -0,0 +1,52 @@
+mod app {
+    #[shared]
+    struct Shared {
+        counter: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    #[task(binds = UART0, priority = 3, shared = [counter])]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let _ = Worker::spawn(1);
+        }
+    }
+
+    #[sw_task(priority = 2, shared = [counter])]
+    struct Worker;
+
+    impl RticSwTask for Worker {
+        type SpawnInput = u32;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, input: u32) {
+            self.shared().counter.lock(|counter| *counter += input);
+        }
+    }
+
+    #[sw_task(priority = 2)]
+    struct Logger;
+
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {}
+    }
+}
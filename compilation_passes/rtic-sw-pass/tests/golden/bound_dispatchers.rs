@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[SoftwareTasks, analysis] at 1:29
+//   The dispatcher `UART0` is also bound to a hardware task of core 0. Use an interrupt that no task binds as dispatcher.
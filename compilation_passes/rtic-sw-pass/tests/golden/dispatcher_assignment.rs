@@ -0,0 +1,294 @@
+#[app(device = pac, dispatchers = [TIM2 = 1, TIM3, TIM4], dispatchers_report = true)]
+mod app {
+    #[shared]
+    struct Shared {
+        samples: u32,
+    }
+    #[init]
+    fn init() -> Shared {
+        Shared { samples: 0 }
+    }
+    /// Software tasks of
+    /// Core 0
+    #[task(priority = 1, shared = [samples], task_trait = RticSwTask)]
+    struct Reporter;
+    impl RticSwTask for Reporter {
+        type SpawnInput = ();
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {
+            self.shared().samples.lock(|samples| report(*samples));
+        }
+    }
+    static mut __rtic_internal__Reporter__INPUTS: rtic::export::Queue<
+        <Reporter as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Reporter {
+        pub fn spawn(
+            input: <Reporter as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Reporter as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Reporter__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Reporter as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Reporter) };
+                __rtic_local_irq_pend(pac::Interrupt::TIM2);
+                Ok(())
+            })
+        }
+    }
+    #[task(priority = 3, shared = [samples], task_trait = RticSwTask)]
+    struct Sampler;
+    impl RticSwTask for Sampler {
+        type SpawnInput = u32;
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, sample: u32) {
+            self.shared().samples.lock(|samples| *samples += sample);
+            let _ = Filter::spawn(sample);
+        }
+    }
+    static mut __rtic_internal__Sampler__INPUTS: rtic::export::Queue<
+        <Sampler as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Sampler {
+        pub fn spawn(
+            input: <Sampler as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Sampler as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Sampler__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio3Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Sampler as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio3Tasks::Sampler) };
+                __rtic_local_irq_pend(pac::Interrupt::TIM3);
+                Ok(())
+            })
+        }
+    }
+    #[task(priority = 2, task_trait = RticSwTask)]
+    struct Filter;
+    impl RticSwTask for Filter {
+        type SpawnInput = u32;
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, _: u32) {
+            let _ = Reporter::spawn(());
+        }
+    }
+    static mut __rtic_internal__Filter__INPUTS: rtic::export::Queue<
+        <Filter as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Filter {
+        pub fn spawn(
+            input: <Filter as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Filter as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Filter__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Filter as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Filter) };
+                __rtic_local_irq_pend(pac::Interrupt::TIM4);
+                Ok(())
+            })
+        }
+    }
+    #[task(priority = 2, task_trait = RticSwTask)]
+    struct Logger;
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {}
+    }
+    static mut __rtic_internal__Logger__INPUTS: rtic::export::Queue<
+        <Logger as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Logger {
+        pub fn spawn(
+            input: <Logger as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Logger__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Logger) };
+                __rtic_local_irq_pend(pac::Interrupt::TIM4);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio1Tasks {
+        Reporter,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio1Tasks__RQ: rtic::export::Queue<
+        Core0Prio1Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[doc(hidden)]
+    #[task(binds = TIM2, priority = 1u16, core = 0)]
+    pub struct Core0Priority1Dispatcher;
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio1Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio1Tasks::Reporter => {
+                            let mut input_consumer = __rtic_internal__Reporter__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            REPORTER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio2Tasks {
+        Filter,
+        Logger,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio2Tasks__RQ: rtic::export::Queue<
+        Core0Prio2Tasks,
+        3usize,
+    > = rtic::export::Queue::new();
+    #[doc(hidden)]
+    #[task(binds = TIM4, priority = 2u16, core = 0)]
+    pub struct Core0Priority2Dispatcher;
+    impl RticTask for Core0Priority2Dispatcher {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio2Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio2Tasks::Filter => {
+                            let mut input_consumer = __rtic_internal__Filter__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            FILTER.assume_init_mut().exec(input);
+                        }
+                        Core0Prio2Tasks::Logger => {
+                            let mut input_consumer = __rtic_internal__Logger__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            LOGGER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio3Tasks {
+        Sampler,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio3Tasks__RQ: rtic::export::Queue<
+        Core0Prio3Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[doc(hidden)]
+    #[task(binds = TIM3, priority = 3u16, core = 0)]
+    pub struct Core0Priority3Dispatcher;
+    impl RticTask for Core0Priority3Dispatcher {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio3Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio3Tasks::Sampler => {
+                            let mut input_consumer = __rtic_internal__Sampler__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            SAMPLER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        rtic::export::cross_core_pend(irq_nbr);
+    }
+}
+
+// warning[SoftwareTasks, analysis] at 1:4
+//   Dispatchers of the software tasks
+//   core 0:
+//     TIM3: priority 3, tasks Sampler
+//     TIM4: priority 2, tasks Filter, Logger
+//     TIM2: priority 1, tasks Reporter
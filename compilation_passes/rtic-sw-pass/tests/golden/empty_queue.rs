@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[SoftwareTasks, parse] at 23:39
+//   The capacity of a software task must be at least 1.
@@ -0,0 +1,163 @@
+#[app(device = pac, dispatchers_report = true)]
+mod app {
+    #[shared]
+    struct Shared {
+        counter: u32,
+    }
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+    #[task(binds = UART0, priority = 3, shared = [counter])]
+    struct Uart0Task;
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            let _ = Worker::spawn(1);
+        }
+    }
+    /// Software tasks of
+    /// Core 0
+    #[task(priority = 2, shared = [counter], task_trait = RticSwTask)]
+    struct Worker;
+    impl RticSwTask for Worker {
+        type SpawnInput = u32;
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, input: u32) {
+            self.shared().counter.lock(|counter| *counter += input);
+        }
+    }
+    static mut __rtic_internal__Worker__INPUTS: rtic::export::Queue<
+        <Worker as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Worker {
+        pub fn spawn(
+            input: <Worker as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Worker as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Worker__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Worker as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Worker) };
+                __rtic_local_irq_pend(pac::Interrupt::UART1);
+                Ok(())
+            })
+        }
+    }
+    #[task(priority = 2, task_trait = RticSwTask)]
+    struct Logger;
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {}
+    }
+    static mut __rtic_internal__Logger__INPUTS: rtic::export::Queue<
+        <Logger as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Logger {
+        pub fn spawn(
+            input: <Logger as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Logger__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Logger) };
+                __rtic_local_irq_pend(pac::Interrupt::UART1);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio2Tasks {
+        Worker,
+        Logger,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio2Tasks__RQ: rtic::export::Queue<
+        Core0Prio2Tasks,
+        3usize,
+    > = rtic::export::Queue::new();
+    #[doc(hidden)]
+    #[task(binds = UART1, priority = 2u16, core = 0)]
+    pub struct Core0Priority2Dispatcher;
+    impl RticTask for Core0Priority2Dispatcher {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio2Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio2Tasks::Worker => {
+                            let mut input_consumer = __rtic_internal__Worker__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            WORKER.assume_init_mut().exec(input);
+                        }
+                        Core0Prio2Tasks::Logger => {
+                            let mut input_consumer = __rtic_internal__Logger__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            LOGGER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        rtic::export::cross_core_pend(irq_nbr);
+    }
+}
+
+// warning[SoftwareTasks, analysis] at 1:4
+//   Dispatchers of the software tasks
+//   core 0:
+//     UART1: priority 2, tasks Worker, Logger
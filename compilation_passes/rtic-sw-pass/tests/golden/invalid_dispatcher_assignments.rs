@@ -0,0 +1,7 @@
+// no code was generated
+
+// error[SoftwareTasks, analysis] at 1:36
+//   The dispatcher `TIM2` is assigned to priority 5, but no software task of core 0 has this priority.
+
+// error[SoftwareTasks, analysis] at 1:56
+//   Priority 1 is assigned to both `TIM3` and `TIM4`.
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[SoftwareTasks, analysis] at 1:0
+//   Expected 1 dispatchers, but found 0.
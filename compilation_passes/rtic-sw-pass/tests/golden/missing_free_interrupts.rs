@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[SoftwareTasks, analysis] at 1:0
+//   Expected 3 dispatchers, but only 2 interrupts usable as dispatchers on core 0 are not bound to hardware tasks. List the dispatchers with `dispatchers = [...]`.
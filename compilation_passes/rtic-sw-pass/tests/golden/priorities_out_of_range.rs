@@ -0,0 +1,10 @@
+// no code was generated
+
+// error[core, analysis] at 12:37
+//   The task `Uart0Task` has priority 3, but the priorities of this target range from 0 (lowest) to 1 (highest).
+
+// error[core, analysis] at 25:25
+//   The task `Worker` has priority 2, but the priorities of this target range from 0 (lowest) to 1 (highest).
+
+// error[core, analysis] at 40:25
+//   The task `Logger` has priority 2, but the priorities of this target range from 0 (lowest) to 1 (highest).
@@ -0,0 +1,158 @@
+#[app(device = pac, dispatchers = [UART1])]
+mod app {
+    #[shared]
+    struct Shared {}
+    #[init]
+    fn init() -> Shared {
+        Shared {}
+    }
+    #[task(binds = UART0, priority = 2)]
+    struct UartRx;
+    impl RticTask for UartRx {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            let _ = Parser::spawn(receive());
+        }
+    }
+    /// Software tasks of
+    /// Core 0
+    #[task(priority = 1, task_trait = RticSwTask)]
+    struct Parser;
+    impl RticSwTask for Parser {
+        type SpawnInput = u8;
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, byte: u8) {
+            if byte == b'\n' {
+                let _ = Logger::spawn(());
+            }
+        }
+    }
+    static mut __rtic_internal__Parser__INPUTS: rtic::export::Queue<
+        <Parser as RticSwTask>::SpawnInput,
+        9usize,
+    > = rtic::export::Queue::new();
+    impl Parser {
+        pub fn spawn(
+            input: <Parser as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Parser as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Parser__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Parser as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Parser) };
+                __rtic_local_irq_pend(pac::Interrupt::UART1);
+                Ok(())
+            })
+        }
+    }
+    #[task(priority = 1, task_trait = RticSwTask)]
+    struct Logger;
+    impl RticSwTask for Logger {
+        type SpawnInput = ();
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {}
+    }
+    static mut __rtic_internal__Logger__INPUTS: rtic::export::Queue<
+        <Logger as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Logger {
+        pub fn spawn(
+            input: <Logger as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Logger__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Logger) };
+                __rtic_local_irq_pend(pac::Interrupt::UART1);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio1Tasks {
+        Parser,
+        Logger,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio1Tasks__RQ: rtic::export::Queue<
+        Core0Prio1Tasks,
+        10usize,
+    > = rtic::export::Queue::new();
+    #[doc(hidden)]
+    #[task(binds = UART1, priority = 1u16, core = 0)]
+    pub struct Core0Priority1Dispatcher;
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio1Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio1Tasks::Parser => {
+                            let mut input_consumer = __rtic_internal__Parser__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            PARSER.assume_init_mut().exec(input);
+                        }
+                        Core0Prio1Tasks::Logger => {
+                            let mut input_consumer = __rtic_internal__Logger__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            LOGGER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        rtic::export::cross_core_pend(irq_nbr);
+    }
+}
@@ -0,0 +1,155 @@
+use rtic_core::{BackendCapabilities, RticMacroBuilder};
+use rtic_sw_pass::SoftwarePass;
+use rtic_testkit::{
+    MockBackend, MockSwBackend, assert_golden, expand_app, expand_app_model, expand_pass,
+};
+use syn::parse_quote;
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn single_core_dispatcher() {
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac, dispatchers = [UART1]",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("single_core_dispatcher", &output);
+}
+
+#[test]
+fn rejects_missing_dispatchers() {
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("missing_dispatchers", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn sizes_the_queues_from_the_task_capacities() {
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac, dispatchers = [UART1]",
+        include_str!("apps/queue_capacity.rs"),
+    );
+    assert_golden("queue_capacity", &output);
+}
+
+#[test]
+fn rejects_empty_queues() {
+    let app = include_str!("apps/queue_capacity.rs").replace("capacity = 8", "capacity = 0");
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac, dispatchers = [UART1]",
+        &app,
+    );
+    assert_golden("empty_queue", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn assigns_dispatchers_to_priorities_deterministically() {
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac, dispatchers = [TIM2 = 1, TIM3, TIM4], dispatchers_report = true",
+        include_str!("apps/priority_levels.rs"),
+    );
+    assert_golden("dispatcher_assignment", &output);
+}
+
+#[test]
+fn rejects_invalid_dispatcher_assignments() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder.bind_pre_core_pass(SoftwarePass::new(MockSwBackend::default()));
+    let output = expand_app(
+        builder,
+        "device = pac, dispatchers = [TIM2 = 5, TIM3 = 1, TIM4 = 1, TIM5]",
+        include_str!("apps/priority_levels.rs"),
+    );
+    assert_golden("invalid_dispatcher_assignments", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn selects_free_interrupts_as_dispatchers() {
+    let backend = MockSwBackend {
+        free_interrupts: vec![
+            parse_quote!(UART0),
+            parse_quote!(UART1),
+            parse_quote!(UART2),
+        ],
+    };
+    let output = expand_pass(
+        &SoftwarePass::new(backend),
+        "device = pac, dispatchers_report = true",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("free_interrupt_dispatchers", &output);
+}
+
+#[test]
+fn rejects_applications_needing_more_free_interrupts() {
+    let backend = MockSwBackend {
+        free_interrupts: vec![parse_quote!(TIM2), parse_quote!(TIM3)],
+    };
+    let output = expand_pass(
+        &SoftwarePass::new(backend),
+        "device = pac",
+        include_str!("apps/priority_levels.rs"),
+    );
+    assert_golden("missing_free_interrupts", &output);
+}
+
+#[test]
+fn publishes_dispatchers_in_the_application_model() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder.bind_pre_core_pass(SoftwarePass::new(MockSwBackend::default()));
+    let model = expand_app_model(
+        builder,
+        "device = pac, dispatchers = [UART1]",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("single_core_model.json", &model);
+}
+
+#[test]
+fn rejects_priorities_out_of_the_target_range() {
+    let mut builder = RticMacroBuilder::new(MockBackend {
+        capabilities: BackendCapabilities {
+            priorities: 0..=1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    builder.bind_pre_core_pass(SoftwarePass::new(MockSwBackend::default()));
+    let output = expand_app(
+        builder,
+        "device = pac, dispatchers = [UART1]",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("priorities_out_of_range", &output);
+}
+
+#[test]
+fn rejects_dispatchers_bound_to_hardware_tasks() {
+    let output = expand_pass(
+        &SoftwarePass::new(MockSwBackend::default()),
+        "device = pac, dispatchers = [UART0]",
+        include_str!("apps/single_core.rs"),
+    );
+    assert_golden("bound_dispatchers", &output);
+}
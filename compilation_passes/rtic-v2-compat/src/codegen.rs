@@ -0,0 +1,270 @@
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use rtic_core::parse_utils::{AppItem, AppModule, RticAttr, RticItem, TaskImpl};
+use rtic_core::rtic_traits::{HWT_TRAIT_TY, IDLE_TRAIT_TY, SWT_TRAIT_TY};
+use syn::{Expr, Ident, Item, ItemFn, Pat, parse_quote};
+
+use crate::parse::{TaskKind, V2App, V2Init, V2Task};
+
+/// Replaces the RTIC v2 tasks and `#[init]` of `module` by their equivalent in the struct + task trait syntax, in place
+pub fn expand(app: V2App, module: &mut AppModule) {
+    // tasks with local resources are moved out of the local resources struct returned by the RTIC v2 init
+    let late_inits: Vec<_> = app
+        .tasks
+        .iter()
+        .filter(|(_, task)| !task.locals.is_empty())
+        .map(|(_, task)| {
+            let ty = task.struct_name();
+            let field = format_ident!("{}", ty.to_string().to_snake_case());
+            let locals = task.locals.iter().map(|local| &local.ident);
+            quote!(#field: #ty { #(#locals: __rtic_local.#locals,)* })
+        })
+        .collect();
+
+    let mut tasks = app.tasks.into_iter().peekable();
+    for (position, item) in std::mem::take(&mut module.items).into_iter().enumerate() {
+        match (item, &app.init) {
+            (AppItem::Init(init), Some(v2_init)) => {
+                let items = expand_init(init, v2_init, app.peripherals.as_ref(), &late_inits);
+                module.items.extend(items);
+            }
+            (_, _) if tasks.peek().is_some_and(|(p, _)| *p == position) => {
+                let (_, task) = tasks.next().unwrap();
+                module.items.extend(expand_task(task));
+            }
+            (item, _) => module.items.push(item),
+        }
+    }
+}
+
+impl V2Task {
+    /// Name of the struct the task function is turned into, e.g. `CommandReceiver` for `command_receiver`
+    fn struct_name(&self) -> Ident {
+        let name = &self.function.sig.ident;
+        format_ident!(
+            "{}",
+            name.to_string().to_upper_camel_case(),
+            span = name.span()
+        )
+    }
+
+    fn uses_shared_resources(&self) -> bool {
+        matches!(self.attr.elements.get("shared"), Some(Expr::Array(array)) if !array.elems.is_empty())
+    }
+}
+
+fn expand_init(
+    mut init: RticItem<ItemFn>,
+    v2_init: &V2Init,
+    peripherals: Option<&syn::Path>,
+    late_inits: &[TokenStream],
+) -> Vec<AppItem> {
+    // the RTIC v2 init is kept as is, and called from the init function of this project
+    let mut v2_init_fn = init.item.clone();
+    v2_init_fn.attrs.clear();
+    v2_init_fn.sig.ident = format_ident!("__rtic_v2_init");
+
+    let shared_ty = &v2_init.shared_ty;
+    let (inputs, context, context_fields) = match peripherals {
+        Some(pac) => (
+            quote!(device: #pac::Peripherals),
+            quote!(init::Context { device }),
+            quote!(pub(super) device: #pac::Peripherals,),
+        ),
+        None => (quote!(), quote!(init::Context {}), quote!()),
+    };
+    let (output, local, result) = if late_inits.is_empty() {
+        (quote!(#shared_ty), quote!(_), quote!(__rtic_shared))
+    } else {
+        (
+            quote!((#shared_ty, TaskInits)),
+            quote!(__rtic_local),
+            quote!((__rtic_shared, TaskInits { #(#late_inits,)* })),
+        )
+    };
+    let init_name = &init.item.sig.ident;
+    init.item.sig.inputs = parse_quote!(#inputs);
+    init.item.sig.output = parse_quote!(-> #output);
+    init.item.block = parse_quote!({
+        #v2_init_fn
+        let (__rtic_shared, #local) = __rtic_v2_init(#context);
+        #result
+    });
+
+    let use_super = peripherals.map(|_| {
+        quote!(
+            use super::*;
+        )
+    });
+    let doc = format!(" RTIC v2 context of `{init_name}`");
+    let context_mod: Item = parse_quote! {
+        #[doc = #doc]
+        mod init {
+            #use_super
+            #[allow(dead_code)]
+            pub(super) struct Context {
+                #context_fields
+            }
+        }
+    };
+    vec![AppItem::Init(init), AppItem::Other(context_mod)]
+}
+
+fn expand_task(task: V2Task) -> Vec<AppItem> {
+    let name = task.function.sig.ident.clone();
+    let ty = task.struct_name();
+    let (docs, attrs): (Vec<_>, Vec<_>) = task
+        .function
+        .attrs
+        .iter()
+        .partition(|attr| attr.path().is_ident("doc"));
+    let vis = &task.function.vis;
+
+    // the local resources become fields of the task struct
+    let local_names: Vec<_> = task.locals.iter().map(|local| &local.ident).collect();
+    let local_types: Vec<_> = task.locals.iter().map(|local| &local.ty).collect();
+    let task_struct = if task.locals.is_empty() {
+        parse_quote!(#(#docs)* #vis struct #ty;)
+    } else {
+        let fields = task.locals.iter().map(|local| {
+            let (attrs, ident, ty) = (&local.attrs, &local.ident, &local.ty);
+            quote!(#(#attrs)* #ident: #ty)
+        });
+        parse_quote!(#(#docs)* #vis struct #ty { #(#fields,)* })
+    };
+
+    let (attr_name, trait_name) = match task.kind {
+        TaskKind::Hardware => ("task", HWT_TRAIT_TY),
+        TaskKind::Software => ("sw_task", SWT_TRAIT_TY),
+        TaskKind::Idle => ("idle", IDLE_TRAIT_TY),
+    };
+    let attr = RticAttr {
+        name: Some(format_ident!("{attr_name}")),
+        elements: task.attr.elements.clone(),
+    };
+    let task_trait = format_ident!("{trait_name}");
+
+    let init_fn = if task.locals.is_empty() {
+        quote!(
+            fn init() -> Self {
+                Self
+            }
+        )
+    } else {
+        // the task is built in #[init], from the local resources
+        quote! {
+            type InitArgs = Self;
+            fn init(task: Self) -> Self { task }
+        }
+    };
+
+    let shared = if task.uses_shared_resources() {
+        quote!(self.shared())
+    } else {
+        quote!(())
+    };
+    let local = if task.locals.is_empty() {
+        quote!(())
+    } else {
+        quote!(#name::LocalResources { #(#local_names: &mut self.#local_names,)* })
+    };
+
+    // software tasks take the arguments after their context as spawn input
+    let inputs: Vec<_> = task.inputs().collect();
+    let input_pats: Vec<_> = inputs.iter().map(|input| &input.pat).collect();
+    let input_types: Vec<_> = inputs.iter().map(|input| &input.ty).collect();
+    let (spawn_input, exec_input) = match inputs.len() {
+        0 => (quote!(()), quote!(_: ())),
+        1 => (quote!(#(#input_types)*), quote!(#(#inputs)*)),
+        _ => (
+            quote!((#(#input_types),*)),
+            quote!((#(#input_pats),*): (#(#input_types),*)),
+        ),
+    };
+    let (spawn_input_ty, exec_input) = match task.kind {
+        TaskKind::Software => (
+            Some(quote!(type SpawnInput = #spawn_input;)),
+            Some(quote!(, #exec_input)),
+        ),
+        _ => (None, None),
+    };
+
+    let context = &task.context;
+    let output = &task.function.sig.output;
+    let stmts = &task.function.block.stmts;
+    let task_impl: syn::ItemImpl = parse_quote! {
+        impl #task_trait for #ty {
+            #spawn_input_ty
+            #init_fn
+
+            #(#attrs)*
+            fn exec(&mut self #exec_input) #output {
+                let #context = #name::Context { shared: #shared, local: #local };
+                #(#stmts)*
+            }
+        }
+    };
+
+    let local_resources = (!task.locals.is_empty()).then(|| {
+        quote! {
+            #[allow(dead_code)]
+            pub(super) struct LocalResources<'a> {
+                #(pub(super) #local_names: &'a mut #local_types,)*
+            }
+        }
+    });
+    let spawn = (task.kind == TaskKind::Software).then(|| {
+        let args: Vec<_> = input_pats
+            .iter()
+            .enumerate()
+            .map(|(i, pat)| match pat.as_ref() {
+                Pat::Ident(pat) => pat.ident.clone(),
+                _ => format_ident!("arg{i}"),
+            })
+            .collect();
+        let input = match args.len() {
+            1 => quote!(#(#args)*),
+            _ => quote!((#(#args),*)),
+        };
+        quote! {
+            pub(super) fn spawn(#(#args: #input_types),*) -> Result<(), #spawn_input> {
+                super::#ty::spawn(#input)
+            }
+        }
+    });
+    let use_super = (local_resources.is_some() || !inputs.is_empty()).then(|| {
+        quote!(
+            use super::*;
+        )
+    });
+    let doc = format!(" RTIC v2 context of `{name}`");
+    let context_mod: Item = parse_quote! {
+        #[doc = #doc]
+        mod #name {
+            #use_super
+            #local_resources
+            #[allow(dead_code)]
+            pub(super) struct Context<S, L> {
+                pub(super) shared: S,
+                pub(super) local: L,
+            }
+            #spawn
+        }
+    };
+
+    let task_item = RticItem::new(attr, task_struct);
+    let task_item = match task.kind {
+        TaskKind::Hardware => AppItem::Task(task_item),
+        TaskKind::Software => AppItem::SwTask(task_item),
+        TaskKind::Idle => AppItem::Idle(task_item),
+    };
+    vec![
+        task_item,
+        AppItem::TaskImpl(TaskImpl {
+            implementor: ty,
+            item: task_impl,
+        }),
+        AppItem::Other(context_mod),
+    ]
+}
@@ -0,0 +1,75 @@
+//! A **Compilation Pass** that accepts the function-style task syntax of RTIC v2 and rewrites it into the struct +
+//! task trait form understood by the other passes and the **Core Pass**, so that existing RTIC v2 applications can be
+//! compiled by a distribution with minimal edits.
+//!
+//! The pass rewrites:
+//! - `#[task(binds = X, shared = [..], local = [..])] fn foo(cx: foo::Context)` into a `#[task]` struct `Foo` that
+//!   implements `RticTask`. The local resources of the task become fields of the struct.
+//! - `#[task(shared = [..], local = [..])] async fn foo(cx: foo::Context, a: A, ..)` into a `#[sw_task]` struct `Foo`
+//!   that implements `RticSwTask`, with the arguments after the context as spawn input. `foo::spawn(a, ..)` is kept
+//!   as a wrapper of `Foo::spawn((a, ..))`.
+//! - `#[idle(shared = [..], local = [..])] fn idle(cx: idle::Context) -> !` into an `#[idle]` struct that implements
+//!   `RticIdleTask`.
+//! - `#[init] fn init(cx: init::Context) -> (Shared, Local)` into an `#[init]` function that moves the fields of the
+//!   `#[local]` struct into the tasks using them (see `TaskInits`). `cx.device` holds the device peripherals when the
+//!   application uses `peripherals = true`.
+//!
+//! Inside the tasks, `cx.shared.<resource>` and `cx.local.<resource>` are available as in RTIC v2, `cx.local`
+//! giving `&mut` access to the local resources of the task.
+//!
+//! Limitations: software tasks run to completion (`.await` is not supported), local resources must be declared in the
+//! `#[local]` struct (`local = [x: T = value]` is not supported), and `cx.core` is not provided.
+
+mod codegen;
+mod parse;
+
+use proc_macro2::TokenStream;
+use rtic_core::diagnostics::Phase;
+use rtic_core::parse_utils::{AppModule, RticAttr};
+use rtic_core::{ArgKind, AttrSchema, PassContext, PassDependencies, RticPass};
+use syn::ItemMod;
+
+pub struct V2CompatPass;
+
+impl RticPass for V2CompatPass {
+    fn run_pass(
+        &self,
+        ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
+        let params = RticAttr::parse_from_tokens(args.clone())?;
+        let mut module = AppModule::parse(app_mod)?;
+        let app = parse::V2App::parse(&params, &mut module)?;
+        ctx.set_phase(Phase::Codegen);
+        codegen::expand(app, &mut module);
+        Ok((args, module.into_item_mod()))
+    }
+
+    fn pass_name(&self) -> &str {
+        "V2Compat"
+    }
+
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::new()
+            .consumes(["local", "task.local", "idle.local"])
+            .produces([
+                "task",
+                "task.binds",
+                "task.priority",
+                "task.shared",
+                "sw_task",
+                "sw_task.priority",
+                "sw_task.shared",
+                "idle",
+                "idle.shared",
+            ])
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        AttrSchema::new()
+            .attribute("local")
+            .arg("task", "local", ArgKind::list(ArgKind::Ident))
+            .arg("idle", "local", ArgKind::list(ArgKind::Ident))
+    }
+}
@@ -0,0 +1,317 @@
+use proc_macro2::Span;
+use rtic_core::diagnostics::combine_errors;
+use rtic_core::parse_utils::{AppItem, AppModule, RticAttr};
+use syn::{Expr, ExprLit, Field, FnArg, Ident, Item, ItemFn, Lit, Pat, Type, spanned::Spanned};
+
+/// An RTIC v2 application (within the v2 compatibility pass context)
+pub struct V2App {
+    /// Path to the PAC when the device peripherals are passed to `#[init]`
+    pub peripherals: Option<syn::Path>,
+    /// `#[init]`, when it has the RTIC v2 signature
+    pub init: Option<V2Init>,
+    /// Function-style tasks, with the position of their function in the items of the module
+    pub tasks: Vec<(usize, V2Task)>,
+}
+
+pub struct V2Init {
+    /// Type of the shared resources returned by `#[init]` next to the local resources
+    pub shared_ty: Type,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskKind {
+    /// `#[task(binds = ...)] fn`
+    Hardware,
+    /// `#[task] async fn`, spawned by software
+    Software,
+    /// `#[idle] fn`
+    Idle,
+}
+
+pub struct V2Task {
+    pub kind: TaskKind,
+    /// The task attribute, without the `local` argument
+    pub attr: RticAttr,
+    /// Fields of the `#[local]` struct used by the task
+    pub locals: Vec<Field>,
+    /// Pattern the task context is bound to, e.g. `mut cx`
+    pub context: Box<Pat>,
+    /// The task function, without its RTIC attribute
+    pub function: ItemFn,
+}
+
+impl V2App {
+    pub fn parse(params: &RticAttr, module: &mut AppModule) -> syn::Result<Self> {
+        let mut errors = Vec::new();
+        let local_fields = take_local_struct(module)?;
+
+        let mut tasks = Vec::new();
+        for (position, item) in module.items.iter().enumerate() {
+            let AppItem::Other(Item::Fn(function)) = item else {
+                continue;
+            };
+            match V2Task::parse(function) {
+                Ok(Some((task, locals))) => tasks.push((position, task, locals)),
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let init = module
+            .inits()
+            .find_map(|init| V2Init::parse(&init.item).transpose());
+        let init = match init {
+            Some(Ok(init)) => Some(init),
+            Some(Err(e)) => {
+                errors.push(e);
+                None
+            }
+            None => None,
+        };
+
+        // every local resource is moved into exactly one task
+        let mut used: Vec<(Ident, Ident)> = Vec::new();
+        let mut resolved_tasks = Vec::with_capacity(tasks.len());
+        for (position, mut task, locals) in tasks {
+            for local in locals {
+                if init.is_none() {
+                    errors.push(syn::Error::new(
+                        local.span(),
+                        "Local resources require an RTIC v2 #[init] function: `fn init(cx: init::Context) -> (Shared, Local)`.",
+                    ));
+                    continue;
+                }
+                let Some(field) = local_fields
+                    .iter()
+                    .find(|field| field.ident.as_ref() == Some(&local))
+                else {
+                    errors.push(syn::Error::new(
+                        local.span(),
+                        format!(
+                            "The local resource `{local}` is not declared in the #[local] struct."
+                        ),
+                    ));
+                    continue;
+                };
+                match used.iter().find(|(resource, _)| *resource == local) {
+                    Some((_, other)) => errors.push(syn::Error::new(
+                        local.span(),
+                        format!(
+                            "The local resource `{local}` is already used by the task `{other}`, local resources belong to a single task."
+                        ),
+                    )),
+                    None => {
+                        used.push((local, task.function.sig.ident.clone()));
+                        task.locals.push(field.clone());
+                    }
+                }
+            }
+            resolved_tasks.push((position, task));
+        }
+
+        let peripherals = match init {
+            Some(_) if peripherals_enabled(params) => match params.elements.get("device") {
+                Some(Expr::Path(path)) => Some(path.path.clone()),
+                device => {
+                    let span = device.map_or_else(Span::call_site, |d| d.span());
+                    errors.push(syn::Error::new(
+                        span,
+                        "`device = path::to::pac` is required to pass the device peripherals to #[init].",
+                    ));
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        match combine_errors(errors) {
+            Some(error) => Err(error),
+            None => Ok(Self {
+                peripherals,
+                init,
+                tasks: resolved_tasks,
+            }),
+        }
+    }
+}
+
+impl V2Init {
+    /// Returns `None` when `init` does not take an `init::Context`, i.e. it already has the syntax of this project
+    fn parse(init: &ItemFn) -> syn::Result<Option<Self>> {
+        let takes_context = match init.sig.inputs.first() {
+            Some(FnArg::Typed(arg)) => is_context_of(&arg.ty, "init"),
+            _ => false,
+        };
+        if !takes_context {
+            return Ok(None);
+        }
+        if init.sig.inputs.len() != 1 {
+            return Err(syn::Error::new(
+                init.sig.inputs.span(),
+                "The RTIC v2 #[init] function takes its context as only argument: `fn init(cx: init::Context)`.",
+            ));
+        }
+        match &init.sig.output {
+            syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+                Type::Tuple(tuple) if tuple.elems.len() == 2 => Ok(Some(Self {
+                    shared_ty: tuple.elems[0].clone(),
+                })),
+                ty => Err(syn::Error::new(
+                    ty.span(),
+                    "The RTIC v2 #[init] function must return the shared and local resources: `(Shared, Local)`.",
+                )),
+            },
+            output => Err(syn::Error::new(
+                output.span(),
+                "The RTIC v2 #[init] function must return the shared and local resources: `-> (Shared, Local)`.",
+            )),
+        }
+    }
+}
+
+impl V2Task {
+    /// Returns `None` when `function` is not a task, otherwise the task and the names of its local resources
+    fn parse(function: &ItemFn) -> syn::Result<Option<(Self, Vec<Ident>)>> {
+        let Some(attr_idx) = function
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("task") || attr.path().is_ident("idle"))
+        else {
+            return Ok(None);
+        };
+        let mut function = function.clone();
+        let mut attr = RticAttr::parse_from_attr(&function.attrs.remove(attr_idx))?;
+        let name = &function.sig.ident;
+
+        let kind = match attr.name.as_ref() {
+            Some(attr_name) if attr_name == "idle" => TaskKind::Idle,
+            _ if attr.elements.contains_key("binds") => TaskKind::Hardware,
+            _ => TaskKind::Software,
+        };
+
+        let locals = match attr.elements.shift_remove("local") {
+            Some(Expr::Array(array)) => array
+                .elems
+                .iter()
+                .map(|element| match element {
+                    Expr::Path(path) if path.path.get_ident().is_some() => {
+                        Ok(path.path.get_ident().unwrap().clone())
+                    }
+                    element => Err(syn::Error::new(
+                        element.span(),
+                        "expected the name of a field of the #[local] struct",
+                    )),
+                })
+                .collect::<syn::Result<_>>()?,
+            Some(other) => {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "expected a list of local resources, e.g. `local = [led, uart]`",
+                ));
+            }
+            None => Vec::new(),
+        };
+
+        let signature_error = || {
+            let extra_args = match kind {
+                TaskKind::Software => ", ..",
+                _ => "",
+            };
+            syn::Error::new(
+                function.sig.span(),
+                format!(
+                    "The task `{name}` must take its context as first argument: `fn {name}(cx: {name}::Context{extra_args})`."
+                ),
+            )
+        };
+        let context = match function.sig.inputs.first() {
+            Some(FnArg::Typed(arg)) => arg.pat.clone(),
+            _ => return Err(signature_error()),
+        };
+        if kind != TaskKind::Software && function.sig.inputs.len() != 1 {
+            return Err(signature_error());
+        }
+
+        let task = Self {
+            kind,
+            attr,
+            locals: Vec::new(),
+            context,
+            function,
+        };
+        Ok(Some((task, locals)))
+    }
+
+    /// Arguments of a software task after its context, i.e. its spawn input
+    pub fn inputs(&self) -> impl Iterator<Item = &syn::PatType> {
+        self.function
+            .sig
+            .inputs
+            .iter()
+            .skip(1)
+            .filter_map(|arg| match arg {
+                FnArg::Typed(arg) => Some(arg),
+                FnArg::Receiver(_) => None,
+            })
+    }
+}
+
+/// Removes the `#[local]` attribute from the local resources struct, which is kept as a plain struct, and returns its
+/// fields
+fn take_local_struct(module: &mut AppModule) -> syn::Result<Vec<Field>> {
+    let mut fields: Option<(Ident, Vec<Field>)> = None;
+    for item in module.items.iter_mut() {
+        let AppItem::Other(Item::Struct(strct)) = item else {
+            continue;
+        };
+        let Some(attr_idx) = strct
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("local"))
+        else {
+            continue;
+        };
+        if let Some((first, _)) = &fields {
+            return Err(syn::Error::new(
+                strct.ident.span(),
+                format!("Only one #[local] struct is allowed, but `{first}` is already declared."),
+            ));
+        }
+        strct.attrs.remove(attr_idx);
+        let syn::Fields::Named(named) = &strct.fields else {
+            return Err(syn::Error::new(
+                strct.ident.span(),
+                format!(
+                    "The #[local] struct `{}` must have named fields, one per local resource.",
+                    strct.ident
+                ),
+            ));
+        };
+        fields = Some((strct.ident.clone(), named.named.iter().cloned().collect()));
+    }
+    Ok(fields.map(|(_, fields)| fields).unwrap_or_default())
+}
+
+/// Whether `ty` is `<module>::Context`, possibly with a lifetime
+fn is_context_of(ty: &Type, module: &str) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let segments: Vec<_> = path
+        .path
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect();
+    segments.len() == 2 && segments[0] == module && segments[1] == "Context"
+}
+
+fn peripherals_enabled(params: &RticAttr) -> bool {
+    matches!(
+        params.elements.get("peripherals"),
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Bool(enabled),
+            ..
+        })) if enabled.value
+    )
+}
@@ -0,0 +1,37 @@
+mod app {
+    #[shared]
+    struct Shared {
+        log: Logger,
+    }
+
+    #[local]
+    struct Local {
+        config: Config,
+        count: u32,
+    }
+
+    #[init]
+    fn init(_cx: init::Context) -> (Shared, Local) {
+        (
+            Shared { log: Logger::new() },
+            Local {
+                config: Config::default(),
+                count: 0,
+            },
+        )
+    }
+
+    #[task(binds = UART0, priority = 2, shared = [log], local = [config])]
+    fn receive(mut cx: receive::Context) {
+        let gain = cx.local.config.gain;
+        cx.shared.log.lock(|log| log.write(&[gain as u8]));
+    }
+
+    #[idle(shared = [log], local = [count])]
+    fn idle(mut cx: idle::Context) -> ! {
+        loop {
+            *cx.local.count += 1;
+            cx.shared.log.lock(|log| log.write(b"idle"));
+        }
+    }
+}
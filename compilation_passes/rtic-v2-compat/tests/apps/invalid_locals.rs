@@ -0,0 +1,23 @@
+mod app {
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {
+        led: LedPin,
+    }
+
+    #[init]
+    fn init(_cx: init::Context) -> (Shared, Local) {
+        (Shared {}, Local { led: setup_led() })
+    }
+
+    #[task(binds = UART0, local = [led, button])]
+    fn first(_cx: first::Context) {}
+
+    #[task(binds = UART1, local = [led])]
+    fn second(_cx: second::Context) {}
+
+    #[task(binds = UART2)]
+    fn third() {}
+}
@@ -0,0 +1,74 @@
+mod app {
+    use heapless::String;
+
+    #[shared]
+    struct Shared {
+        uart_tx: UartTx,
+        alarm: Alarm0,
+    }
+
+    #[local]
+    struct Local {
+        led: LedPin,
+        uart_rx: UartRx,
+        /// Number of received bytes
+        received: u32,
+    }
+
+    #[init]
+    fn init(cx: init::Context) -> (Shared, Local) {
+        let (uart_rx, uart_tx) = setup_uart(cx.device.UART0);
+        let led = setup_led(cx.device.IO_BANK0);
+        let alarm = setup_alarm(cx.device.TIMER);
+        (
+            Shared { uart_tx, alarm },
+            Local {
+                led,
+                uart_rx,
+                received: 0,
+            },
+        )
+    }
+
+    /// Task the receives commands
+    #[task(binds = UART0_IRQ, priority = 1, shared = [uart_tx, alarm], local = [uart_rx, received])]
+    fn command_receiver(mut cx: command_receiver::Context) {
+        let mut data = [0_u8; 48];
+        let bytes = cx.local.uart_rx.read_raw(&mut data).unwrap();
+        *cx.local.received += bytes as u32;
+        cx.shared
+            .uart_tx
+            .lock(|uart| uart.write_full_blocking(&data[..bytes]));
+        if bytes == 0 {
+            return;
+        }
+        let _ = encryptor::spawn(String::new());
+        let _ = logger::spawn(bytes as u32, true);
+    }
+
+    #[task(binds = TIMER_IRQ_0, priority = 2, shared = [alarm], local = [led])]
+    fn command_executor(mut cx: command_executor::Context) {
+        let _ = cx.local.led.toggle();
+        cx.shared.alarm.lock(|alarm| alarm.clear_interrupt());
+    }
+
+    #[task(priority = 3, shared = [uart_tx])]
+    async fn encryptor(mut cx: encryptor::Context, mut data: String<30>) {
+        xor_cipher(unsafe { data.as_bytes_mut() });
+        cx.shared
+            .uart_tx
+            .lock(|uart| uart.write_full_blocking(data.as_bytes()));
+    }
+
+    #[task(priority = 3)]
+    async fn logger(_cx: logger::Context, count: u32, echo: bool) {
+        log(count, echo);
+    }
+
+    #[idle]
+    fn idle(_cx: idle::Context) -> ! {
+        loop {
+            cortex_m::asm::wfi();
+        }
+    }
+}
@@ -0,0 +1,492 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    use pac as _;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rtic::export::interrupt_free(f)
+    }
+    struct Local {
+        config: Config,
+        count: u32,
+    }
+    /// RTIC v2 context of `init`
+    mod init {
+        #[allow(dead_code)]
+        pub(super) struct Context {}
+    }
+    /// RTIC v2 context of `receive`
+    mod receive {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) config: &'a mut Config,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    /// RTIC v2 context of `idle`
+    mod idle {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) count: &'a mut u32,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    /// # CORE 0
+    static mut SHARED: core::mem::MaybeUninit<Shared> = core::mem::MaybeUninit::uninit();
+    struct Shared {
+        log: Logger,
+    }
+    fn init() -> (Shared, TaskInits) {
+        fn __rtic_v2_init(_cx: init::Context) -> (Shared, Local) {
+            (
+                Shared { log: Logger::new() },
+                Local {
+                    config: Config::default(),
+                    count: 0,
+                },
+            )
+        }
+        let (__rtic_shared, __rtic_local) = __rtic_v2_init(init::Context {});
+        (
+            __rtic_shared,
+            TaskInits {
+                receive: Receive {
+                    config: __rtic_local.config,
+                },
+                idle: Idle { count: __rtic_local.count },
+            },
+        )
+    }
+    static mut IDLE: core::mem::MaybeUninit<Idle> = core::mem::MaybeUninit::uninit();
+    struct Idle {
+        count: u32,
+    }
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_idle_task::<Idle>();
+    };
+    impl RticIdleTask for Idle {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) -> ! {
+            let mut cx = idle::Context {
+                shared: self.shared(),
+                local: idle::LocalResources {
+                    count: &mut self.count,
+                },
+            };
+            loop {
+                *cx.local.count += 1;
+                cx.shared.log.lock(|log| log.write(b"idle"));
+            }
+        }
+    }
+    impl Idle {
+        pub const fn priority() -> u16 {
+            0u16
+        }
+    }
+    impl Idle {
+        pub fn shared(&self) -> __idle_shared_resources {
+            const TASK_PRIORITY: u16 = 0u16;
+            __idle_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __idle_shared_resources {
+        pub log: __log_mutex,
+    }
+    impl __idle_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                log: __log_mutex::new(priority),
+            }
+        }
+    }
+    impl Idle {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut RECEIVE: core::mem::MaybeUninit<Receive> = core::mem::MaybeUninit::uninit();
+    struct Receive {
+        config: Config,
+    }
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Receive>();
+    };
+    impl RticTask for Receive {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) {
+            let mut cx = receive::Context {
+                shared: self.shared(),
+                local: receive::LocalResources {
+                    config: &mut self.config,
+                },
+            };
+            let gain = cx.local.config.gain;
+            cx.shared.log.lock(|log| log.write(&[gain as u8]));
+        }
+    }
+    impl Receive {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Receive {
+        pub fn shared(&self) -> __receive_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __receive_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __receive_shared_resources {
+        pub log: __log_mutex,
+    }
+    impl __receive_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                log: __log_mutex::new(priority),
+            }
+        }
+    }
+    impl Receive {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn UART0() {
+        unsafe { RECEIVE.assume_init_mut().exec() };
+    }
+    pub struct __log_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __log_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __log_mutex {
+        type ResourceType = Logger;
+        const CEILING: u16 = 2u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 2u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().log } as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().log as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    /// Type representing tasks that need explicit user initialization
+    pub struct TaskInits {
+        pub receive: Receive,
+        pub idle: Idle,
+    }
+    /// Entry of
+    /// # CORE 0
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let (__shared_resources_0, __late_task_inits): (Shared, TaskInits) = init();
+            unsafe {
+                SHARED.write(__shared_resources_0);
+            }
+            unsafe {
+                RECEIVE.write(__late_task_inits.receive);
+                IDLE.write(__late_task_inits.idle);
+            }
+            unsafe {}
+            rtic::export::enable(pac::Interrupt::UART0, 2u16);
+        });
+        unsafe {
+            IDLE.assume_init_mut().exec();
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+        pub fn implements_rtic_idle_task<T: RticIdleTask>() {}
+    }
+}
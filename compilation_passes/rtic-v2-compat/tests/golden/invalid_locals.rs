@@ -0,0 +1,10 @@
+// no code was generated
+
+// error[V2Compat, parse] at 22:4
+//   The task `third` must take its context as first argument: `fn third(cx: third::Context)`.
+
+// error[V2Compat, parse] at 15:40
+//   The local resource `button` is not declared in the #[local] struct.
+
+// error[V2Compat, parse] at 18:35
+//   The local resource `led` is already used by the task `first`, local resources belong to a single task.
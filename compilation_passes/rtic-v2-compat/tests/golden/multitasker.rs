@@ -0,0 +1,202 @@
+#[app(device = pac, peripherals = true, dispatchers = [SW0_IRQ])]
+mod app {
+    use heapless::String;
+    #[shared]
+    struct Shared {
+        uart_tx: UartTx,
+        alarm: Alarm0,
+    }
+    struct Local {
+        led: LedPin,
+        uart_rx: UartRx,
+        /// Number of received bytes
+        received: u32,
+    }
+    #[init]
+    fn init(device: pac::Peripherals) -> (Shared, TaskInits) {
+        fn __rtic_v2_init(cx: init::Context) -> (Shared, Local) {
+            let (uart_rx, uart_tx) = setup_uart(cx.device.UART0);
+            let led = setup_led(cx.device.IO_BANK0);
+            let alarm = setup_alarm(cx.device.TIMER);
+            (Shared { uart_tx, alarm }, Local { led, uart_rx, received: 0 })
+        }
+        let (__rtic_shared, __rtic_local) = __rtic_v2_init(init::Context { device });
+        (
+            __rtic_shared,
+            TaskInits {
+                command_receiver: CommandReceiver {
+                    uart_rx: __rtic_local.uart_rx,
+                    received: __rtic_local.received,
+                },
+                command_executor: CommandExecutor {
+                    led: __rtic_local.led,
+                },
+            },
+        )
+    }
+    /// RTIC v2 context of `init`
+    mod init {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context {
+            pub(super) device: pac::Peripherals,
+        }
+    }
+    #[task(binds = UART0_IRQ, priority = 1, shared = [uart_tx, alarm])]
+    /// Task the receives commands
+    struct CommandReceiver {
+        uart_rx: UartRx,
+        /// Number of received bytes
+        received: u32,
+    }
+    impl RticTask for CommandReceiver {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) {
+            let mut cx = command_receiver::Context {
+                shared: self.shared(),
+                local: command_receiver::LocalResources {
+                    uart_rx: &mut self.uart_rx,
+                    received: &mut self.received,
+                },
+            };
+            let mut data = [0_u8; 48];
+            let bytes = cx.local.uart_rx.read_raw(&mut data).unwrap();
+            *cx.local.received += bytes as u32;
+            cx.shared.uart_tx.lock(|uart| uart.write_full_blocking(&data[..bytes]));
+            if bytes == 0 {
+                return;
+            }
+            let _ = encryptor::spawn(String::new());
+            let _ = logger::spawn(bytes as u32, true);
+        }
+    }
+    /// RTIC v2 context of `command_receiver`
+    mod command_receiver {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) uart_rx: &'a mut UartRx,
+            pub(super) received: &'a mut u32,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    #[task(binds = TIMER_IRQ_0, priority = 2, shared = [alarm])]
+    struct CommandExecutor {
+        led: LedPin,
+    }
+    impl RticTask for CommandExecutor {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) {
+            let mut cx = command_executor::Context {
+                shared: self.shared(),
+                local: command_executor::LocalResources {
+                    led: &mut self.led,
+                },
+            };
+            let _ = cx.local.led.toggle();
+            cx.shared.alarm.lock(|alarm| alarm.clear_interrupt());
+        }
+    }
+    /// RTIC v2 context of `command_executor`
+    mod command_executor {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) led: &'a mut LedPin,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    #[sw_task(priority = 3, shared = [uart_tx])]
+    struct Encryptor;
+    impl RticSwTask for Encryptor {
+        type SpawnInput = String<30>;
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, mut data: String<30>) {
+            let mut cx = encryptor::Context {
+                shared: self.shared(),
+                local: (),
+            };
+            xor_cipher(unsafe { data.as_bytes_mut() });
+            cx.shared.uart_tx.lock(|uart| uart.write_full_blocking(data.as_bytes()));
+        }
+    }
+    /// RTIC v2 context of `encryptor`
+    mod encryptor {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+        pub(super) fn spawn(data: String<30>) -> Result<(), String<30>> {
+            super::Encryptor::spawn(data)
+        }
+    }
+    #[sw_task(priority = 3)]
+    struct Logger;
+    impl RticSwTask for Logger {
+        type SpawnInput = (u32, bool);
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self, (count, echo): (u32, bool)) {
+            let _cx = logger::Context {
+                shared: (),
+                local: (),
+            };
+            log(count, echo);
+        }
+    }
+    /// RTIC v2 context of `logger`
+    mod logger {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+        pub(super) fn spawn(count: u32, echo: bool) -> Result<(), (u32, bool)> {
+            super::Logger::spawn((count, echo))
+        }
+    }
+    #[idle]
+    struct Idle;
+    impl RticIdleTask for Idle {
+        fn init() -> Self {
+            Self
+        }
+        fn exec(&mut self) -> ! {
+            let _cx = idle::Context {
+                shared: (),
+                local: (),
+            };
+            loop {
+                cortex_m::asm::wfi();
+            }
+        }
+    }
+    /// RTIC v2 context of `idle`
+    mod idle {
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+}
@@ -0,0 +1,844 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    use pac as _;
+    use heapless::String;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        rtic::export::interrupt_free(f)
+    }
+    struct Local {
+        led: LedPin,
+        uart_rx: UartRx,
+        /// Number of received bytes
+        received: u32,
+    }
+    /// RTIC v2 context of `init`
+    mod init {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context {
+            pub(super) device: pac::Peripherals,
+        }
+    }
+    /// RTIC v2 context of `command_receiver`
+    mod command_receiver {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) uart_rx: &'a mut UartRx,
+            pub(super) received: &'a mut u32,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    /// RTIC v2 context of `command_executor`
+    mod command_executor {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct LocalResources<'a> {
+            pub(super) led: &'a mut LedPin,
+        }
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    /// RTIC v2 context of `encryptor`
+    mod encryptor {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+        pub(super) fn spawn(data: String<30>) -> Result<(), String<30>> {
+            super::Encryptor::spawn(data)
+        }
+    }
+    /// RTIC v2 context of `logger`
+    mod logger {
+        use super::*;
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+        pub(super) fn spawn(count: u32, echo: bool) -> Result<(), (u32, bool)> {
+            super::Logger::spawn((count, echo))
+        }
+    }
+    /// RTIC v2 context of `idle`
+    mod idle {
+        #[allow(dead_code)]
+        pub(super) struct Context<S, L> {
+            pub(super) shared: S,
+            pub(super) local: L,
+        }
+    }
+    static mut __rtic_internal__Encryptor__INPUTS: rtic::export::Queue<
+        <Encryptor as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Encryptor {
+        pub fn spawn(
+            input: <Encryptor as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Encryptor as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Encryptor__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio3Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<
+                (),
+                <Encryptor as RticSwTask>::SpawnInput,
+            > {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio3Tasks::Encryptor) };
+                __rtic_local_irq_pend(pac::Interrupt::SW0_IRQ);
+                Ok(())
+            })
+        }
+    }
+    static mut __rtic_internal__Logger__INPUTS: rtic::export::Queue<
+        <Logger as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Logger {
+        pub fn spawn(
+            input: <Logger as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Logger__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio3Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Logger as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio3Tasks::Logger) };
+                __rtic_local_irq_pend(pac::Interrupt::SW0_IRQ);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio3Tasks {
+        Encryptor,
+        Logger,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio3Tasks__RQ: rtic::export::Queue<
+        Core0Prio3Tasks,
+        3usize,
+    > = rtic::export::Queue::new();
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        rtic::export::cross_core_pend(irq_nbr);
+    }
+    /// # CORE 0
+    static mut SHARED: core::mem::MaybeUninit<Shared> = core::mem::MaybeUninit::uninit();
+    struct Shared {
+        uart_tx: UartTx,
+        alarm: Alarm0,
+    }
+    fn init(device: pac::Peripherals) -> (Shared, TaskInits) {
+        fn __rtic_v2_init(cx: init::Context) -> (Shared, Local) {
+            let (uart_rx, uart_tx) = setup_uart(cx.device.UART0);
+            let led = setup_led(cx.device.IO_BANK0);
+            let alarm = setup_alarm(cx.device.TIMER);
+            (Shared { uart_tx, alarm }, Local { led, uart_rx, received: 0 })
+        }
+        let (__rtic_shared, __rtic_local) = __rtic_v2_init(init::Context { device });
+        (
+            __rtic_shared,
+            TaskInits {
+                command_receiver: CommandReceiver {
+                    uart_rx: __rtic_local.uart_rx,
+                    received: __rtic_local.received,
+                },
+                command_executor: CommandExecutor {
+                    led: __rtic_local.led,
+                },
+            },
+        )
+    }
+    static mut IDLE: core::mem::MaybeUninit<Idle> = core::mem::MaybeUninit::uninit();
+    struct Idle;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_idle_task::<Idle>();
+    };
+    impl RticIdleTask for Idle {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) -> ! {
+            let _cx = idle::Context {
+                shared: (),
+                local: (),
+            };
+            loop {
+                cortex_m::asm::wfi();
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Idle {
+        pub const fn priority() -> u16 {
+            0u16
+        }
+    }
+    impl Idle {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut COMMAND_RECEIVER: core::mem::MaybeUninit<CommandReceiver> = core::mem::MaybeUninit::uninit();
+    /// Task the receives commands
+    struct CommandReceiver {
+        uart_rx: UartRx,
+        /// Number of received bytes
+        received: u32,
+    }
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<CommandReceiver>();
+    };
+    impl RticTask for CommandReceiver {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) {
+            let mut cx = command_receiver::Context {
+                shared: self.shared(),
+                local: command_receiver::LocalResources {
+                    uart_rx: &mut self.uart_rx,
+                    received: &mut self.received,
+                },
+            };
+            let mut data = [0_u8; 48];
+            let bytes = cx.local.uart_rx.read_raw(&mut data).unwrap();
+            *cx.local.received += bytes as u32;
+            cx.shared.uart_tx.lock(|uart| uart.write_full_blocking(&data[..bytes]));
+            if bytes == 0 {
+                return;
+            }
+            let _ = encryptor::spawn(String::new());
+            let _ = logger::spawn(bytes as u32, true);
+        }
+    }
+    impl CommandReceiver {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl CommandReceiver {
+        pub fn shared(&self) -> __command_receiver_shared_resources {
+            const TASK_PRIORITY: u16 = 1u16;
+            __command_receiver_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __command_receiver_shared_resources {
+        pub uart_tx: __uart_tx_mutex,
+        pub alarm: __alarm_mutex,
+    }
+    impl __command_receiver_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                uart_tx: __uart_tx_mutex::new(priority),
+                alarm: __alarm_mutex::new(priority),
+            }
+        }
+    }
+    impl CommandReceiver {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut COMMAND_EXECUTOR: core::mem::MaybeUninit<CommandExecutor> = core::mem::MaybeUninit::uninit();
+    struct CommandExecutor {
+        led: LedPin,
+    }
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<CommandExecutor>();
+    };
+    impl RticTask for CommandExecutor {
+        type InitArgs = Self;
+        fn init(task: Self) -> Self {
+            task
+        }
+        fn exec(&mut self) {
+            let mut cx = command_executor::Context {
+                shared: self.shared(),
+                local: command_executor::LocalResources {
+                    led: &mut self.led,
+                },
+            };
+            let _ = cx.local.led.toggle();
+            cx.shared.alarm.lock(|alarm| alarm.clear_interrupt());
+        }
+    }
+    impl CommandExecutor {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl CommandExecutor {
+        pub fn shared(&self) -> __command_executor_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __command_executor_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __command_executor_shared_resources {
+        pub alarm: __alarm_mutex,
+    }
+    impl __command_executor_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                alarm: __alarm_mutex::new(priority),
+            }
+        }
+    }
+    impl CommandExecutor {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut ENCRYPTOR: core::mem::MaybeUninit<Encryptor> = core::mem::MaybeUninit::uninit();
+    /// Software tasks of
+    /// Core 0
+    struct Encryptor;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Encryptor>();
+    };
+    impl RticSwTask for Encryptor {
+        type SpawnInput = String<30>;
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, mut data: String<30>) {
+            let mut cx = encryptor::Context {
+                shared: self.shared(),
+                local: (),
+            };
+            xor_cipher(unsafe { data.as_bytes_mut() });
+            cx.shared.uart_tx.lock(|uart| uart.write_full_blocking(data.as_bytes()));
+        }
+        type InitArgs = ();
+    }
+    impl Encryptor {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl Encryptor {
+        pub fn shared(&self) -> __encryptor_shared_resources {
+            const TASK_PRIORITY: u16 = 3u16;
+            __encryptor_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __encryptor_shared_resources {
+        pub uart_tx: __uart_tx_mutex,
+    }
+    impl __encryptor_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                uart_tx: __uart_tx_mutex::new(priority),
+            }
+        }
+    }
+    impl Encryptor {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut LOGGER: core::mem::MaybeUninit<Logger> = core::mem::MaybeUninit::uninit();
+    struct Logger;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Logger>();
+    };
+    impl RticSwTask for Logger {
+        type SpawnInput = (u32, bool);
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, (count, echo): (u32, bool)) {
+            let _cx = logger::Context {
+                shared: (),
+                local: (),
+            };
+            log(count, echo);
+        }
+        type InitArgs = ();
+    }
+    impl Logger {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl Logger {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut CORE0_PRIORITY3_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority3Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[doc(hidden)]
+    pub struct Core0Priority3Dispatcher;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority3Dispatcher>();
+    };
+    impl RticTask for Core0Priority3Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio3Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio3Tasks::Encryptor => {
+                            let mut input_consumer = __rtic_internal__Encryptor__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            ENCRYPTOR.assume_init_mut().exec(input);
+                        }
+                        Core0Prio3Tasks::Logger => {
+                            let mut input_consumer = __rtic_internal__Logger__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            LOGGER.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority3Dispatcher {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl Core0Priority3Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn UART0_IRQ() {
+        unsafe { COMMAND_RECEIVER.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn TIMER_IRQ_0() {
+        unsafe { COMMAND_EXECUTOR.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn SW0_IRQ() {
+        unsafe { CORE0_PRIORITY3_DISPATCHER.assume_init_mut().exec() };
+    }
+    pub struct __uart_tx_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __uart_tx_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __uart_tx_mutex {
+        type ResourceType = UartTx;
+        const CEILING: u16 = 3u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 3u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().uart_tx }
+                as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().uart_tx as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    pub struct __alarm_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __alarm_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __alarm_mutex {
+        type ResourceType = Alarm0;
+        const CEILING: u16 = 2u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 2u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().alarm } as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().alarm as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    /// Type representing tasks that need explicit user initialization
+    pub struct TaskInits {
+        pub command_receiver: CommandReceiver,
+        pub command_executor: CommandExecutor,
+    }
+    /// Entry of
+    /// # CORE 0
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __rtic_device_peripherals = unsafe { pac::Peripherals::steal() };
+            let (__shared_resources_0, __late_task_inits): (Shared, TaskInits) = init(
+                __rtic_device_peripherals,
+            );
+            unsafe {
+                SHARED.write(__shared_resources_0);
+            }
+            unsafe {
+                COMMAND_RECEIVER.write(__late_task_inits.command_receiver);
+                COMMAND_EXECUTOR.write(__late_task_inits.command_executor);
+            }
+            unsafe {
+                ENCRYPTOR.write(Encryptor::init(()));
+                LOGGER.write(Logger::init(()));
+                CORE0_PRIORITY3_DISPATCHER.write(Core0Priority3Dispatcher::init(()));
+            }
+            rtic::export::enable(pac::Interrupt::UART0_IRQ, 1u16);
+            rtic::export::enable(pac::Interrupt::TIMER_IRQ_0, 2u16);
+            rtic::export::enable(pac::Interrupt::SW0_IRQ, 3u16);
+        });
+        unsafe {
+            IDLE.write(Idle::init(()));
+            IDLE.assume_init_mut().exec();
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+        pub fn implements_rtic_sw_task<T: RticSwTask>() {}
+        pub fn implements_rtic_idle_task<T: RticIdleTask>() {}
+    }
+}
@@ -0,0 +1,50 @@
+use rtic_core::RticMacroBuilder;
+use rtic_sw_pass::SoftwarePass;
+use rtic_testkit::{MockBackend, MockSwBackend, assert_golden, expand_app, expand_pass};
+use rtic_v2_compat::V2CompatPass;
+
+#[test]
+fn rewrites_function_style_tasks() {
+    let output = expand_pass(
+        &V2CompatPass,
+        "device = pac, peripherals = true, dispatchers = [SW0_IRQ]",
+        include_str!("apps/multitasker.rs"),
+    );
+    assert_golden("multitasker", &output);
+}
+
+#[test]
+fn rejects_invalid_local_resources() {
+    let output = expand_pass(
+        &V2CompatPass,
+        "device = pac",
+        include_str!("apps/invalid_locals.rs"),
+    );
+    assert_golden("invalid_locals", &output);
+}
+
+#[test]
+fn expands_through_the_core_pass() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder.bind_pre_core_pass(V2CompatPass);
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/hardware_tasks.rs"),
+    );
+    assert_golden("hardware_tasks_app", &output);
+}
+
+#[test]
+fn expands_software_tasks_and_locals_through_the_core_pass() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder
+        .bind_pre_core_pass(V2CompatPass)
+        .bind_pre_core_pass(SoftwarePass::new(MockSwBackend::default()));
+    let output = expand_app(
+        builder,
+        "device = pac, peripherals = true, dispatchers = [SW0_IRQ]",
+        include_str!("apps/multitasker.rs"),
+    );
+    assert_golden("multitasker_app", &output);
+}
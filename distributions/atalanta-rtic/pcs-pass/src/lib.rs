@@ -2,19 +2,18 @@ mod codegen;
 // mod error;
 mod parse;
 
-use std::cell::RefCell;
-
 use codegen::Codegen;
 use parse::App;
 use proc_macro2::TokenStream;
 use rtic_core::parse_utils::RticAttr;
-use rtic_core::RticPass;
+use rtic_core::{ArgKind, AttrSchema, PassContext, PassDependencies, RticPass};
 use syn::ItemMod;
 
 pub const PCS_ATTR_IDENT: &str = "fast";
 
-// HACK: pass the list of dispatchers from PCS pass to core pass backend.
-thread_local!(pub static PCS_DISPATCHERS: RefCell<Vec<proc_macro2::Ident>> = const { RefCell::new(vec![]) });
+/// Interrupts bound by the tasks that leverage PCS, published to the [PassContext] for the core pass backend.
+#[derive(Debug, Clone, Default)]
+pub struct PcsDispatchers(pub Vec<proc_macro2::Ident>);
 
 pub struct PcsPass {
     max_num_pcs: usize,
@@ -28,11 +27,16 @@ impl PcsPass {
 }
 
 impl RticPass for PcsPass {
-    fn run_pass(&self, args: TokenStream, app_mod: ItemMod) -> syn::Result<(TokenStream, ItemMod)> {
+    fn run_pass(
+        &self,
+        ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
         let params = RticAttr::parse_from_tokens(args.clone())?;
         let mut parsed = App::parse(&params, app_mod)?;
 
-        self.analyze(&mut parsed);
+        self.analyze(ctx, &mut parsed);
 
         let code = Codegen::new(parsed).run();
         Ok((args, code))
@@ -41,10 +45,19 @@ impl RticPass for PcsPass {
     fn pass_name(&self) -> &str {
         "pcs-pass"
     }
+
+    fn dependencies(&self) -> PassDependencies {
+        // every interrupt bound by a task must be known, including the ones of software task dispatchers
+        PassDependencies::new().consumes(["task.binds", "task.fast"])
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        AttrSchema::new().arg("task", PCS_ATTR_IDENT, ArgKind::Flag)
+    }
 }
 
 impl PcsPass {
-    fn analyze(&self, app: &mut App) {
+    fn analyze(&self, ctx: &PassContext, app: &mut App) {
         // Partition interrupts into PCS interrupts and non-PCS interrupts
         let (pcs_irqs, rest_irqs): (Vec<_>, Vec<_>) = app.tasks.iter().partition(|task| task.fast);
 
@@ -71,6 +84,6 @@ impl PcsPass {
                 pcs_dispatchers.push(task.binds.clone());
             }
         }
-        PCS_DISPATCHERS.replace(pcs_dispatchers);
+        ctx.insert(PcsDispatchers(pcs_dispatchers));
     }
 }
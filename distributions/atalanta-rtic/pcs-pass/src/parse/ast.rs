@@ -1,6 +1,6 @@
 use crate::PCS_ATTR_IDENT;
 use quote::{format_ident, ToTokens};
-use rtic_core::parse_utils::RticAttr;
+use rtic_core::parse_utils::RticItem;
 use syn::{Ident, ItemStruct};
 
 #[derive(Debug)]
@@ -12,20 +12,15 @@ pub struct RticTask {
 }
 
 impl RticTask {
-    pub fn from_struct((task_struct, attr_idx): (&ItemStruct, usize)) -> syn::Result<Self> {
-        let name = task_struct.ident.to_string();
-        let params = RticAttr::parse_from_attr(&task_struct.attrs[attr_idx]).inspect_err(|_e| {
-            eprintln!(
-                "An error occurred while parsing: {:?}",
-                task_struct.attrs[attr_idx].to_token_stream().to_string()
-            )
-        })?;
-        let binds_expr = params
+    pub fn from_item(task: &RticItem<ItemStruct>) -> syn::Result<Self> {
+        let name = task.name().to_string();
+        let binds_expr = task
+            .attr
             .elements
             .get("binds")
             .expect("Internal error: any task should always have a bound interrupt");
         let binds = format_ident!("{}", binds_expr.to_token_stream().to_string());
-        let fast = params.elements.contains_key(PCS_ATTR_IDENT);
+        let fast = task.attr.elements.contains_key(PCS_ATTR_IDENT);
 
         Ok(Self { name, binds, fast })
     }
@@ -2,23 +2,36 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 
-use rtic_core::{AppArgs, CorePassBackend, RticMacroBuilder, SubAnalysis, SubApp};
+use rtic_core::{
+    AppArgs, BackendCapabilities, CorePassBackend, PassContext, RticMacroBuilder, SubAnalysis,
+    SubApp,
+};
 use syn::{parse_quote, ItemFn};
 
 extern crate proc_macro;
 
 struct AtalantaRtic;
 
+#[cfg(feature = "deadline-pass")]
+use rtic_deadline_pass::DeadlineToPriorityPass;
 #[cfg(feature = "pcs-pass")]
 use pcs_pass::PcsPass;
 use rtic_sw_pass::SoftwarePass;
 
-const MIN_TASK_PRIORITY: u16 = 1; // lowest Atalanta priority
+const MIN_TASK_PRIORITY: u16 = 1; // lowest Atalanta priority, the levels grow with urgency like the logical priorities
+const MAX_TASK_PRIORITY: u16 = 255; // highest Atalanta priority, the CLIC is configured with 8 level bits
 
 #[proc_macro_attribute]
 pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
     let mut builder = RticMacroBuilder::new(AtalantaRtic);
 
+    // the builder orders the passes based on their declared dependencies (deadline pass runs before software pass,
+    // which runs before PCS pass)
+
+    // use the standard deadline to priority pass provided by the rtic-deadline-pass crate
+    #[cfg(feature = "deadline-pass")]
+    builder.bind_pre_core_pass(DeadlineToPriorityPass::new(MAX_TASK_PRIORITY));
+
     // use the standard software pass provided by rtic-sw-pass crate
     let sw_pass = SoftwarePass::new(SwPassBackend);
     builder.bind_pre_core_pass(sw_pass);
@@ -37,12 +50,22 @@ pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
 
 // =========================================== Trait implementations ===================================================
 impl CorePassBackend for AtalantaRtic {
-    fn default_task_priority(&self) -> u16 {
+    fn default_task_priority(&self, _ctx: &PassContext) -> u16 {
         MIN_TASK_PRIORITY
     }
 
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        BackendCapabilities {
+            priorities: MIN_TASK_PRIORITY..=MAX_TASK_PRIORITY,
+            max_cores: 1,
+            exceptions: false,
+            cross_core_pend: false,
+        }
+    }
+
     fn post_init(
         &self,
+        ctx: &PassContext,
         _app_args: &AppArgs,
         _sub_app: &SubApp,
         app_analysis: &SubAnalysis,
@@ -59,7 +82,9 @@ impl CorePassBackend for AtalantaRtic {
         });
 
         let pcs_dispatchers = if cfg!(feature = "pcs-pass") {
-            pcs_pass::PCS_DISPATCHERS.with(|ds| ds.borrow().clone())
+            ctx.get::<pcs_pass::PcsDispatchers>()
+                .map(|ds| ds.0)
+                .unwrap_or_default()
         } else {
             vec![]
         };
@@ -72,7 +97,6 @@ impl CorePassBackend for AtalantaRtic {
         // Append dispatchers
         let init_dispatcher_interrupts =
             app_analysis.used_irqs.iter().map(|(irq_name, priority)| {
-                let priority = priority.max(&MIN_TASK_PRIORITY); // limit priority to minimum
                 let pcs = if pcs_dispatchers.contains(irq_name) {
                     quote!(PCS_TRUE)
                 } else {
@@ -96,13 +120,13 @@ impl CorePassBackend for AtalantaRtic {
         Some(out)
     }
 
-    fn populate_idle_loop(&self) -> Option<TokenStream2> {
+    fn populate_idle_loop(&self, _ctx: &PassContext) -> Option<TokenStream2> {
         Some(quote! {
             unsafe { core::arch::asm!("wfi") };
         })
     }
 
-    fn generate_interrupt_free_fn(&self, mut empty_body_fn: ItemFn) -> ItemFn {
+    fn generate_interrupt_free_fn(&self, _ctx: &PassContext, mut empty_body_fn: ItemFn) -> ItemFn {
         // eprintln!("{}", empty_body_fn.to_token_stream().to_string()); // enable comment to see the function signature
         let fn_body = parse_quote! {
             {
@@ -118,6 +142,7 @@ impl CorePassBackend for AtalantaRtic {
 
     fn generate_global_definitions(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _app_info: &SubApp,
         _app_analysis: &SubAnalysis,
@@ -127,13 +152,14 @@ impl CorePassBackend for AtalantaRtic {
 
     fn generate_resource_proxy_lock_impl(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _app_info: &SubApp,
         incomplete_lock_fn: syn::ImplItemFn,
     ) -> syn::ImplItemFn {
         let lock_impl: syn::Block = parse_quote! {
             {
-                unsafe { rtic::export::lock(resource_ptr, task_priority as u8, CEILING as u8, f); }
+                unsafe { rtic::export::lock(resource_ptr, task_priority as u8, CEILING as u8, f) }
             }
         };
 
@@ -142,13 +168,14 @@ impl CorePassBackend for AtalantaRtic {
         completed_lock_fn
     }
 
-    fn entry_name(&self, _core: u32) -> Ident {
+    fn entry_name(&self, _ctx: &PassContext, _core: u32) -> Ident {
         format_ident!("main")
     }
 
     /// Customize how the task is dispatched when its bound interrupt is triggered
     fn wrap_task_execution(
         &self,
+        _ctx: &PassContext,
         _task_prio: u16,
         _dispatch_task_call: TokenStream2,
     ) -> Option<TokenStream2> {
@@ -158,17 +185,18 @@ impl CorePassBackend for AtalantaRtic {
     /// further analysis of parsed user code
     fn pre_codegen_validation(
         &self,
+        _ctx: &PassContext,
         _app: &rtic_core::App,
         _analysis: &rtic_core::Analysis,
     ) -> syn::Result<()> {
         Ok(())
     }
 
-    fn entry_attrs(&self) -> Vec<syn::Attribute> {
+    fn entry_attrs(&self, _ctx: &PassContext) -> Vec<syn::Attribute> {
         vec![syn::parse_quote!(#[bsp::rt::entry])]
     }
 
-    fn task_attrs(&self) -> Vec<syn::Attribute> {
+    fn task_attrs(&self, _ctx: &PassContext) -> Vec<syn::Attribute> {
         vec![
             // Interrupt wrapper generation is delegated to PCS pass if enabled
             #[cfg(not(feature = "pcs-pass"))]
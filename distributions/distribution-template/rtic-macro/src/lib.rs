@@ -98,7 +98,7 @@ impl StandardPassImpl for RenodeRtic {
 
         let lock_impl: syn::Block = parse_quote! {
             { 
-                unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f); }
+                unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f) }
             } 
         };
 
@@ -3,7 +3,14 @@ use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 #[cfg(feature = "autoassign")]
 use rtic_auto_assign::AutoAssignPass;
-use rtic_core::{AppArgs, CorePassBackend, RticMacroBuilder, SubAnalysis, SubApp};
+use rtic_core::{
+    AppArgs, BackendCapabilities, CorePassBackend, PassContext, RticMacroBuilder, SubAnalysis,
+    SubApp,
+};
+#[cfg(feature = "schedulability")]
+use rtic_schedulability::SchedulabilityPass;
+#[cfg(feature = "v2compat")]
+use rtic_v2_compat::V2CompatPass;
 use syn::{parse_quote, ItemFn};
 
 extern crate proc_macro;
@@ -13,8 +20,28 @@ struct Rp2040Rtic;
 #[cfg(feature = "swtasks")]
 use rtic_sw_pass::{SoftwarePass, SwPassBackend};
 
-const MIN_TASK_PRIORITY: u16 = 3;
-const MAX_TASK_PRIORITY: u16 = 0;
+/// The Cortex-M0+ NVIC implements 2 priority bits: 4 levels, with level 0 being the most urgent
+const NVIC_PRIO_BITS: u16 = 2;
+/// Logical priority of the least urgent level (3). Logical priorities grow with urgency, see [rtic_core::capabilities].
+const MIN_TASK_PRIORITY: u16 = 1;
+/// Logical priority of the most urgent level (0)
+const MAX_TASK_PRIORITY: u16 = 1 << NVIC_PRIO_BITS;
+
+/// Cortex-M0+ exceptions a hardware task can be bound to. Any other binding is an interrupt of the device.
+const EXCEPTIONS: [&str; 5] = ["NonMaskableInt", "HardFault", "SVCall", "PendSV", "SysTick"];
+/// Exceptions whose priority is configurable through the SCB
+const CONFIGURABLE_EXCEPTIONS: [&str; 3] = ["SVCall", "PendSV", "SysTick"];
+/// Interrupts picked as dispatchers when the application lists none, in order of preference. These are the lines of
+/// the peripherals the HAL drivers seldom need. `SIO_IRQ_PROC0/1` are left out, they forward the cross-core pends.
+#[cfg(feature = "swtasks")]
+const FREE_INTERRUPTS: [&str; 6] = [
+    "DMA_IRQ_0",
+    "DMA_IRQ_1",
+    "PIO1_IRQ_0",
+    "PIO1_IRQ_1",
+    "XIP_IRQ",
+    "CLOCKS_IRQ",
+];
 
 #[proc_macro_attribute]
 pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -22,22 +49,45 @@ pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
     #[cfg(feature = "swtasks")]
     let sw_pass = SoftwarePass::new(SwPassBackendImpl);
 
+    // the builder orders the passes based on their declared dependencies (auto-assign runs before software-pass)
     #[allow(unused_mut)]
     let mut builder = RticMacroBuilder::new(Rp2040Rtic);
+    #[cfg(feature = "v2compat")]
+    builder.bind_pre_core_pass(V2CompatPass);
     #[cfg(feature = "autoassign")]
-    builder.bind_pre_core_pass(AutoAssignPass); // run auto-assign pass first
+    builder.bind_pre_core_pass(AutoAssignPass);
     #[cfg(feature = "swtasks")]
-    builder.bind_pre_core_pass(sw_pass); // run software-pass second
+    builder.bind_pre_core_pass(sw_pass);
+    // collects the timing annotations before the core pass, and analyzes them with the ceilings computed by it
+    #[cfg(feature = "schedulability")]
+    builder
+        .bind_pre_core_pass(SchedulabilityPass)
+        .bind_post_core_pass(SchedulabilityPass);
     builder.build_rtic_macro(args, input)
 }
 
 // =========================================== Trait implementations ===================================================
 impl CorePassBackend for Rp2040Rtic {
-    fn default_task_priority(&self) -> u16 {
+    fn default_task_priority(&self, _ctx: &PassContext) -> u16 {
         MIN_TASK_PRIORITY
     }
+
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        BackendCapabilities {
+            priorities: MIN_TASK_PRIORITY..=MAX_TASK_PRIORITY,
+            max_cores: 2,
+            exceptions: true,
+            cross_core_pend: true,
+        }
+    }
+
+    fn hardware_priority(&self, _ctx: &PassContext, _core: u32, logical: u16) -> u16 {
+        hardware_level(logical)
+    }
+
     fn post_init(
         &self,
+        ctx: &PassContext,
         app_args: &AppArgs,
         sub_app: &SubApp,
         app_analysis: &SubAnalysis,
@@ -45,17 +95,33 @@ impl CorePassBackend for Rp2040Rtic {
         let peripheral_crate = &app_args.pacs[sub_app.core as usize];
         let initialize_dispatcher_interrupts =
             app_analysis.used_irqs.iter().map(|(irq_name, priority)| {
-                let priority = priority.min(&MIN_TASK_PRIORITY); // limit piority to minmum
+                let priority =
+                    priority_register(self.hardware_priority(ctx, sub_app.core, *priority));
                 quote! {
                     //set interrupt priority
                     #peripheral_crate::CorePeripherals::steal()
                         .NVIC
-                        .set_priority(#peripheral_crate::Interrupt::#irq_name, #priority as u8);
+                        .set_priority(#peripheral_crate::Interrupt::#irq_name, #priority);
                     //unmask interrupt
                     #peripheral_crate::NVIC::unmask(#peripheral_crate::Interrupt::#irq_name);
                 }
             });
 
+        let initialize_exceptions = app_analysis
+            .used_exceptions
+            .iter()
+            .filter(|(exception, _)| CONFIGURABLE_EXCEPTIONS.iter().any(|e| exception == e))
+            .map(|(exception, priority)| {
+                let priority =
+                    priority_register(self.hardware_priority(ctx, sub_app.core, *priority));
+                quote! {
+                    //set exception priority, exceptions are always enabled
+                    #peripheral_crate::CorePeripherals::steal()
+                        .SCB
+                        .set_priority(rtic::export::SystemHandler::#exception, #priority);
+                }
+            });
+
         // initialize core 1 from core 0 if the application is for multicore (cores > 1)
         let init_and_spawn_core1 = if sub_app.core == 0 && app_args.cores > 1 {
             Some(init_core1(peripheral_crate))
@@ -72,6 +138,7 @@ impl CorePassBackend for Rp2040Rtic {
         Some(quote! {
             unsafe {
                 #(#initialize_dispatcher_interrupts)*
+                #(#initialize_exceptions)*
             }
             // init and spawn core 1 (if app.core == 0 and app_args.cores == 2 )
             #init_and_spawn_core1
@@ -81,13 +148,13 @@ impl CorePassBackend for Rp2040Rtic {
         })
     }
 
-    fn populate_idle_loop(&self) -> Option<TokenStream2> {
+    fn populate_idle_loop(&self, _ctx: &PassContext) -> Option<TokenStream2> {
         Some(quote! {
             unsafe { core::arch::asm!("wfi" ); }
         })
     }
 
-    fn generate_interrupt_free_fn(&self, mut empty_body_fn: ItemFn) -> ItemFn {
+    fn generate_interrupt_free_fn(&self, _ctx: &PassContext, mut empty_body_fn: ItemFn) -> ItemFn {
         // eprintln!("{}", empty_body_fn.to_token_stream().to_string()); // enable comment to see the function signature
         let fn_body = parse_quote! {
             {
@@ -103,25 +170,23 @@ impl CorePassBackend for Rp2040Rtic {
 
     fn generate_global_definitions(
         &self,
+        _ctx: &PassContext,
         app_args: &AppArgs,
         app_info: &SubApp,
-        _app_analysis: &SubAnalysis,
+        app_analysis: &SubAnalysis,
     ) -> Option<TokenStream2> {
         let peripheral_crate = &app_args.pacs[app_info.core as usize];
 
-        // irq names from hadware tasks
-        let irq_list_as_u32 = app_info.tasks.iter().filter_map(|t| {
-            let irq_name = t.args.binds.as_ref()?;
-            Some(quote! { #peripheral_crate::Interrupt::#irq_name as u32, })
+        // irq names from hadware tasks (exceptions are not in the NVIC, so they are left out of the masks)
+        let irq_list_as_u32 = app_analysis.used_irqs.iter().map(|(irq_name, _)| {
+            quote! { #peripheral_crate::Interrupt::#irq_name as u32, }
         });
 
+        // one mask per logical priority below MAX_TASK_PRIORITY, a lock with a ceiling of MAX_TASK_PRIORITY disables
+        // all the interrupts instead
         let mut irq_prio_map = [Vec::new(), Vec::new(), Vec::new()];
-        for hw_task in app_info.tasks.iter() {
-            let prio = hw_task.args.priority;
-            if (1..=3).contains(&prio) {
-                let Some(irq_name) = hw_task.args.binds.as_ref() else {
-                    continue;
-                };
+        for (irq_name, prio) in app_analysis.used_irqs.iter() {
+            if (MIN_TASK_PRIORITY..MAX_TASK_PRIORITY).contains(prio) {
                 irq_prio_map[(prio - 1) as usize].push(quote! {
                     #peripheral_crate::Interrupt::#irq_name as u32,
                 })
@@ -158,6 +223,7 @@ impl CorePassBackend for Rp2040Rtic {
 
     fn generate_resource_proxy_lock_impl(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         app_info: &SubApp,
         incomplete_lock_fn: syn::ImplItemFn,
@@ -166,7 +232,7 @@ impl CorePassBackend for Rp2040Rtic {
         let masks_ident = format_ident!("__rtic_internal_MASKS_core{core}"); // already computed by `compute_lock_static_args(...)`
 
         let lock_impl: syn::Block = parse_quote! {
-            { unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, &#masks_ident, f); } }
+            { unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, &#masks_ident, f) } }
         };
 
         let mut completed_lock_fn = incomplete_lock_fn;
@@ -174,7 +240,7 @@ impl CorePassBackend for Rp2040Rtic {
         completed_lock_fn
     }
 
-    fn entry_name(&self, core: u32) -> Ident {
+    fn entry_name(&self, _ctx: &PassContext, core: u32) -> Ident {
         match core {
             0 => format_ident!("main"),
             _ => format_ident!("core{core}_entry"),
@@ -183,6 +249,7 @@ impl CorePassBackend for Rp2040Rtic {
 
     fn wrap_task_execution(
         &self,
+        _ctx: &PassContext,
         _task_prio: u16,
         _dispatch_task_call: TokenStream2,
     ) -> Option<TokenStream2> {
@@ -191,10 +258,16 @@ impl CorePassBackend for Rp2040Rtic {
 
     fn pre_codegen_validation(
         &self,
-        _app: &rtic_core::App,
-        _analysis: &rtic_core::Analysis,
+        _ctx: &PassContext,
+        app: &rtic_core::App,
+        analysis: &rtic_core::Analysis,
     ) -> syn::Result<()> {
-        Ok(())
+        // the locks mask interrupts through the NVIC, which has no control over the exceptions
+        analysis.reject_exception_resource_users(app, |_| false)
+    }
+
+    fn is_exception(&self, _ctx: &PassContext, _core: u32, binds: &Ident) -> bool {
+        EXCEPTIONS.iter().any(|exception| binds == exception)
     }
 }
 
@@ -228,6 +301,14 @@ impl SwPassBackend for SwPassBackendImpl {
         empty_body_fn.block = Box::new(body);
         Some(empty_body_fn)
     }
+
+    /// Both cores have their own NVIC, so they can use the same interrupts as dispatchers.
+    fn free_interrupts(&self, _core: u32) -> Vec<syn::Path> {
+        FREE_INTERRUPTS
+            .iter()
+            .map(|irq| syn::parse_str(irq).unwrap())
+            .collect()
+    }
 }
 
 fn init_core1(pac: &syn::Path) -> TokenStream2 {
@@ -255,9 +336,20 @@ fn init_core1(pac: &syn::Path) -> TokenStream2 {
     }
 }
 
+/// NVIC level of the `logical` priority
+fn hardware_level(logical: u16) -> u16 {
+    MAX_TASK_PRIORITY - logical
+}
+
+/// Value of the NVIC and SCB priority registers for `level`, which is stored in their most significant bits
+fn priority_register(level: u16) -> u8 {
+    (level << (8 - NVIC_PRIO_BITS)) as u8
+}
+
 fn configure_fifo(peripheral_crate: &syn::Path, core: u32) -> TokenStream2 {
     #[allow(non_snake_case)]
     let SIO_IRQ_PROC = format_ident!("SIO_IRQ_PROC{core}");
+    let max_priority = priority_register(hardware_level(MAX_TASK_PRIORITY));
     quote! {
         unsafe {
             let sio = unsafe { &(*rp2040_hal::pac::SIO::PTR) };
@@ -270,9 +362,32 @@ fn configure_fifo(peripheral_crate: &syn::Path, core: u32) -> TokenStream2 {
             #peripheral_crate::NVIC::unpend( #peripheral_crate::Interrupt::#SIO_IRQ_PROC);
             // Set FIFO0 interrupts priority to MAX priority
             #peripheral_crate::CorePeripherals::steal()
-                .NVIC.set_priority( #peripheral_crate::Interrupt::#SIO_IRQ_PROC, #MAX_TASK_PRIORITY as u8);
+                .NVIC.set_priority( #peripheral_crate::Interrupt::#SIO_IRQ_PROC, #max_priority);
             // unmask FIFO irq
             #peripheral_crate::NVIC::unmask( #peripheral_crate::Interrupt::#SIO_IRQ_PROC);
         }
     }
 }
+
+#[cfg(all(test, feature = "swtasks"))]
+mod tests {
+    use super::{Rp2040Rtic, SwPassBackendImpl};
+    use rtic_core::RticMacroBuilder;
+    use rtic_sw_pass::SoftwarePass;
+    use rtic_testkit::{assert_golden, expand_app};
+
+    #[test]
+    fn selects_dispatchers_among_the_free_interrupts() {
+        let mut builder = RticMacroBuilder::new(Rp2040Rtic);
+        builder.bind_pre_core_pass(SoftwarePass::new(SwPassBackendImpl));
+        let output = expand_app(
+            builder,
+            "device = rp2040_hal::pac, peripherals = false, dispatchers_report = true",
+            include_str!("../tests/apps/free_interrupts.rs"),
+        );
+        // DMA_IRQ_0 is bound to a hardware task, the next free interrupts are used
+        assert!(output.contains("//     DMA_IRQ_1: priority 2, tasks Blink"));
+        assert!(output.contains("//     PIO1_IRQ_0: priority 1, tasks Log"));
+        assert_golden("free_interrupts", &output);
+    }
+}
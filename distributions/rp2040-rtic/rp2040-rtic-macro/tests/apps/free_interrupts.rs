@@ -0,0 +1,54 @@
+mod app {
+    #[shared]
+    struct Shared {
+        count: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { count: 0 }
+    }
+
+    #[task(binds = DMA_IRQ_0, priority = 3, shared = [count])]
+    struct DmaTask;
+
+    impl RticTask for DmaTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().count.lock(|count| *count += 1);
+            let _ = Blink::spawn(());
+        }
+    }
+
+    #[sw_task(priority = 2, shared = [count])]
+    struct Blink;
+
+    impl RticSwTask for Blink {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {
+            self.shared().count.lock(|count| *count = 0);
+            let _ = Log::spawn(());
+        }
+    }
+
+    #[sw_task(priority = 1)]
+    struct Log;
+
+    impl RticSwTask for Log {
+        type SpawnInput = ();
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: ()) {}
+    }
+}
@@ -0,0 +1,688 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    use rp2040_hal::pac as _;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        unsafe {
+            core::arch::asm!("cpsid i");
+        }
+        let r = f();
+        unsafe {
+            core::arch::asm!("cpsie i");
+        }
+        r
+    }
+    static mut __rtic_internal__Blink__INPUTS: rtic::export::Queue<
+        <Blink as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Blink {
+        pub fn spawn(
+            input: <Blink as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Blink as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe {
+                __rtic_internal__Blink__INPUTS.split().0
+            };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Blink as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio2Tasks::Blink) };
+                __rtic_local_irq_pend(rp2040_hal::pac::Interrupt::DMA_IRQ_1);
+                Ok(())
+            })
+        }
+    }
+    static mut __rtic_internal__Log__INPUTS: rtic::export::Queue<
+        <Log as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Log {
+        pub fn spawn(
+            input: <Log as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Log as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe { __rtic_internal__Log__INPUTS.split().0 };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Log as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Log) };
+                __rtic_local_irq_pend(rp2040_hal::pac::Interrupt::PIO1_IRQ_0);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio1Tasks {
+        Log,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio1Tasks__RQ: rtic::export::Queue<
+        Core0Prio1Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio2Tasks {
+        Blink,
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio2Tasks__RQ: rtic::export::Queue<
+        Core0Prio2Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::NVIC::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        use rtic::export::InterruptNumber;
+        let _ = rtic::export::cross_core::pend_irq(irq_nbr.number());
+    }
+    /// # CORE 0
+    static mut SHARED: core::mem::MaybeUninit<Shared> = core::mem::MaybeUninit::uninit();
+    struct Shared {
+        count: u32,
+    }
+    fn init() -> Shared {
+        Shared { count: 0 }
+    }
+    static mut DMA_TASK: core::mem::MaybeUninit<DmaTask> = core::mem::MaybeUninit::uninit();
+    struct DmaTask;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<DmaTask>();
+    };
+    impl RticTask for DmaTask {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            self.shared().count.lock(|count| *count += 1);
+            let _ = Blink::spawn(());
+        }
+        type InitArgs = ();
+    }
+    impl DmaTask {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    impl DmaTask {
+        pub fn shared(&self) -> __dma_task_shared_resources {
+            const TASK_PRIORITY: u16 = 3u16;
+            __dma_task_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __dma_task_shared_resources {
+        pub count: __count_mutex,
+    }
+    impl __dma_task_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                count: __count_mutex::new(priority),
+            }
+        }
+    }
+    impl DmaTask {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut BLINK: core::mem::MaybeUninit<Blink> = core::mem::MaybeUninit::uninit();
+    /// Software tasks of
+    /// Core 0
+    struct Blink;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Blink>();
+    };
+    impl RticSwTask for Blink {
+        type SpawnInput = ();
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {
+            self.shared().count.lock(|count| *count = 0);
+            let _ = Log::spawn(());
+        }
+        type InitArgs = ();
+    }
+    impl Blink {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Blink {
+        pub fn shared(&self) -> __blink_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __blink_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    pub struct __blink_shared_resources {
+        pub count: __count_mutex,
+    }
+    impl __blink_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                count: __count_mutex::new(priority),
+            }
+        }
+    }
+    impl Blink {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut LOG: core::mem::MaybeUninit<Log> = core::mem::MaybeUninit::uninit();
+    struct Log;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Log>();
+    };
+    impl RticSwTask for Log {
+        type SpawnInput = ();
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, _: ()) {}
+        type InitArgs = ();
+    }
+    impl Log {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl Log {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut CORE0_PRIORITY1_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority1Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[doc(hidden)]
+    pub struct Core0Priority1Dispatcher;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority1Dispatcher>();
+    };
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio1Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio1Tasks::Log => {
+                            let mut input_consumer = __rtic_internal__Log__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            LOG.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority1Dispatcher {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    impl Core0Priority1Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    static mut CORE0_PRIORITY2_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority2Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[doc(hidden)]
+    pub struct Core0Priority2Dispatcher;
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority2Dispatcher>();
+    };
+    impl RticTask for Core0Priority2Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio2Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio2Tasks::Blink => {
+                            let mut input_consumer = __rtic_internal__Blink__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            BLINK.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority2Dispatcher {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    impl Core0Priority2Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn DMA_IRQ_0() {
+        unsafe { DMA_TASK.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn PIO1_IRQ_0() {
+        unsafe { CORE0_PRIORITY1_DISPATCHER.assume_init_mut().exec() };
+    }
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn DMA_IRQ_1() {
+        unsafe { CORE0_PRIORITY2_DISPATCHER.assume_init_mut().exec() };
+    }
+    pub struct __count_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    impl __count_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    impl RticMutex for __count_mutex {
+        type ResourceType = u32;
+        const CEILING: u16 = 3u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 3u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED.assume_init_mut().count } as *mut _;
+            unsafe {
+                rtic::export::lock(
+                    resource_ptr,
+                    task_priority,
+                    CEILING,
+                    &__rtic_internal_MASKS_core0,
+                    f,
+                )
+            }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED.assume_init_mut().count as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe {
+                rtic::export::lock(
+                    resource_ptr,
+                    task_priority,
+                    CEILING,
+                    &__rtic_internal_MASKS_core0,
+                    f,
+                )
+            }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    const __rtic_internal_MASK_CHUNKS_core0: usize = rtic::export::compute_mask_chunks([
+        rp2040_hal::pac::Interrupt::DMA_IRQ_0 as u32,
+        rp2040_hal::pac::Interrupt::PIO1_IRQ_0 as u32,
+        rp2040_hal::pac::Interrupt::DMA_IRQ_1 as u32,
+    ]);
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    const __rtic_internal_MASKS_core0: [rtic::export::Mask<
+        __rtic_internal_MASK_CHUNKS_core0,
+    >; 3] = [
+        rtic::export::create_mask([rp2040_hal::pac::Interrupt::PIO1_IRQ_0 as u32]),
+        rtic::export::create_mask([rp2040_hal::pac::Interrupt::DMA_IRQ_1 as u32]),
+        rtic::export::create_mask([rp2040_hal::pac::Interrupt::DMA_IRQ_0 as u32]),
+    ];
+    /// Type representing tasks that need explicit user initialization
+    /// Entry of
+    /// # CORE 0
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __shared_resources_0: Shared = init();
+            unsafe {
+                SHARED.write(__shared_resources_0);
+            }
+            unsafe {
+                DMA_TASK.write(DmaTask::init(()));
+                BLINK.write(Blink::init(()));
+                LOG.write(Log::init(()));
+                CORE0_PRIORITY1_DISPATCHER.write(Core0Priority1Dispatcher::init(()));
+                CORE0_PRIORITY2_DISPATCHER.write(Core0Priority2Dispatcher::init(()));
+            }
+            unsafe {
+                rp2040_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(rp2040_hal::pac::Interrupt::DMA_IRQ_0, 64u8);
+                rp2040_hal::pac::NVIC::unmask(rp2040_hal::pac::Interrupt::DMA_IRQ_0);
+                rp2040_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(rp2040_hal::pac::Interrupt::PIO1_IRQ_0, 192u8);
+                rp2040_hal::pac::NVIC::unmask(rp2040_hal::pac::Interrupt::PIO1_IRQ_0);
+                rp2040_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(rp2040_hal::pac::Interrupt::DMA_IRQ_1, 128u8);
+                rp2040_hal::pac::NVIC::unmask(rp2040_hal::pac::Interrupt::DMA_IRQ_1);
+            }
+        });
+        loop {
+            unsafe {
+                core::arch::asm!("wfi");
+            }
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+        pub fn implements_rtic_sw_task<T: RticSwTask>() {}
+    }
+}
+
+// warning[SoftwareTasks, analysis] at 1:4
+//   Dispatchers of the software tasks
+//   core 0:
+//     DMA_IRQ_1: priority 2, tasks Blink
+//     PIO1_IRQ_0: priority 1, tasks Log
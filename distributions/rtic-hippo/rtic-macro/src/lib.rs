@@ -2,7 +2,10 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 
-use rtic_core::{AppArgs, CorePassBackend, RticMacroBuilder, SubAnalysis, SubApp};
+use rtic_core::{
+    AppArgs, BackendCapabilities, CorePassBackend, PassContext, RticMacroBuilder, SubAnalysis,
+    SubApp,
+};
 use syn::{parse_quote, ItemFn};
 
 extern crate proc_macro;
@@ -14,8 +17,8 @@ use rtic_deadline_pass::{DeadlineToPriorityPass /* DeadlineToPriorityPassImpl */
 
 use rtic_sw_pass::SoftwarePass;
 
-const MIN_TASK_PRIORITY: u16 = 0; // lowest hippo priority
-#[cfg(feature = "deadline-pass")]
+// lowest hippo task priority, above idle (0), the levels grow with urgency like the logical priorities
+const MIN_TASK_PRIORITY: u16 = 1;
 const MAX_TASK_PRIORITY: u16 = 3; // highest hippo priority
 
 #[proc_macro_attribute]
@@ -26,31 +29,41 @@ pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
     #[cfg(feature = "deadline-pass")]
     let deadline_pass = DeadlineToPriorityPass::new(MAX_TASK_PRIORITY);
 
+    // the builder orders the passes based on their declared dependencies (deadline pass runs before software pass)
     let mut builder = RticMacroBuilder::new(HippoRtic);
     #[cfg(feature = "deadline-pass")]
     {
-        builder.bind_pre_core_pass(deadline_pass); // run deadline to priority pass first
+        builder.bind_pre_core_pass(deadline_pass);
         println!("--- deadline pass added --- ");
     }
-    builder.bind_pre_core_pass(sw_pass); // run software pass second
+    builder.bind_pre_core_pass(sw_pass);
     builder.build_rtic_macro(args, input)
 }
 
 // =========================================== Trait implementations ===================================================
 impl CorePassBackend for HippoRtic {
-    fn default_task_priority(&self) -> u16 {
+    fn default_task_priority(&self, _ctx: &PassContext) -> u16 {
         MIN_TASK_PRIORITY
     }
 
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        BackendCapabilities {
+            priorities: MIN_TASK_PRIORITY..=MAX_TASK_PRIORITY,
+            max_cores: 1,
+            exceptions: false,
+            cross_core_pend: false,
+        }
+    }
+
     fn post_init(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _sub_app: &SubApp,
         app_analysis: &SubAnalysis,
     ) -> Option<TokenStream2> {
         let initialize_dispatcher_interrupts =
             app_analysis.used_irqs.iter().map(|(irq_name, priority)| {
-                let priority = priority.max(&MIN_TASK_PRIORITY); // limit piority to minmum
                 quote! {
                     //set interrupt priority
                     rtic::export::enable(
@@ -67,11 +80,11 @@ impl CorePassBackend for HippoRtic {
         })
     }
 
-    fn populate_idle_loop(&self) -> Option<TokenStream2> {
+    fn populate_idle_loop(&self, _ctx: &PassContext) -> Option<TokenStream2> {
         None
     }
 
-    fn generate_interrupt_free_fn(&self, mut empty_body_fn: ItemFn) -> ItemFn {
+    fn generate_interrupt_free_fn(&self, _ctx: &PassContext, mut empty_body_fn: ItemFn) -> ItemFn {
         // eprintln!("{}", empty_body_fn.to_token_stream().to_string()); // enable comment to see the function signature
         let fn_body = parse_quote! {
             {
@@ -87,6 +100,7 @@ impl CorePassBackend for HippoRtic {
 
     fn generate_global_definitions(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _app_info: &SubApp,
         _app_analysis: &SubAnalysis,
@@ -96,13 +110,14 @@ impl CorePassBackend for HippoRtic {
 
     fn generate_resource_proxy_lock_impl(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _app_info: &SubApp,
         incomplete_lock_fn: syn::ImplItemFn,
     ) -> syn::ImplItemFn {
         let lock_impl: syn::Block = parse_quote! {
             {
-                unsafe { rtic::export::lock(resource_ptr, task_priority as u8, CEILING as u8, f); }
+                unsafe { rtic::export::lock(resource_ptr, task_priority as u8, CEILING as u8, f) }
             }
         };
 
@@ -111,7 +126,7 @@ impl CorePassBackend for HippoRtic {
         completed_lock_fn
     }
 
-    fn entry_name(&self, _core: u32) -> Ident {
+    fn entry_name(&self, _ctx: &PassContext, _core: u32) -> Ident {
         // same entry name for both cores.
         // two main() functions will be generated but both will be guarded by #[cfg(core = "X")]
         // each generated binary will have have one entry
@@ -121,6 +136,7 @@ impl CorePassBackend for HippoRtic {
     /// Customize how the task is dispatched when its bound interrupt is triggered (save baspri before and restore after executing the task)
     fn wrap_task_execution(
         &self,
+        _ctx: &PassContext,
         _task_prio: u16,
         _dispatch_task_call: TokenStream2,
     ) -> Option<TokenStream2> {
@@ -130,6 +146,7 @@ impl CorePassBackend for HippoRtic {
     /// further analysis of parsed user code
     fn pre_codegen_validation(
         &self,
+        _ctx: &PassContext,
         _app: &rtic_core::App,
         _analysis: &rtic_core::Analysis,
     ) -> syn::Result<()> {
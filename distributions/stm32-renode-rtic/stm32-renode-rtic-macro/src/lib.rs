@@ -2,7 +2,10 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 use rtic_auto_assign::AutoAssignPass;
-use rtic_core::{AppArgs, CorePassBackend, RticMacroBuilder, SubAnalysis, SubApp};
+use rtic_core::{
+    AppArgs, BackendCapabilities, CorePassBackend, PassContext, RticMacroBuilder, SubAnalysis,
+    SubApp,
+};
 use syn::{parse_quote, ItemFn};
 
 extern crate proc_macro;
@@ -11,26 +14,75 @@ struct RenodeRtic;
 
 use rtic_sw_pass::{SoftwarePass, SwPassBackend};
 
-const MIN_TASK_PRIORITY: u16 = 15; // cortex m3 has 16 programmable priority levels (0 -> 15) with level 15 being the lowest
-const MAX_TASK_PRIORITY: u16 = 0;
+/// Cortex-M3 has 16 programmable priority levels (0 -> 15) with level 15 being the lowest. Must match the
+/// `NVIC_PRIO_BITS` of the device crate, which `rtic::export::lock` uses at run time.
+const NVIC_PRIO_BITS: u16 = 4;
+/// Logical priority of the lowest level (15). Logical priorities grow with urgency, see [rtic_core::capabilities],
+/// and are converted to levels like `rtic::export::cortex_logical2hw` does.
+const MIN_TASK_PRIORITY: u16 = 1;
+/// Logical priority of the highest level (0)
+const MAX_TASK_PRIORITY: u16 = 1 << NVIC_PRIO_BITS;
+
+/// Cortex-M3 exceptions a hardware task can be bound to. Any other binding is an interrupt of the device.
+const EXCEPTIONS: [&str; 9] = [
+    "NonMaskableInt",
+    "HardFault",
+    "MemoryManagement",
+    "BusFault",
+    "UsageFault",
+    "SVCall",
+    "DebugMonitor",
+    "PendSV",
+    "SysTick",
+];
+/// Exceptions whose priority is configurable through the SCB, and which can therefore be masked through BASEPRI
+const CONFIGURABLE_EXCEPTIONS: [&str; 7] = [
+    "MemoryManagement",
+    "BusFault",
+    "UsageFault",
+    "SVCall",
+    "DebugMonitor",
+    "PendSV",
+    "SysTick",
+];
+/// Interrupts picked as dispatchers when the application lists none, in order of preference. These are the lines of
+/// the peripherals the examples do not use. `DMA2_CHANNEL4_5` is left out, the mailbox takes it over.
+const FREE_INTERRUPTS: [&str; 6] = ["TIM3", "TIM4", "TIM2", "SPI2", "I2C2_EV", "I2C2_ER"];
+
 #[proc_macro_attribute]
 pub fn app(args: TokenStream, input: TokenStream) -> TokenStream {
     // use the standard software pass provided by rtic-sw-pass crate
     let sw_pass = SoftwarePass::new(SwPassBackendImpl);
 
+    // the builder orders the passes based on their declared dependencies (auto-assign runs before software pass)
     let mut builder = RticMacroBuilder::new(RenodeRtic);
-    builder.bind_pre_core_pass(AutoAssignPass); // run auto-assign first
-    builder.bind_pre_core_pass(sw_pass); // run software pass second
+    builder.bind_pre_core_pass(AutoAssignPass);
+    builder.bind_pre_core_pass(sw_pass);
     builder.build_rtic_macro(args, input)
 }
 
 // =========================================== Trait implementations ===================================================
 impl CorePassBackend for RenodeRtic {
-    fn default_task_priority(&self) -> u16 {
+    fn default_task_priority(&self, _ctx: &PassContext) -> u16 {
         MIN_TASK_PRIORITY
     }
+
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        BackendCapabilities {
+            priorities: MIN_TASK_PRIORITY..=MAX_TASK_PRIORITY,
+            max_cores: 2,
+            exceptions: true,
+            cross_core_pend: true,
+        }
+    }
+
+    fn hardware_priority(&self, _ctx: &PassContext, _core: u32, logical: u16) -> u16 {
+        hardware_level(logical)
+    }
+
     fn post_init(
         &self,
+        ctx: &PassContext,
         app_args: &AppArgs,
         sub_app: &SubApp,
         app_analysis: &SubAnalysis,
@@ -38,17 +90,33 @@ impl CorePassBackend for RenodeRtic {
         let peripheral_crate = &app_args.pacs[sub_app.core as usize];
         let initialize_dispatcher_interrupts =
             app_analysis.used_irqs.iter().map(|(irq_name, priority)| {
-                let priority = priority.min(&MIN_TASK_PRIORITY); // limit piority to minmum
+                let priority =
+                    priority_register(self.hardware_priority(ctx, sub_app.core, *priority));
                 quote! {
                     //set interrupt priority
                     #peripheral_crate::CorePeripherals::steal()
                         .NVIC
-                        .set_priority(#peripheral_crate::Interrupt::#irq_name, #priority as u8);
+                        .set_priority(#peripheral_crate::Interrupt::#irq_name, #priority);
                     //unmask interrupt
                     #peripheral_crate::NVIC::unmask(#peripheral_crate::Interrupt::#irq_name);
                 }
             });
 
+        let initialize_exceptions = app_analysis
+            .used_exceptions
+            .iter()
+            .filter(|(exception, _)| CONFIGURABLE_EXCEPTIONS.iter().any(|e| exception == e))
+            .map(|(exception, priority)| {
+                let priority =
+                    priority_register(self.hardware_priority(ctx, sub_app.core, *priority));
+                quote! {
+                    //set exception priority, exceptions are always enabled
+                    #peripheral_crate::CorePeripherals::steal()
+                        .SCB
+                        .set_priority(rtic::export::SystemHandler::#exception, #priority);
+                }
+            });
+
         let configure_fifo = if app_args.cores > 1 {
             Some(configure_fifo(peripheral_crate, sub_app.core))
         } else {
@@ -58,18 +126,19 @@ impl CorePassBackend for RenodeRtic {
         Some(quote! {
             unsafe {
                 #(#initialize_dispatcher_interrupts)*
+                #(#initialize_exceptions)*
             }
             #configure_fifo
         })
     }
 
-    fn populate_idle_loop(&self) -> Option<TokenStream2> {
+    fn populate_idle_loop(&self, _ctx: &PassContext) -> Option<TokenStream2> {
         Some(quote! {
             unsafe { core::arch::asm!("wfi" ); }
         })
     }
 
-    fn generate_interrupt_free_fn(&self, mut empty_body_fn: ItemFn) -> ItemFn {
+    fn generate_interrupt_free_fn(&self, _ctx: &PassContext, mut empty_body_fn: ItemFn) -> ItemFn {
         // eprintln!("{}", empty_body_fn.to_token_stream().to_string()); // enable comment to see the function signature
         let fn_body = parse_quote! {
             {
@@ -85,6 +154,7 @@ impl CorePassBackend for RenodeRtic {
 
     fn generate_global_definitions(
         &self,
+        _ctx: &PassContext,
         app_args: &AppArgs,
         app_info: &SubApp,
         _app_analysis: &SubAnalysis,
@@ -103,13 +173,14 @@ impl CorePassBackend for RenodeRtic {
 
     fn generate_resource_proxy_lock_impl(
         &self,
+        _ctx: &PassContext,
         _app_args: &AppArgs,
         _app_info: &SubApp,
         incomplete_lock_fn: syn::ImplItemFn,
     ) -> syn::ImplItemFn {
         let lock_impl: syn::Block = parse_quote! {
             {
-                unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f); }
+                unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f) }
             }
         };
 
@@ -118,7 +189,7 @@ impl CorePassBackend for RenodeRtic {
         completed_lock_fn
     }
 
-    fn entry_name(&self, _core: u32) -> Ident {
+    fn entry_name(&self, _ctx: &PassContext, _core: u32) -> Ident {
         // same entry name for both cores.
         // two main() functions will be generated but both will be guarded by #[cfg(core = "X")]
         // each generated binary will have have one entry
@@ -128,6 +199,7 @@ impl CorePassBackend for RenodeRtic {
     /// Customize how the task is dispatched when its bound interrupt is triggered (save baspri before and restore after executing the task)
     fn wrap_task_execution(
         &self,
+        _ctx: &PassContext,
         task_prio: u16,
         dispatch_task_call: TokenStream2,
     ) -> Option<TokenStream2> {
@@ -136,16 +208,24 @@ impl CorePassBackend for RenodeRtic {
         })
     }
 
-    fn multibin_shared_macro_path(&self) -> syn::Path {
+    fn multibin_shared_macro_path(&self, _ctx: &PassContext) -> syn::Path {
         parse_quote!(rtic::export::microamp::shared)
     }
 
     fn pre_codegen_validation(
         &self,
-        _app: &rtic_core::App,
-        _analysis: &rtic_core::Analysis,
+        _ctx: &PassContext,
+        app: &rtic_core::App,
+        analysis: &rtic_core::Analysis,
     ) -> syn::Result<()> {
-        Ok(())
+        // BASEPRI masks the exceptions with a configurable priority only
+        analysis.reject_exception_resource_users(app, |exception| {
+            CONFIGURABLE_EXCEPTIONS.iter().any(|e| exception == e)
+        })
+    }
+
+    fn is_exception(&self, _ctx: &PassContext, _core: u32, binds: &Ident) -> bool {
+        EXCEPTIONS.iter().any(|exception| binds == exception)
     }
 }
 
@@ -169,9 +249,28 @@ impl SwPassBackend for SwPassBackendImpl {
         empty_body_fn.block = Box::new(body);
         Some(empty_body_fn)
     }
+
+    /// Each core is a separate device with its own NVIC, so they can use the same interrupts as dispatchers.
+    fn free_interrupts(&self, _core: u32) -> Vec<syn::Path> {
+        FREE_INTERRUPTS
+            .iter()
+            .map(|irq| syn::parse_str(irq).unwrap())
+            .collect()
+    }
+}
+
+/// NVIC level of the `logical` priority
+fn hardware_level(logical: u16) -> u16 {
+    MAX_TASK_PRIORITY - logical
+}
+
+/// Value of the NVIC and SCB priority registers for `level`, which is stored in their most significant bits
+fn priority_register(level: u16) -> u8 {
+    (level << (8 - NVIC_PRIO_BITS)) as u8
 }
 
 fn configure_fifo(peripheral_crate: &syn::Path, _core: u32) -> TokenStream2 {
+    let max_priority = priority_register(hardware_level(MAX_TASK_PRIORITY));
     quote! {
         unsafe {
             let fifo = &mut rtic::mailbox::Mailbox;
@@ -181,9 +280,33 @@ fn configure_fifo(peripheral_crate: &syn::Path, _core: u32) -> TokenStream2 {
             #peripheral_crate::NVIC::unpend(rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT);
             // Set FIFO0 interrupts priority to MAX priority
             #peripheral_crate::CorePeripherals::steal()
-                .NVIC.set_priority( rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT, #MAX_TASK_PRIORITY as u8);
+                .NVIC.set_priority( rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT, #max_priority);
             // unmask FIFO irq
             #peripheral_crate::NVIC::unmask( rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{RenodeRtic, SwPassBackendImpl};
+    use rtic_core::RticMacroBuilder;
+    use rtic_sw_pass::SoftwarePass;
+    use rtic_testkit::{assert_golden, expand_app};
+
+    #[test]
+    fn selects_dispatchers_among_the_free_interrupts() {
+        let mut builder = RticMacroBuilder::new(RenodeRtic);
+        builder.bind_pre_core_pass(SoftwarePass::new(SwPassBackendImpl));
+        let output = expand_app(
+            builder,
+            "device = [stm32f1xx_hal::pac, stm32f1xx_hal::pac], peripherals = false, cores = 2, \
+            dispatchers_report = true",
+            include_str!("../tests/apps/free_interrupts.rs"),
+        );
+        // TIM3 is bound to a hardware task of core 0 only, core 1 still picks it first
+        assert!(output.contains("//   core 0:\n//     TIM4: priority 1, tasks Ping"));
+        assert!(output.contains("//   core 1:\n//     TIM3: priority 2, tasks Pong"));
+        assert_golden("free_interrupts", &output);
+    }
+}
@@ -0,0 +1,66 @@
+mod app {
+    #[shared(core = 0)]
+    struct Shared0 {
+        count: u32,
+    }
+
+    #[shared(core = 1)]
+    struct Shared1 {
+        total: u32,
+    }
+
+    #[init(core = 0)]
+    fn init0() -> Shared0 {
+        Shared0 { count: 0 }
+    }
+
+    #[init(core = 1)]
+    fn init1() -> Shared1 {
+        Shared1 { total: 0 }
+    }
+
+    #[task(binds = TIM3, priority = 3, core = 0, shared = [count])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().count.lock(|count| *count += 1);
+            let _ = Ping::spawn(1);
+        }
+    }
+
+    #[sw_task(priority = 1, core = 0, shared = [count])]
+    struct Ping;
+
+    impl RticSwTask for Ping {
+        type SpawnInput = u32;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, _: u32) {
+            let count = self.shared().count.lock(|count| *count);
+            let _ = Pong::spawn(count);
+        }
+    }
+
+    #[sw_task(priority = 2, core = 1, spawn_by = 0, shared = [total])]
+    struct Pong;
+
+    impl RticSwTask for Pong {
+        type SpawnInput = u32;
+
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self, count: u32) {
+            self.shared().total.lock(|total| *total += count);
+        }
+    }
+}
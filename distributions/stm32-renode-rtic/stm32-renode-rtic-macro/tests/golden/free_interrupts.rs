@@ -0,0 +1,912 @@
+pub mod app {
+    /// Include peripheral crate(s) that defines the vector table
+    #[cfg(core = "0")]
+    use stm32f1xx_hal::pac as _;
+    #[cfg(core = "1")]
+    use stm32f1xx_hal::pac as _;
+    use rtic::export::microamp::shared as multibin_shared;
+    /// Module defining rtic traits
+    pub use rtic_traits::*;
+    pub mod rtic_traits {
+        /// Trait for a hardware task
+        pub trait RticTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be bound to a HW Interrupt
+            fn exec(&mut self);
+        }
+        /// Trait for an idle task
+        pub trait RticIdleTask {
+            /// Associated type that can be used to make [Self::init] take arguments
+            type InitArgs: Sized;
+            /// Task local variables initialization routine
+            fn init(args: Self::InitArgs) -> Self;
+            /// Function to be executing when no other task is running
+            fn exec(&mut self) -> !;
+        }
+        pub trait RticMutex {
+            type ResourceType;
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(
+                task_priority: u16,
+                ceiling: u16,
+                f: impl FnOnce() -> R,
+            ) -> R;
+        }
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait RticReadMutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait RticMultiLock<F, R> {
+            fn lock(&mut self, f: F) -> R;
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            F: FnOnce(&mut T0::ResourceType, &mut T1::ResourceType) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1) = (self.0.resource_ptr(), self.1.resource_ptr());
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [T0::CEILING, T1::CEILING, T2::CEILING, T3::CEILING]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(&mut *ptr0, &mut *ptr1, &mut *ptr2, &mut *ptr3, &mut *ptr4),
+                    )
+                }
+            }
+        }
+        impl<
+            T0: RticMutex,
+            T1: RticMutex,
+            T2: RticMutex,
+            T3: RticMutex,
+            T4: RticMutex,
+            T5: RticMutex,
+            F: FnOnce(
+                    &mut T0::ResourceType,
+                    &mut T1::ResourceType,
+                    &mut T2::ResourceType,
+                    &mut T3::ResourceType,
+                    &mut T4::ResourceType,
+                    &mut T5::ResourceType,
+                ) -> R,
+            R,
+        > RticMultiLock<F, R> for (T0, T1, T2, T3, T4, T5) {
+            fn lock(&mut self, f: F) -> R {
+                let ceiling = [
+                    T0::CEILING,
+                    T1::CEILING,
+                    T2::CEILING,
+                    T3::CEILING,
+                    T4::CEILING,
+                    T5::CEILING,
+                ]
+                    .into_iter()
+                    .max()
+                    .unwrap_or_default();
+                let task_priority = self.0.task_priority();
+                let (ptr0, ptr1, ptr2, ptr3, ptr4, ptr5) = (
+                    self.0.resource_ptr(),
+                    self.1.resource_ptr(),
+                    self.2.resource_ptr(),
+                    self.3.resource_ptr(),
+                    self.4.resource_ptr(),
+                    self.5.resource_ptr(),
+                );
+                unsafe {
+                    T0::lock_with_ceiling(
+                        task_priority,
+                        ceiling,
+                        || f(
+                            &mut *ptr0,
+                            &mut *ptr1,
+                            &mut *ptr2,
+                            &mut *ptr3,
+                            &mut *ptr4,
+                            &mut *ptr5,
+                        ),
+                    )
+                }
+            }
+        }
+    }
+    /// critical section function
+    #[inline]
+    pub fn __rtic_interrupt_free<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        unsafe {
+            core::arch::asm!("cpsid i");
+        }
+        let r = f();
+        unsafe {
+            core::arch::asm!("cpsie i");
+        }
+        r
+    }
+    #[cfg(core = "0")]
+    static mut __rtic_internal__Ping__INPUTS: rtic::export::Queue<
+        <Ping as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[cfg(core = "0")]
+    impl Ping {
+        pub fn spawn(
+            input: <Ping as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Ping as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe { __rtic_internal__Ping__INPUTS.split().0 };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core0Prio1Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Ping as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core0Prio1Tasks::Ping) };
+                __rtic_local_irq_pend(stm32f1xx_hal::pac::Interrupt::TIM4);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 0
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core0Prio1Tasks {
+        Ping,
+    }
+    #[multibin_shared]
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core0Prio1Tasks__RQ: rtic::export::Queue<
+        Core0Prio1Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    #[multibin_shared]
+    static mut __rtic_internal__Pong__INPUTS: rtic::export::Queue<
+        <Pong as RticSwTask>::SpawnInput,
+        2usize,
+    > = rtic::export::Queue::new();
+    impl Pong {
+        pub fn spawn_from(
+            _spawner: __rtic__internal__Core0,
+            input: <Pong as RticSwTask>::SpawnInput,
+        ) -> Result<(), <Pong as RticSwTask>::SpawnInput> {
+            let mut inputs_producer = unsafe { __rtic_internal__Pong__INPUTS.split().0 };
+            let mut ready_producer = unsafe {
+                __rtic_internal__Core1Prio2Tasks__RQ.split().0
+            };
+            /// need to protect by a critical section because many producers of different priorities can spawn/enqueue this task
+            __rtic_interrupt_free(|| -> Result<(), <Pong as RticSwTask>::SpawnInput> {
+                inputs_producer.enqueue(input)?;
+                unsafe { ready_producer.enqueue_unchecked(Core1Prio2Tasks::Pong) };
+                __rtic_cross_irq_pend(stm32f1xx_hal::pac::Interrupt::TIM3, 1u32);
+                Ok(())
+            })
+        }
+    }
+    /// Dispatchers of
+    /// Core 1
+    #[derive(Clone, Copy)]
+    #[doc(hidden)]
+    pub enum Core1Prio2Tasks {
+        Pong,
+    }
+    #[multibin_shared]
+    #[doc(hidden)]
+    #[allow(non_upper_case_globals)]
+    static mut __rtic_internal__Core1Prio2Tasks__RQ: rtic::export::Queue<
+        Core1Prio2Tasks,
+        2usize,
+    > = rtic::export::Queue::new();
+    /// RTIC Software task trait
+    /// Trait for a software task
+    pub trait RticSwTask {
+        type InitArgs: Sized;
+        type SpawnInput;
+        /// Task local variables initialization routine
+        fn init(args: Self::InitArgs) -> Self;
+        /// Function to be executing when the scheduled software task is dispatched
+        fn exec(&mut self, input: Self::SpawnInput);
+    }
+    /// Core local interrupt pending
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_local_irq_pend<I: rtic::export::InterruptNumber>(irq_nbr: I) {
+        rtic::export::NVIC::pend(irq_nbr);
+    }
+    #[doc(hidden)]
+    #[inline]
+    pub fn __rtic_cross_irq_pend<I: rtic::export::InterruptNumber>(
+        irq_nbr: I,
+        core: u32,
+    ) {
+        use rtic::export::InterruptNumber;
+        rtic::export::cross_core::pend_irq(irq_nbr.number());
+    }
+    /// # CORE 0
+    #[cfg(core = "0")]
+    static mut SHARED0: core::mem::MaybeUninit<Shared0> = core::mem::MaybeUninit::uninit();
+    #[cfg(core = "0")]
+    struct Shared0 {
+        count: u32,
+    }
+    #[cfg(core = "0")]
+    fn init0() -> Shared0 {
+        Shared0 { count: 0 }
+    }
+    #[cfg(core = "0")]
+    static mut TIMER_TASK: core::mem::MaybeUninit<TimerTask> = core::mem::MaybeUninit::uninit();
+    #[cfg_attr(not(core = "0"), allow(unused))]
+    struct TimerTask;
+    #[cfg(core = "0")]
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<TimerTask>();
+    };
+    #[cfg(core = "0")]
+    impl RticTask for TimerTask {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            self.shared().count.lock(|count| *count += 1);
+            let _ = Ping::spawn(1);
+        }
+        type InitArgs = ();
+    }
+    #[cfg(not(core = "0"))]
+    #[allow(unused)]
+    impl RticTask for TimerTask {
+        fn init(_: ()) -> Self {
+            unreachable!();
+        }
+        fn exec(&mut self) {
+            unreachable!();
+        }
+        type InitArgs = ();
+    }
+    impl TimerTask {
+        pub const fn priority() -> u16 {
+            3u16
+        }
+    }
+    #[cfg(core = "0")]
+    impl TimerTask {
+        pub fn shared(&self) -> __timer_task_shared_resources {
+            const TASK_PRIORITY: u16 = 3u16;
+            __timer_task_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    #[cfg(core = "0")]
+    pub struct __timer_task_shared_resources {
+        pub count: __count_mutex,
+    }
+    #[cfg(core = "0")]
+    impl __timer_task_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                count: __count_mutex::new(priority),
+            }
+        }
+    }
+    #[cfg(core = "0")]
+    impl TimerTask {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[cfg(core = "0")]
+    static mut PING: core::mem::MaybeUninit<Ping> = core::mem::MaybeUninit::uninit();
+    #[cfg_attr(not(core = "0"), allow(unused))]
+    /// Software tasks of
+    /// Core 0
+    struct Ping;
+    #[cfg(core = "0")]
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Ping>();
+    };
+    #[cfg(core = "0")]
+    impl RticSwTask for Ping {
+        type SpawnInput = u32;
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, _: u32) {
+            let count = self.shared().count.lock(|count| *count);
+            let _ = Pong::spawn(count);
+        }
+        type InitArgs = ();
+    }
+    #[cfg(not(core = "0"))]
+    #[allow(unused)]
+    impl RticSwTask for Ping {
+        type SpawnInput = u32;
+        fn init(_: ()) -> Self {
+            unreachable!();
+        }
+        fn exec(&mut self, _: u32) {
+            unreachable!();
+        }
+        type InitArgs = ();
+    }
+    impl Ping {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    #[cfg(core = "0")]
+    impl Ping {
+        pub fn shared(&self) -> __ping_shared_resources {
+            const TASK_PRIORITY: u16 = 1u16;
+            __ping_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    #[cfg(core = "0")]
+    pub struct __ping_shared_resources {
+        pub count: __count_mutex,
+    }
+    #[cfg(core = "0")]
+    impl __ping_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                count: __count_mutex::new(priority),
+            }
+        }
+    }
+    #[cfg(core = "0")]
+    impl Ping {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[cfg(core = "0")]
+    static mut CORE0_PRIORITY1_DISPATCHER: core::mem::MaybeUninit<
+        Core0Priority1Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[cfg_attr(not(core = "0"), allow(unused))]
+    #[doc(hidden)]
+    pub struct Core0Priority1Dispatcher;
+    #[cfg(core = "0")]
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core0Priority1Dispatcher>();
+    };
+    #[cfg(core = "0")]
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core0Prio1Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core0Prio1Tasks::Ping => {
+                            let mut input_consumer = __rtic_internal__Ping__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            PING.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    #[cfg(not(core = "0"))]
+    #[allow(unused)]
+    impl RticTask for Core0Priority1Dispatcher {
+        fn init(_: ()) -> Self {
+            unreachable!();
+        }
+        fn exec(&mut self) {
+            unreachable!();
+        }
+        type InitArgs = ();
+    }
+    impl Core0Priority1Dispatcher {
+        pub const fn priority() -> u16 {
+            1u16
+        }
+    }
+    #[cfg(core = "0")]
+    impl Core0Priority1Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core0 {
+            unsafe { __rtic__internal__Core0::new() }
+        }
+    }
+    #[cfg(core = "0")]
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn TIM3() {
+        rtic::export::run(
+            3u16 as u8,
+            || {
+                unsafe { TIMER_TASK.assume_init_mut().exec() };
+            },
+        );
+    }
+    #[cfg(core = "0")]
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn TIM4() {
+        rtic::export::run(
+            1u16 as u8,
+            || {
+                unsafe { CORE0_PRIORITY1_DISPATCHER.assume_init_mut().exec() };
+            },
+        );
+    }
+    #[cfg(core = "0")]
+    pub struct __count_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    #[cfg(core = "0")]
+    impl __count_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    #[cfg(core = "0")]
+    impl RticMutex for __count_mutex {
+        type ResourceType = u32;
+        const CEILING: u16 = 3u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            const CEILING: u16 = 3u16;
+            let task_priority = self.task_priority;
+            let resource_ptr = unsafe { &mut SHARED0.assume_init_mut().count } as *mut _;
+            unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f) }
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED0.assume_init_mut().count as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f) }
+        }
+    }
+    ///Unique type for core 0
+    pub use core0_type_mod::__rtic__internal__Core0;
+    mod core0_type_mod {
+        struct __rtic__internal__Core0Inner;
+        pub struct __rtic__internal__Core0(__rtic__internal__Core0Inner);
+        impl __rtic__internal__Core0 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core0(__rtic__internal__Core0Inner)
+            }
+        }
+    }
+    use stm32f1xx_hal::pac::NVIC_PRIO_BITS;
+    /// Type representing tasks that need explicit user initialization
+    /// Entry of
+    /// # CORE 0
+    #[cfg(core = "0")]
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __shared_resources_0: Shared0 = init0();
+            unsafe {
+                SHARED0.write(__shared_resources_0);
+            }
+            unsafe {
+                TIMER_TASK.write(TimerTask::init(()));
+                PING.write(Ping::init(()));
+                CORE0_PRIORITY1_DISPATCHER.write(Core0Priority1Dispatcher::init(()));
+            }
+            unsafe {
+                stm32f1xx_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(stm32f1xx_hal::pac::Interrupt::TIM3, 208u8);
+                stm32f1xx_hal::pac::NVIC::unmask(stm32f1xx_hal::pac::Interrupt::TIM3);
+                stm32f1xx_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(stm32f1xx_hal::pac::Interrupt::TIM4, 240u8);
+                stm32f1xx_hal::pac::NVIC::unmask(stm32f1xx_hal::pac::Interrupt::TIM4);
+            }
+            unsafe {
+                let fifo = &mut rtic::mailbox::Mailbox;
+                fifo.drain();
+                stm32f1xx_hal::pac::NVIC::unpend(
+                    rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT,
+                );
+                stm32f1xx_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT, 0u8);
+                stm32f1xx_hal::pac::NVIC::unmask(
+                    rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT,
+                );
+            }
+        });
+        loop {
+            unsafe {
+                core::arch::asm!("wfi");
+            }
+        }
+    }
+    /// # CORE 1
+    #[cfg(core = "1")]
+    static mut SHARED1: core::mem::MaybeUninit<Shared1> = core::mem::MaybeUninit::uninit();
+    #[cfg(core = "1")]
+    struct Shared1 {
+        total: u32,
+    }
+    #[cfg(core = "1")]
+    fn init1() -> Shared1 {
+        Shared1 { total: 0 }
+    }
+    #[cfg(core = "1")]
+    static mut PONG: core::mem::MaybeUninit<Pong> = core::mem::MaybeUninit::uninit();
+    #[cfg_attr(not(core = "1"), allow(unused))]
+    /// Software tasks of
+    /// Core 1
+    struct Pong;
+    #[cfg(core = "1")]
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_sw_task::<Pong>();
+    };
+    #[cfg(core = "1")]
+    impl RticSwTask for Pong {
+        type SpawnInput = u32;
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self, count: u32) {
+            self.shared().total.lock(|total| *total += count);
+        }
+        type InitArgs = ();
+    }
+    #[cfg(not(core = "1"))]
+    #[allow(unused)]
+    impl RticSwTask for Pong {
+        type SpawnInput = u32;
+        fn init(_: ()) -> Self {
+            unreachable!();
+        }
+        fn exec(&mut self, count: u32) {
+            unreachable!();
+        }
+        type InitArgs = ();
+    }
+    impl Pong {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    #[cfg(core = "1")]
+    impl Pong {
+        pub fn shared(&self) -> __pong_shared_resources {
+            const TASK_PRIORITY: u16 = 2u16;
+            __pong_shared_resources::new(TASK_PRIORITY)
+        }
+    }
+    #[cfg(core = "1")]
+    pub struct __pong_shared_resources {
+        pub total: __total_mutex,
+    }
+    #[cfg(core = "1")]
+    impl __pong_shared_resources {
+        #[inline(always)]
+        pub fn new(priority: u16) -> Self {
+            Self {
+                total: __total_mutex::new(priority),
+            }
+        }
+    }
+    #[cfg(core = "1")]
+    impl Pong {
+        pub const fn current_core() -> __rtic__internal__Core1 {
+            unsafe { __rtic__internal__Core1::new() }
+        }
+    }
+    #[cfg(core = "1")]
+    static mut CORE1_PRIORITY2_DISPATCHER: core::mem::MaybeUninit<
+        Core1Priority2Dispatcher,
+    > = core::mem::MaybeUninit::uninit();
+    #[cfg_attr(not(core = "1"), allow(unused))]
+    #[doc(hidden)]
+    pub struct Core1Priority2Dispatcher;
+    #[cfg(core = "1")]
+    const _: fn() = || {
+        __rtic_trait_checks::implements_rtic_task::<Core1Priority2Dispatcher>();
+    };
+    #[cfg(core = "1")]
+    impl RticTask for Core1Priority2Dispatcher {
+        fn init(_: ()) -> Self {
+            Self
+        }
+        fn exec(&mut self) {
+            unsafe {
+                let mut ready_consumer = __rtic_internal__Core1Prio2Tasks__RQ.split().1;
+                while let Some(task) = ready_consumer.dequeue() {
+                    match task {
+                        Core1Prio2Tasks::Pong => {
+                            let mut input_consumer = __rtic_internal__Pong__INPUTS
+                                .split()
+                                .1;
+                            let input = input_consumer.dequeue_unchecked();
+                            PONG.assume_init_mut().exec(input);
+                        }
+                    }
+                }
+            }
+        }
+        type InitArgs = ();
+    }
+    #[cfg(not(core = "1"))]
+    #[allow(unused)]
+    impl RticTask for Core1Priority2Dispatcher {
+        fn init(_: ()) -> Self {
+            unreachable!();
+        }
+        fn exec(&mut self) {
+            unreachable!();
+        }
+        type InitArgs = ();
+    }
+    impl Core1Priority2Dispatcher {
+        pub const fn priority() -> u16 {
+            2u16
+        }
+    }
+    #[cfg(core = "1")]
+    impl Core1Priority2Dispatcher {
+        pub const fn current_core() -> __rtic__internal__Core1 {
+            unsafe { __rtic__internal__Core1::new() }
+        }
+    }
+    #[cfg(core = "1")]
+    #[allow(non_snake_case)]
+    #[unsafe(no_mangle)]
+    fn TIM3() {
+        rtic::export::run(
+            2u16 as u8,
+            || {
+                unsafe { CORE1_PRIORITY2_DISPATCHER.assume_init_mut().exec() };
+            },
+        );
+    }
+    #[cfg(core = "1")]
+    pub struct __total_mutex {
+        #[doc(hidden)]
+        task_priority: u16,
+    }
+    #[cfg(core = "1")]
+    impl __total_mutex {
+        #[inline(always)]
+        pub fn new(task_priority: u16) -> Self {
+            Self { task_priority }
+        }
+    }
+    #[cfg(core = "1")]
+    impl RticMutex for __total_mutex {
+        type ResourceType = u32;
+        const CEILING: u16 = 2u16;
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+            f(unsafe { &mut *self.resource_ptr() })
+        }
+        fn task_priority(&self) -> u16 {
+            self.task_priority
+        }
+        fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+            unsafe { &mut SHARED1.assume_init_mut().total as *mut _ }
+        }
+        unsafe fn lock_with_ceiling<R>(
+            task_priority: u16,
+            ceiling: u16,
+            f: impl FnOnce() -> R,
+        ) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling;
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr();
+            let f = |_: &mut ()| f();
+            unsafe { rtic::export::lock(resource_ptr, CEILING as u8, NVIC_PRIO_BITS, f) }
+        }
+    }
+    ///Unique type for core 1
+    pub use core1_type_mod::__rtic__internal__Core1;
+    mod core1_type_mod {
+        struct __rtic__internal__Core1Inner;
+        pub struct __rtic__internal__Core1(__rtic__internal__Core1Inner);
+        impl __rtic__internal__Core1 {
+            pub const unsafe fn new() -> Self {
+                __rtic__internal__Core1(__rtic__internal__Core1Inner)
+            }
+        }
+    }
+    /// Type representing tasks that need explicit user initialization
+    /// Entry of
+    /// # CORE 1
+    #[cfg(core = "1")]
+    #[unsafe(no_mangle)]
+    fn main() -> ! {
+        __rtic_interrupt_free(|| {
+            let __shared_resources_0: Shared1 = init1();
+            unsafe {
+                SHARED1.write(__shared_resources_0);
+            }
+            unsafe {
+                PONG.write(Pong::init(()));
+                CORE1_PRIORITY2_DISPATCHER.write(Core1Priority2Dispatcher::init(()));
+            }
+            unsafe {
+                stm32f1xx_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(stm32f1xx_hal::pac::Interrupt::TIM3, 224u8);
+                stm32f1xx_hal::pac::NVIC::unmask(stm32f1xx_hal::pac::Interrupt::TIM3);
+            }
+            unsafe {
+                let fifo = &mut rtic::mailbox::Mailbox;
+                fifo.drain();
+                stm32f1xx_hal::pac::NVIC::unpend(
+                    rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT,
+                );
+                stm32f1xx_hal::pac::CorePeripherals::steal()
+                    .NVIC
+                    .set_priority(rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT, 0u8);
+                stm32f1xx_hal::pac::NVIC::unmask(
+                    rtic::mailbox::InterruptExt::MAILBOX_INTERRUPT,
+                );
+            }
+        });
+        loop {
+            unsafe {
+                core::arch::asm!("wfi");
+            }
+        }
+    }
+    /// Utility functions used to enforce implementing appropriate task traits
+    mod __rtic_trait_checks {
+        use super::*;
+        pub fn implements_rtic_task<T: RticTask>() {}
+        pub fn implements_rtic_sw_task<T: RticSwTask>() {}
+    }
+}
+
+// warning[SoftwareTasks, analysis] at 1:4
+//   Dispatchers of the software tasks
+//   core 0:
+//     TIM4: priority 1, tasks Ping
+//   core 1:
+//     TIM3: priority 2, tasks Pong
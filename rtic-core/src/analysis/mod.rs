@@ -1,36 +1,45 @@
-use std::collections::HashSet;
-
 use proc_macro2::Span;
-use syn::spanned::Spanned;
 use syn::Ident;
+use syn::spanned::Spanned;
 
-use crate::parser::ast::{HardwareTask, SharedResources};
+use crate::diagnostics::combine_errors;
 use crate::parser::SubApp;
-use crate::App;
+use crate::parser::ast::{RticTask, SharedResources};
+use crate::{App, CorePassBackend, PassContext};
 use heck::ToSnakeCase;
+use indexmap::IndexSet;
 pub struct Analysis {
     pub sub_analysis: Vec<SubAnalysis>,
-    pub task_traits: HashSet<syn::Ident>,
+    pub task_traits: IndexSet<syn::Ident>,
 }
 
 impl Analysis {
     /// - updates resource ceilings
     /// - collects and structure key information about the user application to be used during code generation
     /// - collect the task traits
-    pub fn run(parsed_app: &mut App) -> syn::Result<Self> {
+    ///
+    /// `backend` tells apart the hardware tasks bound to exceptions from the ones bound to interrupts.
+    pub fn run(
+        parsed_app: &mut App,
+        backend: &dyn CorePassBackend,
+        ctx: &PassContext,
+    ) -> syn::Result<Self> {
         // update resource ceilings
         for app in parsed_app.sub_apps.iter_mut() {
-            update_resource_priorities(app.shared.as_mut(), &app.tasks)?;
+            // idle is a user of shared resources like any other task, at the lowest priority
+            let tasks: Vec<_> = app.tasks.iter().chain(app.idle.iter()).collect();
+            update_resource_priorities(&mut app.shared, &tasks)?;
+            find_lock_free_resources(ctx, &mut app.shared, &tasks, app.idle.as_ref());
         }
 
         // collect and structure key information about the user application to be used during code generation
         let sub_analysis = parsed_app
             .sub_apps
             .iter()
-            .map(SubAnalysis::run)
+            .map(|app| SubAnalysis::run(app, backend, ctx))
             .collect::<syn::Result<_>>()?;
 
-        let mut task_traits = HashSet::new();
+        let mut task_traits = IndexSet::new();
         for subapp in parsed_app.sub_apps.iter() {
             for task in subapp.tasks.iter() {
                 task_traits.insert(task.args.task_trait.clone());
@@ -45,24 +54,69 @@ impl Analysis {
             task_traits,
         })
     }
+
+    /// Rejects the hardware tasks that access shared resources while bound to an exception for which `maskable`
+    /// returns false.
+    ///
+    /// Meant to be called from [CorePassBackend::pre_codegen_validation] by backends whose lock implementation can not
+    /// mask some (or all) exceptions, e.g. interrupt masking through the NVIC, as a task bound to such an exception
+    /// would not be blocked by the locks of the other users of its resources.
+    pub fn reject_exception_resource_users(
+        &self,
+        app: &App,
+        maskable: impl Fn(&Ident) -> bool,
+    ) -> syn::Result<()> {
+        let mut errors = Vec::new();
+        for (sub_app, analysis) in app.sub_apps.iter().zip(self.sub_analysis.iter()) {
+            for task in sub_app.tasks.iter() {
+                let Some(exception) = task.args.binds.as_ref() else {
+                    continue;
+                };
+                if task.args.shared.is_empty()
+                    || !analysis.is_exception(exception)
+                    || maskable(exception)
+                {
+                    continue;
+                }
+                let resources: Vec<_> = task.args.shared.iter().map(|r| format!("`{r}`")).collect();
+                errors.push(syn::Error::new(
+                    exception.span(),
+                    format!(
+                        "The task `{}` is bound to the exception `{exception}` and accesses the shared resource(s) {}, \
+                        but `{exception}` can not be masked by the locks of this target. Bind the task to an interrupt, \
+                        or move the resource accesses to a task bound to an interrupt.",
+                        task.name(),
+                        resources.join(", ")
+                    ),
+                ));
+            }
+        }
+        combine_errors(errors).map_or(Ok(()), Err)
+    }
 }
 
 #[derive(Debug)]
 pub struct SubAnalysis {
     // used interrupts and their priorities
     pub used_irqs: Vec<(syn::Ident, u16)>,
+    // used exceptions (see [CorePassBackend::is_exception]) and their priorities
+    pub used_exceptions: Vec<(syn::Ident, u16)>,
     // tasks requiring some late local resource initialization.
     pub late_resource_tasks: Vec<LateResourceTask>,
 }
 
 impl SubAnalysis {
-    pub fn run(app: &SubApp) -> syn::Result<Self> {
-        // hw interrupts bound to hardware tasks
-        let used_interrupts = app
+    pub fn run(
+        app: &SubApp,
+        backend: &dyn CorePassBackend,
+        ctx: &PassContext,
+    ) -> syn::Result<Self> {
+        // hw interrupts and exceptions bound to hardware tasks
+        let (used_exceptions, used_interrupts) = app
             .tasks
             .iter()
             .filter_map(|t| Some((t.args.binds.clone()?, t.args.priority)))
-            .collect();
+            .partition(|(binds, _)| backend.is_exception(ctx, app.core, binds));
 
         let user_initializable_tasks = app
             .tasks
@@ -81,35 +135,95 @@ impl SubAnalysis {
 
         Ok(Self {
             used_irqs: used_interrupts,
+            used_exceptions,
             late_resource_tasks: user_initializable_tasks,
         })
     }
+
+    /// Whether `binds` is one of the exceptions bound to a hardware task
+    pub fn is_exception(&self, binds: &Ident) -> bool {
+        self.used_exceptions
+            .iter()
+            .any(|(exception, _)| exception == binds)
+    }
 }
 
 fn update_resource_priorities(
-    shared: Option<&mut SharedResources>,
-    hw_tasks: &[HardwareTask],
+    shared: &mut [SharedResources],
+    tasks: &[&RticTask],
 ) -> syn::Result<()> {
-    let Some(shared) = shared else { return Ok(()) };
-    for task in hw_tasks.iter() {
+    if shared.is_empty() {
+        return Ok(());
+    }
+    let mut errors = Vec::new();
+    for task in tasks.iter() {
         let task_priority = task.args.priority;
         for resource_ident in task.args.shared.iter() {
-            if let Some(shared_element) = shared.get_field_mut(resource_ident) {
+            let shared_element = shared
+                .iter_mut()
+                .find_map(|shared| shared.get_field_mut(resource_ident));
+            if let Some(shared_element) = shared_element {
                 if shared_element.priority < task_priority {
                     shared_element.priority = task_priority
                 }
+                if task.reads_only(resource_ident) {
+                    shared_element.has_readers = true;
+                } else if shared_element.reader_ceiling < task_priority {
+                    shared_element.reader_ceiling = task_priority
+                }
             } else {
-                return Err(syn::Error::new(
+                let structs: Vec<_> = shared
+                    .iter()
+                    .map(|shared| format!("`{}`", shared.strct.ident))
+                    .collect();
+                errors.push(syn::Error::new(
                     task.task_struct.span(),
                     format!(
-                        "The resource `{resource_ident}` was not found in `{}`",
-                        shared.strct.ident
+                        "The resource `{resource_ident}` was not found in {}",
+                        structs.join(" or ")
                     ),
                 ));
             }
         }
     }
-    Ok(())
+    combine_errors(errors).map_or(Ok(()), Err)
+}
+
+/// Marks the resources whose users all run at the same priority as lock free (see `SharedElement::lock_free`), and
+/// warns about the resources no task uses. Must run after the ceilings are computed.
+///
+/// `tasks` includes `idle`, which never shares a resource lock free with a task: idle is not run by the interrupt
+/// controller, so an interrupt at the priority of idle still preempts it.
+fn find_lock_free_resources(
+    ctx: &PassContext,
+    shared: &mut [SharedResources],
+    tasks: &[&RticTask],
+    idle: Option<&RticTask>,
+) {
+    let elements = shared
+        .iter_mut()
+        .flat_map(|shared| shared.resources.iter_mut());
+    for element in elements {
+        let users: Vec<_> = tasks
+            .iter()
+            .filter(|task| task.args.shared.contains(&element.ident))
+            .collect();
+        if users.is_empty() {
+            ctx.warning(
+                element.ident.span(),
+                format!(
+                    "The shared resource `{}` is not used by any task.",
+                    element.ident
+                ),
+            );
+        }
+        let idle_user = idle.is_some_and(|idle| idle.args.shared.contains(&element.ident));
+        // the ceiling is the highest priority among the users
+        element.lock_free = users
+            .iter()
+            .all(|task| task.args.priority == element.priority)
+            && !(idle_user && users.len() > 1);
+    }
 }
 
 #[derive(Debug)]
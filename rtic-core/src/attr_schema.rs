@@ -0,0 +1,328 @@
+//! Declarations of the attribute arguments understood by the **Compilation Passes** and the **Core Pass**, and the
+//! validation of the user application against them.
+//!
+//! Every pass registers the arguments it introduces through [crate::RticPass::attribute_schema], e.g. the deadline
+//! pass registers `deadline` for `#[task]` and `#[sw_task]`. Before running any pass, [crate::RticMacroBuilder] merges
+//! the schemas of all the bound passes with the one of the core pass and checks every `#[app(...)]`, `#[task(...)]`,
+//! ... attribute of the user application against it. Unknown arguments (with a "did you mean" suggestion when a
+//! registered argument has a similar name), duplicated arguments and values of the wrong kind are reported as spanned
+//! errors. Attributes that no pass registered are not checked.
+
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Expr, ExprLit, Item, ItemMod, Lit, Meta, Token, parse::Parser, spanned::Spanned};
+
+use crate::PassContext;
+
+/// Name under which the arguments of the `#[app(...)]` attribute are registered
+pub const APP_ATTR: &str = "app";
+
+/// Minimum [strsim::jaro_winkler] similarity for a registered argument to be suggested instead of an unknown one
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+/// The kind of value an attribute argument accepts
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgKind {
+    /// The argument takes no value, e.g. `#[task(binds = UART0, fast)]`
+    Flag,
+    /// `true` or `false`
+    Bool,
+    /// An integer literal
+    Int,
+    /// A single identifier
+    Ident,
+    /// A path, e.g. `rp2040_hal::pac`
+    Path,
+    /// An array whose elements are all of the given kind, e.g. `[a, b]`
+    List(Box<ArgKind>),
+    /// A shared reference to the given kind, e.g. `&config`
+    Ref(Box<ArgKind>),
+    /// An assignment of the second kind to the first one, e.g. `TIM3 = 2`
+    Assign(Box<ArgKind>, Box<ArgKind>),
+    /// Any of the given kinds
+    OneOf(Vec<ArgKind>),
+    /// Any expression, the pass consuming the argument validates it
+    Expr,
+}
+
+impl ArgKind {
+    /// Shorthand for [ArgKind::List]
+    pub fn list(element: ArgKind) -> Self {
+        Self::List(Box::new(element))
+    }
+
+    /// Shorthand for [ArgKind::Ref]
+    pub fn reference(kind: ArgKind) -> Self {
+        Self::Ref(Box::new(kind))
+    }
+
+    /// Shorthand for [ArgKind::Assign]
+    pub fn assign(left: ArgKind, right: ArgKind) -> Self {
+        Self::Assign(Box::new(left), Box::new(right))
+    }
+
+    /// A list of shared resources accessed by a task, e.g. `[counter, &config]`
+    pub fn shared_resources() -> Self {
+        Self::list(Self::OneOf(vec![Self::Ident, Self::reference(Self::Ident)]))
+    }
+
+    fn matches(&self, value: Option<&Expr>) -> bool {
+        match (self, value) {
+            (Self::Flag, None) => true,
+            (Self::OneOf(kinds), value) => kinds.iter().any(|k| k.matches(value)),
+            (_, None) | (Self::Flag, Some(_)) => false,
+            (Self::Bool, Some(expr)) => matches!(
+                expr,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Bool(_),
+                    ..
+                })
+            ),
+            (Self::Int, Some(expr)) => matches!(
+                expr,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(_),
+                    ..
+                })
+            ),
+            (Self::Ident, Some(Expr::Path(path))) => {
+                path.qself.is_none() && path.path.get_ident().is_some()
+            }
+            (Self::Path, Some(Expr::Path(path))) => path.qself.is_none(),
+            (Self::List(element), Some(Expr::Array(array))) => {
+                array.elems.iter().all(|e| element.matches(Some(e)))
+            }
+            (Self::Ref(kind), Some(Expr::Reference(reference))) => {
+                reference.mutability.is_none() && kind.matches(Some(&reference.expr))
+            }
+            (Self::Assign(left, right), Some(Expr::Assign(assign))) => {
+                left.matches(Some(&assign.left)) && right.matches(Some(&assign.right))
+            }
+            (Self::Expr, Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn accepts_no_value(&self) -> bool {
+        self.matches(None)
+    }
+
+    fn describe(&self, plural: bool) -> String {
+        let (one, many) = match self {
+            Self::Flag => ("no value", "no values"),
+            Self::Bool => ("`true` or `false`", "booleans"),
+            Self::Int => ("an integer literal", "integer literals"),
+            Self::Ident => ("an identifier", "identifiers"),
+            Self::Path => ("a path", "paths"),
+            Self::Expr => ("an expression", "expressions"),
+            Self::List(element) => {
+                let element = element.describe(true);
+                return match plural {
+                    false => format!("a list of {element}"),
+                    true => format!("lists of {element}"),
+                };
+            }
+            Self::Ref(kind) => {
+                let kind = kind.describe(plural);
+                return match plural {
+                    false => format!("a reference to {kind}"),
+                    true => format!("references to {kind}"),
+                };
+            }
+            Self::Assign(left, right) => {
+                let (left, right) = (left.describe(plural), right.describe(plural));
+                return match plural {
+                    false => format!("an assignment of {right} to {left}"),
+                    true => format!("assignments of {right} to {left}"),
+                };
+            }
+            Self::OneOf(kinds) => {
+                let kinds: Vec<_> = kinds.iter().map(|k| k.describe(plural)).collect();
+                return kinds.join(" or ");
+            }
+        };
+        if plural { many } else { one }.to_string()
+    }
+
+    /// Combines two registrations of the same argument
+    fn merge(self, other: ArgKind) -> ArgKind {
+        if self == other {
+            return self;
+        }
+        let mut kinds = match self {
+            Self::OneOf(kinds) => kinds,
+            kind => vec![kind],
+        };
+        match other {
+            Self::OneOf(others) => kinds.extend(others),
+            other => kinds.push(other),
+        }
+        Self::OneOf(kinds)
+    }
+}
+
+impl Display for ArgKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(false))
+    }
+}
+
+/// The attributes and attribute arguments a pass understands, see the [module level documentation](self).
+#[derive(Debug, Default, Clone)]
+pub struct AttrSchema {
+    /// attribute name -> (argument name -> kind)
+    attributes: IndexMap<String, IndexMap<String, ArgKind>>,
+}
+
+impl AttrSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the argument `name` of the `#[attribute(...)]` attribute. Use [APP_ATTR] for the arguments of the
+    /// `#[app(...)]` attribute.
+    pub fn arg(mut self, attribute: &str, name: &str, kind: ArgKind) -> Self {
+        let args = self.attributes.entry(attribute.to_string()).or_default();
+        let kind = match args.shift_remove(name) {
+            Some(registered) => registered.merge(kind),
+            None => kind,
+        };
+        args.insert(name.to_string(), kind);
+        self
+    }
+
+    /// Registers an attribute that takes no arguments (besides the ones registered with [Self::arg])
+    pub fn attribute(mut self, attribute: &str) -> Self {
+        self.attributes.entry(attribute.to_string()).or_default();
+        self
+    }
+
+    /// Adds all the attributes and arguments registered in `other` to this schema
+    pub fn merge(mut self, other: AttrSchema) -> Self {
+        for (attribute, args) in other.attributes {
+            self = self.attribute(&attribute);
+            for (name, kind) in args {
+                self = self.arg(&attribute, &name, kind);
+            }
+        }
+        self
+    }
+
+    /// Reports (in `ctx`) all the arguments of `app_args` and of the attributes in `app_mod` that do not match this
+    /// schema.
+    pub fn validate(&self, ctx: &PassContext, app_args: &TokenStream2, app_mod: &ItemMod) {
+        if let Some(args) = self.attributes.get(APP_ATTR) {
+            self.validate_args(ctx, APP_ATTR, args, app_args.clone());
+        }
+
+        let items = app_mod.content.iter().flat_map(|(_, items)| items.iter());
+        for item in items {
+            let attrs = match item {
+                Item::Fn(f) => &f.attrs,
+                Item::Struct(s) => &s.attrs,
+                _ => continue,
+            };
+            for attr in attrs {
+                let Some(name) = attr.path().get_ident().map(|i| i.to_string()) else {
+                    continue;
+                };
+                let Some(args) = self.attributes.get(&name) else {
+                    continue;
+                };
+                match &attr.meta {
+                    Meta::Path(_) => {}
+                    Meta::List(list) => self.validate_args(ctx, &name, args, list.tokens.clone()),
+                    Meta::NameValue(nv) => ctx.error(
+                        nv.span(),
+                        format!("expected `#[{name}]` or `#[{name}(...)]`"),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn validate_args(
+        &self,
+        ctx: &PassContext,
+        attribute: &str,
+        registered: &IndexMap<String, ArgKind>,
+        tokens: TokenStream2,
+    ) {
+        let mut seen: Vec<String> = Vec::new();
+        let result = syn::meta::parser(|meta| {
+            let value = if meta.input.peek(Token![=]) {
+                Some(meta.value()?.parse::<Expr>()?)
+            } else {
+                None
+            };
+            let Some(ident) = meta.path.get_ident() else {
+                ctx.error(meta.path.span(), "expected an argument name");
+                return Ok(());
+            };
+            let name = ident.to_string();
+
+            if seen.contains(&name) {
+                ctx.error(ident.span(), format!("duplicate argument `{name}`"));
+                return Ok(());
+            }
+            seen.push(name.clone());
+
+            let Some(kind) = registered.get(&name) else {
+                ctx.error(
+                    ident.span(),
+                    unknown_arg_message(attribute, &name, registered),
+                );
+                return Ok(());
+            };
+            match value {
+                None if !kind.accepts_no_value() => ctx.error(
+                    ident.span(),
+                    format!("argument `{name}` expects a value: {kind}"),
+                ),
+                Some(value) if *kind == ArgKind::Flag => ctx.error(
+                    value.span(),
+                    format!("argument `{name}` does not take a value"),
+                ),
+                Some(value) if !kind.matches(Some(&value)) => {
+                    ctx.error(value.span(), format!("argument `{name}` expects {kind}"))
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+        .parse2(tokens);
+        let _ = ctx.ok_or_report(result);
+    }
+}
+
+fn unknown_arg_message(
+    attribute: &str,
+    name: &str,
+    registered: &IndexMap<String, ArgKind>,
+) -> String {
+    let attribute = match attribute {
+        APP_ATTR => String::from("#[app]"),
+        other => format!("#[{other}]"),
+    };
+    let suggestion = registered
+        .keys()
+        .map(|candidate| (candidate, strsim::jaro_winkler(name, candidate)))
+        .filter(|(_, similarity)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    match suggestion {
+        Some((candidate, _)) => {
+            format!("unknown argument `{name}` for `{attribute}`, did you mean `{candidate}`?")
+        }
+        None if registered.is_empty() => format!("`{attribute}` takes no arguments"),
+        None => {
+            let expected: Vec<_> = registered.keys().map(|k| format!("`{k}`")).collect();
+            format!(
+                "unknown argument `{name}` for `{attribute}`, expected one of: {}",
+                expected.join(", ")
+            )
+        }
+    }
+}
@@ -2,6 +2,9 @@ use super::*;
 
 /// Interface for providing the low-level hardware bindings specific for a target(s) (A.k.a The Backend) to be used during code generation phase
 /// of the **Core Compilation Pass*.
+///
+/// Every method receives the [PassContext] of the current macro expansion, which holds the facts published by the
+/// compilation passes that ran before the core pass (e.g. the dispatchers a pass generated).
 pub trait CorePassBackend {
     /// # Setting up the system
     /// Implementation must return the TokenStream to be inserted **AFTER** the call to Global `#[init]` and tasks init() functions,
@@ -13,7 +16,8 @@ pub trait CorePassBackend {
     /// ## Use case
     /// This trait method is meant to cover the following use cases:
     /// - enabling interrupt lines used by the application
-    /// - setting priority of interrupts, and similar initializations depending on specific hardware details
+    /// - setting priority of interrupts, and similar initializations depending on specific hardware details. The
+    ///   priorities of `app_analysis` are logical, see [CorePassBackend::hardware_priority].
     /// - multicore systems where a master core needs to wake-up and initialize other cores (see rp2040 distribution as an example)
     /// ## Note
     /// This function will be called several times in case of a multicore system, each time with different `app_info` and `app_analysis`.
@@ -23,6 +27,7 @@ pub trait CorePassBackend {
     /// - `app_analysis`: Information about the analyzed application. For single core this will be the analysis of the full application. For multicore, this represents the analysis of a sub-application corresponding to a specific core.
     fn post_init(
         &self,
+        ctx: &PassContext,
         app_args: &AppArgs,
         app_info: &SubApp,
         app_analysis: &SubAnalysis,
@@ -52,7 +57,7 @@ pub trait CorePassBackend {
     /// impl RticMutex for __resource1_mutex {
     ///     type ResourceType = R1Type;
     ///     // this is what the trait method argument `incomplete_lock_fn` expands to
-    ///     fn lock(&mut self, f: impl FnOnce(&mut Self::ResourceType)) {
+    ///     fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
     ///         const CEILING: u16 = 3u16; // resource ceiling
     ///         let task_priority = self.task_priority; // current task priority
     ///         let resource_ref = unsafe { &mut SHARED.assume_init_mut().resource1 };
@@ -71,15 +76,22 @@ pub trait CorePassBackend {
     ///    * System interrupt priority ceiling is raised to the value of `CEILING`.
     ///    * The closure `f` is called and `resource_ref` is passed to it as a parameter. (to execute the resource critical section).
     ///    * System interrupt priority ceiling should be restored back to `task_priority` value.
+    ///* The populated function must return the value returned by `f`, e.g. by ending with the call to a lock function
+    ///  that returns it.
     ///* If global definitions need to be generated for use in the locking implementation, the trait method which will be described next should be used to cover such need.
+    ///* `CEILING` and `task_priority` are logical priorities (the larger, the more urgent). A target with a different
+    ///  ordering of its priority levels must convert them the same way as [CorePassBackend::hardware_priority].
     ///
     /// ## Note
     /// This trait method is called for every shared resource in every sub-application.
+    /// It is also used to complete the `lock_ref` function of read-only resource proxies and the `lock_with_ceiling`
+    /// function used to lock tuples of resources, which follow the same contract.
     ///
     /// ## Debugging Tip
     /// Use ```eprintln("{}", incomplete_lock_fn.to_token_stream().to_string())``` to see the `incomplete_lock_fn` signature and already provided logic inside it.
     fn generate_resource_proxy_lock_impl(
         &self,
+        ctx: &PassContext,
         app_args: &AppArgs,
         app_info: &SubApp,
         incomplete_lock_fn: syn::ImplItemFn,
@@ -102,6 +114,7 @@ pub trait CorePassBackend {
     /// - `app_analysis`: Information about the analyzed application. For single core this will be the analysis of the full application. For multicore, this represents the analysis of a sub-application corresponding to a specific core.
     fn generate_global_definitions(
         &self,
+        ctx: &PassContext,
         app_args: &AppArgs,
         app_info: &SubApp,
         app_analysis: &SubAnalysis,
@@ -120,6 +133,7 @@ pub trait CorePassBackend {
     /// The `dispatch_task_call` token stream must be placed in between your custom logic. This tokenstream must not be mutated.
     fn wrap_task_execution(
         &self,
+        ctx: &PassContext,
         task_prio: u16, // TODO: more information needs to be provided here to cover more complex cases
         dispatch_task_call: TokenStream2,
     ) -> Option<TokenStream2>;
@@ -140,12 +154,12 @@ pub trait CorePassBackend {
     ///
     /// By default you should implement this function as
     /// ```rust
-    /// fn entry_name(&self, core: u32) -> Ident {
+    /// fn entry_name(&self, ctx: &PassContext, core: u32) -> Ident {
     ///     format_ident!("main")
     /// }
     /// ```
     ///
-    fn entry_name(&self, core: u32) -> Ident;
+    fn entry_name(&self, ctx: &PassContext, core: u32) -> Ident;
 
     /// # Customizing the default behavior of idle task
     /// When the user doesn't define an idle task, RTIC automatically defines one with a default implementation.
@@ -163,7 +177,7 @@ pub trait CorePassBackend {
     ///
     /// If None is returned, the default implementation of the [exec] method of the idle task will be an empty infinite loop
     /// that will only waste cycles !.
-    fn populate_idle_loop(&self) -> Option<TokenStream2>;
+    fn populate_idle_loop(&self, ctx: &PassContext) -> Option<TokenStream2>;
 
     /// # Non-preemptable code sections
     /// The RTIC implementation occasionally generates code that must run in a non-preemptable fashion. Therefore, a
@@ -184,7 +198,11 @@ pub trait CorePassBackend {
     /// ## Contract
     /// - You MUST not change the function signature (of `empty_body_fn`)
     /// - The generated function must re-enable interrupts at end of the critical section.
-    fn generate_interrupt_free_fn(&self, empty_body_fn: syn::ItemFn) -> syn::ItemFn;
+    fn generate_interrupt_free_fn(
+        &self,
+        ctx: &PassContext,
+        empty_body_fn: syn::ItemFn,
+    ) -> syn::ItemFn;
 
     /// # The "multibin" feature additional requirements
     /// If a distribution enables `multibin` feature to allow targeting a multi-binary target, then the distibution must:
@@ -193,7 +211,7 @@ pub trait CorePassBackend {
     ///
     /// Example implementation can be
     /// ```rust
-    /// fn multibin_shared_macro_path() -> syn::Path {
+    /// fn multibin_shared_macro_path(&self, ctx: &PassContext) -> syn::Path {
     ///     syn::parse_quote! { rtic::export::microamp::shared }
     /// }
     ///
@@ -204,7 +222,7 @@ pub trait CorePassBackend {
     /// where multibin_shared is the proc macro attribute used to indicate shared data across cores
     /// ```
     #[cfg(feature = "multibin")]
-    fn multibin_shared_macro_path(&self) -> syn::Path;
+    fn multibin_shared_macro_path(&self, ctx: &PassContext) -> syn::Path;
 
     /// # Additional user code validation
     /// Implement this method to validate/analyze the resulting parsed and analyzed user application before the code generation phase starts.
@@ -213,10 +231,60 @@ pub trait CorePassBackend {
     /// In certain cases, some checks/validation related to implementation/hardware specific details need to be made before allowing the user code to be expanded.
     /// An example, could be that the user has attempted to use an Exception line as for a dispatcher, but the distribution needs to forbid that.
     /// Implementing this trait method, gives the ability to enforcing such checks.
-    fn pre_codegen_validation(&self, app: &App, analysis: &Analysis) -> syn::Result<()>;
+    fn pre_codegen_validation(
+        &self,
+        ctx: &PassContext,
+        app: &App,
+        analysis: &Analysis,
+    ) -> syn::Result<()>;
+
+    /// Implementation must return the default task priority to be used in tasks when priority argument value is not provided by the user.
+    /// This is a logical priority, usually the least urgent of [BackendCapabilities::priorities].
+    ///
+    /// This method is called before any compilation pass runs, so `ctx` is still empty at that point.
+    fn default_task_priority(&self, ctx: &PassContext) -> u16;
+
+    /// Priority the idle task runs at, i.e. the lowest priority of the target. The idle task accesses its shared
+    /// resources with this priority and the resource ceilings account for it.
+    ///
+    /// This method is called before any compilation pass runs, so `ctx` is still empty at that point.
+    fn idle_task_priority(&self, _ctx: &PassContext) -> u16 {
+        0
+    }
 
-    /// Implementation must return the default task priority to be used in idle task and tasks when priority argument value is not provided by the user.
-    fn default_task_priority(&self) -> u16;
+    /// What the target supports: the valid task priorities, the number of cores, etc. The **Core Pass** rejects the
+    /// applications that exceed them before code generation, so the other methods of the backend only ever see
+    /// priorities within [BackendCapabilities::priorities] and don't need to clamp them.
+    ///
+    /// This method is called before any compilation pass runs, so `ctx` is still empty at that point. The returned
+    /// capabilities are published in `ctx` for the passes. By default, the target has no restrictions.
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        BackendCapabilities::default()
+    }
+
+    /// Maps the `logical` priority of a task, a dispatcher or a resource ceiling on `core` to the priority level of
+    /// the hardware, to be used by [CorePassBackend::post_init] and by the locks when setting up interrupts. See
+    /// [crate::capabilities] for the logical priorities.
+    ///
+    /// For instance, the NVIC of a Cortex-M with 4 priority bits has 16 levels with 15 being the least urgent, so
+    /// the logical priorities `1..=16` map to the levels `16 - logical`. By default, the logical priorities are the
+    /// hardware levels, for targets where a larger level is more urgent.
+    fn hardware_priority(&self, _ctx: &PassContext, _core: u32, logical: u16) -> u16 {
+        logical
+    }
+
+    /// Whether `binds`, the argument of `#[task(binds = ...)]` of a task on `core`, names a core exception (e.g.
+    /// `SysTick` or `PendSV` on Cortex-M) rather than an interrupt.
+    ///
+    /// Exceptions and interrupts are reported separately in [SubAnalysis::used_exceptions] and
+    /// [SubAnalysis::used_irqs], since they are usually enabled and prioritized through different peripherals. A
+    /// backend whose locks can not mask exceptions should also reject the tasks bound to an exception that access
+    /// shared resources, see [Analysis::reject_exception_resource_users].
+    ///
+    /// The task handler keeps the name of `binds` in both cases. By default, everything is an interrupt.
+    fn is_exception(&self, _ctx: &PassContext, _core: u32, _binds: &Ident) -> bool {
+        false
+    }
 
     /// Attribute macros to add to the entry point
     ///
@@ -233,7 +301,7 @@ pub trait CorePassBackend {
     ///     loop {}
     /// }
     /// ```
-    fn entry_attrs(&self) -> Vec<syn::Attribute> {
+    fn entry_attrs(&self, _ctx: &PassContext) -> Vec<syn::Attribute> {
         // vec![syn::parse_quote!(#[some_random_attr1]) , syn::parse_quote!(#[some_random_attr2])]
         vec![]
     }
@@ -251,7 +319,7 @@ pub trait CorePassBackend {
     /// #[riscv_rt::interrupt]
     /// fn Uart() {}
     /// ```
-    fn task_attrs(&self) -> Vec<syn::Attribute> {
+    fn task_attrs(&self, _ctx: &PassContext) -> Vec<syn::Attribute> {
         // vec![syn::parse_quote!(#[some_random_attr1]) , syn::parse_quote!(#[some_random_attr2])]
         vec![]
     }
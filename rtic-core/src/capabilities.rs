@@ -0,0 +1,140 @@
+//! Description of what a target can run, see [BackendCapabilities].
+//!
+//! # Priorities
+//! The user application, the analysis of the **Core Pass** and the compilation passes all use the same *logical*
+//! priorities: the larger the number, the more urgent the task. The resource ceilings are the highest logical priority
+//! among their users, and the idle task runs at [crate::CorePassBackend::idle_task_priority] (0 by default), below
+//! every other task. A backend describes the logical priorities its tasks can be given with
+//! [BackendCapabilities::priorities], and maps them to the priority levels of the hardware with
+//! [crate::CorePassBackend::hardware_priority] (e.g. on Cortex-M, the lowest logical priority is the largest NVIC
+//! level).
+//!
+//! # Checks
+//! The **Core Pass** checks the analyzed application against the capabilities returned by
+//! [crate::CorePassBackend::capabilities] before generating any code, so that an application the target can not run
+//! is rejected with an error pointing at the offending item instead of being silently adapted by the backend (e.g. by
+//! clamping a priority). The capabilities are also published as a fact of the [crate::PassContext] before any pass
+//! runs, for the passes that generate code depending on them (e.g. cross-core spawns of software tasks).
+
+use std::ops::RangeInclusive;
+
+use crate::diagnostics::combine_errors;
+use crate::model::{INPUT_STAGE, TaskOrigins};
+use crate::{Analysis, App};
+
+/// What a target supports, as returned by [crate::CorePassBackend::capabilities]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    /// Logical priorities the tasks can be given, from the least to the most urgent
+    pub priorities: RangeInclusive<u16>,
+    /// Maximum value of the `cores` argument of `#[app(...)]`
+    pub max_cores: u32,
+    /// Whether hardware tasks can be bound to core exceptions, see [crate::CorePassBackend::is_exception]
+    pub exceptions: bool,
+    /// Whether a core can pend an interrupt of another core, e.g. for software tasks spawned by another core
+    pub cross_core_pend: bool,
+}
+
+impl Default for BackendCapabilities {
+    /// No restrictions at all
+    fn default() -> Self {
+        Self {
+            priorities: 0..=u16::MAX,
+            max_cores: u32::MAX,
+            exceptions: true,
+            cross_core_pend: true,
+        }
+    }
+}
+
+impl BackendCapabilities {
+    /// Rejects the priorities out of [Self::priorities], the cores beyond [Self::max_cores] and the tasks bound to
+    /// exceptions when the target does not support it.
+    ///
+    /// The priority of idle is given by the backend, so it is not checked, but every other task must run above it:
+    /// a task at the priority of idle could not preempt idle, and idle could not lock out the task either. A task
+    /// added by a compilation pass is
+    /// only reported if no task of the user application has the same out of range priority on its core, since the
+    /// pass most likely derived it from such a task (e.g. the dispatchers of software tasks).
+    pub(crate) fn check(
+        &self,
+        app: &App,
+        analysis: &Analysis,
+        origins: &TaskOrigins,
+        idle_priority: u16,
+    ) -> syn::Result<()> {
+        let mut errors = Vec::new();
+
+        let cores = app.args.cores;
+        if cores > self.max_cores {
+            errors.push(syn::Error::new(
+                app.app_name.span(),
+                format!(
+                    "The application has {cores} cores, but this target has at most {}.",
+                    self.max_cores
+                ),
+            ));
+        }
+
+        let (lowest, highest) = (self.priorities.start(), self.priorities.end());
+        let valid =
+            |priority: &u16| self.priorities.contains(priority) && *priority > idle_priority;
+        for sub_app in app.sub_apps.iter() {
+            let out_of_range: Vec<_> = sub_app
+                .tasks
+                .iter()
+                .filter(|task| !valid(&task.args.priority))
+                .collect();
+            let (user_tasks, pass_tasks): (Vec<_>, Vec<_>) = out_of_range
+                .into_iter()
+                .partition(|task| origins.of(&task.name().to_string()) == INPUT_STAGE);
+            let pass_tasks = pass_tasks.into_iter().filter(|task| {
+                user_tasks
+                    .iter()
+                    .all(|user_task| user_task.args.priority != task.args.priority)
+            });
+
+            for task in user_tasks.iter().copied().chain(pass_tasks) {
+                let origin = origins.of(&task.name().to_string());
+                let task_name = match origin {
+                    INPUT_STAGE => format!("`{}`", task.name()),
+                    pass => format!("`{}` (added by the `{pass}` pass)", task.name()),
+                };
+                let priority = task.args.priority;
+                let message = if self.priorities.contains(&priority) {
+                    format!(
+                        "The task {task_name} has priority {priority}, but the tasks of this target must have a higher \
+                        priority than idle ({idle_priority})."
+                    )
+                } else {
+                    format!(
+                        "The task {task_name} has priority {priority}, but the priorities of this target range from \
+                        {lowest} (lowest) to {highest} (highest)."
+                    )
+                };
+                errors.push(syn::Error::new(
+                    task.args.priority_span.unwrap_or(task.name().span()),
+                    message,
+                ));
+            }
+        }
+
+        if !self.exceptions {
+            let exceptions = analysis
+                .sub_analysis
+                .iter()
+                .flat_map(|analysis| analysis.used_exceptions.iter());
+            for (exception, _) in exceptions {
+                errors.push(syn::Error::new(
+                    exception.span(),
+                    format!(
+                        "`{exception}` is an exception, and this target does not support binding tasks to \
+                        exceptions."
+                    ),
+                ));
+            }
+        }
+
+        combine_errors(errors).map_or(Ok(()), Err)
+    }
+}
@@ -5,7 +5,7 @@ use syn::{ImplItem, ImplItemFn, parse_quote};
 #[cfg(feature = "multibin")]
 use crate::multibin::{multibin_cfg_core, multibin_cfg_not_core};
 use crate::{
-    CorePassBackend,
+    CorePassBackend, PassContext,
     codegen::utils,
     parser::ast::{HardwareTask, RticTask, SharedResources},
 };
@@ -16,7 +16,7 @@ use crate::{
 
 impl RticTask {
     /// Generates task definition, Context struct, resource proxies and binds task to appropriate interrupt
-    pub fn generate_task_def(&self, shared_resources: Option<&SharedResources>) -> TokenStream2 {
+    pub fn generate_task_def(&self, shared_resources: &[SharedResources]) -> TokenStream2 {
         let cfg_core = multibin::multibin_cfg_core(self.args.core);
         let allow_unused_not_core =
             multibin::multibin_cfg_attr_not_core(self.args.core, quote! {allow(unused)});
@@ -32,7 +32,7 @@ impl RticTask {
             .map(|t| process_task_impl(t, self.args.core));
 
         let task_prio_impl = self.generate_priority_func();
-        let shared_mod = shared_resources.map(|shared| shared.generate_shared_for_task(self));
+        let shared_mod = SharedResources::generate_shared_for_task(shared_resources, self);
         let current_current_fn = self.generate_current_core_fn();
         quote! {
             #cfg_core
@@ -123,9 +123,10 @@ impl HardwareTask {
     pub fn generate_hw_task_to_irq_binding(
         &self,
         implementation: &dyn CorePassBackend,
+        ctx: &PassContext,
     ) -> Option<TokenStream2> {
         let cfg_core = multibin::multibin_cfg_core(self.args.core);
-        let task_attrs = implementation.task_attrs();
+        let task_attrs = implementation.task_attrs(ctx);
         let task_static_handle = &self.name_uppercase();
         let task_irq_handler = &self.args.binds.clone()?;
 
@@ -134,7 +135,7 @@ impl HardwareTask {
         };
 
         let task_dispatch_call = implementation
-            .wrap_task_execution(self.args.priority, default_task_dispatch_call.clone())
+            .wrap_task_execution(ctx, self.args.priority, default_task_dispatch_call.clone())
             .unwrap_or(default_task_dispatch_call);
 
         Some(quote! {
@@ -189,13 +190,13 @@ impl HardwareTask {
 
         // find the init function and correct its signature
         task_impl.items.iter_mut().for_each(|item| {
-            if let ImplItem::Fn(f) = item {
-                if f.sig.ident == "init" {
-                    let default_init: ImplItemFn = parse_quote!(
-                        fn init(_: ()) -> Self {}
-                    );
-                    f.sig = default_init.sig; // correct the signature
-                }
+            if let ImplItem::Fn(f) = item
+                && f.sig.ident == "init"
+            {
+                let default_init: ImplItemFn = parse_quote!(
+                    fn init(_: ()) -> Self {}
+                );
+                f.sig = default_init.sig; // correct the signature
             }
         });
 
@@ -1,8 +1,7 @@
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{ToTokens, format_ident, quote};
+use quote::{format_ident, quote};
 use task_init::{generate_late_init_tasks_struct, generate_late_tasks_init_calls};
 
-use crate::CorePassBackend;
 use crate::analysis::Analysis;
 use crate::multibin::multibin_cfg_core;
 use crate::parser::ast::{RticTask, SharedResources};
@@ -11,6 +10,7 @@ use crate::rtic_functions::{
     INTERRUPT_FREE_FN, generate_task_traits_check_functions, get_interrupt_free_fn,
 };
 use crate::rtic_traits::get_rtic_traits_mod;
+use crate::{CorePassBackend, PassContext};
 
 pub mod hw_task;
 pub use utils::multibin;
@@ -22,11 +22,13 @@ pub struct CodeGen<'a> {
     app: &'a App,
     analysis: &'a Analysis,
     implementation: &'a dyn CorePassBackend,
+    ctx: &'a PassContext,
 }
 
 impl<'a> CodeGen<'a> {
     pub fn new(
         implementation: &'a dyn CorePassBackend,
+        ctx: &'a PassContext,
         app: &'a App,
         analysis: &'a Analysis,
     ) -> Self {
@@ -34,16 +36,18 @@ impl<'a> CodeGen<'a> {
             app,
             analysis,
             implementation,
+            ctx,
         }
     }
 
     pub fn run(&self) -> TokenStream2 {
         let app = self.app;
         let implementation = self.implementation;
+        let ctx = self.ctx;
 
         #[cfg(feature = "multibin")]
         let use_multibin_shared = {
-            let multibin_shared_path = self.implementation.multibin_shared_macro_path();
+            let multibin_shared_path = self.implementation.multibin_shared_macro_path(ctx);
             Some(quote!(use #multibin_shared_path as multibin_shared;))
         };
         #[cfg(not(feature = "multibin"))]
@@ -53,7 +57,7 @@ impl<'a> CodeGen<'a> {
         let peripheral_crate = generate_use_pac_statement(app);
         let user_includes = &app.user_includes;
         let user_code = &app.other_code;
-        let interrupt_free_fn = get_interrupt_free_fn(implementation);
+        let interrupt_free_fn = get_interrupt_free_fn(implementation, ctx);
 
         // traits
         let rtic_traits_mod = get_rtic_traits_mod();
@@ -94,6 +98,7 @@ impl<'a> CodeGen<'a> {
 
     fn generate_sub_apps(&self) -> TokenStream2 {
         let implementation = self.implementation;
+        let ctx = self.ctx;
         let iter = self
             .app
             .sub_apps
@@ -102,45 +107,76 @@ impl<'a> CodeGen<'a> {
         let args = &self.app.args;
         let apps = iter.map(|(app, analysis)| {
             let cfg_core = multibin::multibin_cfg_core(app.core);
-            let post_init = implementation.post_init(args, app, analysis);
+            let post_init = implementation.post_init(ctx, args, app, analysis);
 
             // init
             let def_init_task = &app.init.body;
             let init_task = &app.init.ident;
+            let (take_peripherals, init_args) = if args.peripherals {
+                let pac = &args.pacs[app.core as usize];
+                let peripherals = format_ident!("__rtic_device_peripherals");
+                let take = quote! {
+                    // the device peripherals of this core are taken here only, and moved into init
+                    let #peripherals = unsafe { #pac::Peripherals::steal() };
+                };
+                (take, quote!(#peripherals))
+            } else {
+                (quote!(), quote!())
+            };
             let late_init_struct = generate_late_init_tasks_struct(&analysis.late_resource_tasks);
 
             // idle
             let def_idle_task = app.idle.as_ref().map(|idle| {
-                let idle_task = idle.generate_task_def(app.shared.as_ref());
+                let idle_task = idle.generate_task_def(&app.shared);
                 Some(idle_task)
             });
 
             let call_idle_task =
-                generate_idle_call(app.idle.as_ref(), implementation.populate_idle_loop());
+                generate_idle_call(app.idle.as_ref(), implementation.populate_idle_loop(ctx));
 
             // tasks
             let tasks_def = app
                 .tasks
                 .iter()
-                .map(|task| task.generate_task_def(app.shared.as_ref()));
+                .map(|task| task.generate_task_def(&app.shared));
             let task_init_calls = app.tasks.iter().filter_map(RticTask::task_init_call);
 
             let hw_tasks_binds = app
                 .tasks
                 .iter()
-                .filter_map(|t| t.generate_hw_task_to_irq_binding(implementation));
+                .filter_map(|t| t.generate_hw_task_to_irq_binding(implementation, ctx));
 
             // shared resources
-            let shared = app.shared.as_ref();
-            let def_shared = shared.map(|shared| shared.generate_shared_resources_def());
-            let shared_resources_handle = shared.map(SharedResources::name_uppercase);
-            let shared_resources_handle = shared_resources_handle.iter();
+            let def_shared = app
+                .shared
+                .iter()
+                .map(SharedResources::generate_shared_resources_def);
             let resource_proxies = app
                 .shared
-                .as_ref()
-                .map(|shared| shared.generate_resource_proxies(implementation, args, app));
-
-            // local and shared resources initialization
+                .iter()
+                .map(|shared| shared.generate_resource_proxies(implementation, ctx, args, app));
+
+            // local and shared resources initialization.
+            // init returns the shared resources struct, or a tuple of them if the core has several #[shared] structs
+            let shared_resources_handles = app.shared.iter().map(SharedResources::name_uppercase);
+            let shared_resources_types = app.shared.iter().map(|s| &s.strct.ident);
+            let shared_resources_inits: Vec<_> = (0..app.shared.len())
+                .map(|i| format_ident!("__shared_resources_{i}"))
+                .collect();
+            let (shared_resources_ty, shared_resources_pattern) = match app.shared.len() {
+                0 => (quote!(()), quote!(_)),
+                1 => (
+                    quote!(#(#shared_resources_types)*),
+                    quote!(#(#shared_resources_inits)*),
+                ),
+                _ => (
+                    quote!((#(#shared_resources_types),*)),
+                    quote!((#(#shared_resources_inits),*)),
+                ),
+            };
+            let init_shared_resources = quote! {
+                #(unsafe {#shared_resources_handles.write(#shared_resources_inits);})* // init shared resources
+            };
             let init_system = if let Some(s) = late_init_struct.as_ref() {
                 let tasks_initializer = format_ident!("__late_task_inits");
                 let user_task_late_inits = generate_late_tasks_init_calls(
@@ -148,25 +184,25 @@ impl<'a> CodeGen<'a> {
                     &tasks_initializer,
                 );
                 let task_inits_ty = &s.ident;
-                let shared_resource_ty = shared
-                    .map(|s| s.strct.ident.to_token_stream())
-                    .unwrap_or(quote!("()"));
                 quote! {
-                    let (__shared_resources, #tasks_initializer) : (#shared_resource_ty, #task_inits_ty) = #init_task(); // call to init and get shared and local resources inits
-                    #(unsafe {#shared_resources_handle.write(__shared_resources);})* // init shared resources
+                    #take_peripherals
+                    let (#shared_resources_pattern, #tasks_initializer) : (#shared_resources_ty, #task_inits_ty) = #init_task(#init_args); // call to init and get shared and local resources inits
+                    #init_shared_resources
                     #user_task_late_inits
                 }
             } else {
                 quote! {
-                    let shared_resources = #init_task();  // call to init and get shared resources init
-                    #(unsafe {#shared_resources_handle.write(shared_resources);})* // init shared resources
+                    #take_peripherals
+                    let #shared_resources_pattern: #shared_resources_ty = #init_task(#init_args);  // call to init and get shared resources init
+                    #init_shared_resources
                 }
             };
 
             // priority masks
-            let priority_masks = implementation.generate_global_definitions(args, app, analysis);
-            let entry_attrs = implementation.entry_attrs();
-            let entry_name = implementation.entry_name(app.core);
+            let priority_masks =
+                implementation.generate_global_definitions(ctx, args, app, analysis);
+            let entry_attrs = implementation.entry_attrs(ctx);
+            let entry_name = implementation.entry_name(ctx, app.core);
 
             let interrupt_free = format_ident!("{}", INTERRUPT_FREE_FN);
 
@@ -176,7 +212,7 @@ impl<'a> CodeGen<'a> {
             quote! {
                 #[doc = #doc]
                 // define static mut shared resources
-                #def_shared
+                #(#def_shared)*
                 // init task
                 #cfg_core
                 #def_init_task
@@ -187,7 +223,7 @@ impl<'a> CodeGen<'a> {
                 // bind hw tasks to interrupts
                 #(#hw_tasks_binds)*
                 // proxies for accessing the shared resources
-                #resource_proxies
+                #(#resource_proxies)*
                 // unique type for the specific sub-app/core
                 #def_core_type
                 // Computed priority Masks
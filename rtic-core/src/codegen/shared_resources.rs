@@ -1,10 +1,13 @@
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use quote::{ToTokens, format_ident, quote};
 
-use crate::parser::ast::{RticTask, SharedResources};
-use crate::rtic_functions::get_resource_proxy_lock_fn;
-use crate::rtic_traits::MUTEX_TY;
-use crate::{multibin, AppArgs, CorePassBackend, SubApp};
+use crate::parser::ast::{RticTask, SharedElement, SharedResources};
+use crate::rtic_functions::{
+    get_resource_proxy_lock_fn, get_resource_proxy_lock_ref_fn,
+    get_resource_proxy_lock_with_ceiling_fn,
+};
+use crate::rtic_traits::{MUTEX_TY, READ_MUTEX_TY};
+use crate::{AppArgs, CorePassBackend, PassContext, SubApp, multibin};
 
 impl SharedResources {
     pub fn generate_shared_resources_def(&self) -> TokenStream2 {
@@ -24,6 +27,7 @@ impl SharedResources {
     pub fn generate_resource_proxies(
         &self,
         implementor: &dyn CorePassBackend,
+        ctx: &PassContext,
         app_params: &AppArgs,
         app_info: &SubApp,
     ) -> TokenStream2 {
@@ -35,14 +39,33 @@ impl SharedResources {
             let mutex_ty = format_ident!("{}", MUTEX_TY);
             let cfg_core = multibin::multibin_cfg_core(self.args.core);
 
-            // generate the implementation of lock function, using external implementation
-            let impl_lock_fn = get_resource_proxy_lock_fn(
-                implementor,
-                app_params,
-                app_info,
-                element,
-                &static_mut_shared_resources,
-            );
+            // generate the implementation of lock function, using external implementation unless the users of the
+            // resource can not preempt each other
+            let impl_lock_fn = if element.lock_free {
+                quote! {
+                    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
+                        // all the users of the resource run at the same priority
+                        f(unsafe { &mut *self.resource_ptr() })
+                    }
+                }
+            } else {
+                get_resource_proxy_lock_fn(
+                    implementor,
+                    ctx,
+                    app_params,
+                    app_info,
+                    element,
+                    &static_mut_shared_resources,
+                )
+                .to_token_stream()
+            };
+            let impl_lock_with_ceiling_fn =
+                get_resource_proxy_lock_with_ceiling_fn(implementor, ctx, app_params, app_info);
+            let ceiling = element.priority;
+
+            let read_only_proxy = element.has_readers.then(|| {
+                self.generate_read_only_proxy(implementor, ctx, app_params, app_info, element)
+            });
 
             quote! {
                 // Resource proxy for `#element_name`
@@ -63,8 +86,21 @@ impl SharedResources {
                 #cfg_core
                 impl #mutex_ty for #proxy_name {
                     type ResourceType = #element_ty;
+                    const CEILING: u16 = #ceiling;
                     #impl_lock_fn
+
+                    fn task_priority(&self) -> u16 {
+                        self.task_priority
+                    }
+
+                    fn resource_ptr(&mut self) -> *mut Self::ResourceType {
+                        unsafe { &mut #static_mut_shared_resources.assume_init_mut().#element_name as *mut _ }
+                    }
+
+                    #impl_lock_with_ceiling_fn
                 }
+
+                #read_only_proxy
             }
         });
         quote! {
@@ -72,8 +108,91 @@ impl SharedResources {
         }
     }
 
-    pub fn generate_shared_for_task(&self, task: &RticTask) -> TokenStream2 {
+    /// Generates the proxy given to the tasks accessing `element` as `&element`. Resources that no task modifies, and
+    /// lock free resources, are read without locking.
+    fn generate_read_only_proxy(
+        &self,
+        implementor: &dyn CorePassBackend,
+        ctx: &PassContext,
+        app_params: &AppArgs,
+        app_info: &SubApp,
+        element: &SharedElement,
+    ) -> TokenStream2 {
+        let static_mut_shared_resources = self.name_uppercase();
+        let element_name = &element.ident;
+        let element_ty = &element.ty;
+        let proxy_name = utils::get_read_only_proxy_name(element_name);
+        let read_mutex_ty = format_ident!("{}", READ_MUTEX_TY);
         let cfg_core = multibin::multibin_cfg_core(self.args.core);
+
+        let (read_fn, impl_lock_ref_fn, allow_unused_priority) = if element.reader_ceiling == 0 {
+            let read_fn = quote! {
+                #cfg_core
+                impl #proxy_name {
+                    /// The resource is never modified after init, so it can be read without locking
+                    #[inline(always)]
+                    pub fn read(&self) -> &#element_ty {
+                        unsafe { &#static_mut_shared_resources.assume_init_ref().#element_name }
+                    }
+                }
+            };
+            let lock_ref_fn = quote! {
+                fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R {
+                    f(self.read())
+                }
+            };
+            (read_fn, lock_ref_fn, quote!(#[allow(dead_code)]))
+        } else if element.lock_free {
+            let lock_ref_fn = quote! {
+                fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R {
+                    // all the users of the resource run at the same priority
+                    f(unsafe { &#static_mut_shared_resources.assume_init_ref().#element_name })
+                }
+            };
+            (quote!(), lock_ref_fn, quote!(#[allow(dead_code)]))
+        } else {
+            let lock_ref_fn = get_resource_proxy_lock_ref_fn(
+                implementor,
+                ctx,
+                app_params,
+                app_info,
+                element,
+                &static_mut_shared_resources,
+            );
+            (quote!(), quote!(#lock_ref_fn), quote!())
+        };
+
+        quote! {
+            // Read-only resource proxy for `#element_name`
+            #cfg_core
+            pub struct #proxy_name {
+                #[doc(hidden)]
+                #allow_unused_priority
+                task_priority: u16,
+            }
+
+            #cfg_core
+            impl #proxy_name {
+                #[inline(always)]
+                pub fn new(task_priority: u16) -> Self {
+                    Self { task_priority }
+                }
+            }
+
+            #read_fn
+
+            #cfg_core
+            impl #read_mutex_ty for #proxy_name {
+                type ResourceType = #element_ty;
+                #impl_lock_ref_fn
+            }
+        }
+    }
+
+    /// Generates the `shared()` API of `task`, giving access to the resources it uses among all the `shared`
+    /// structs of its core
+    pub fn generate_shared_for_task(shared: &[SharedResources], task: &RticTask) -> TokenStream2 {
+        let cfg_core = multibin::multibin_cfg_core(task.args.core);
         let task_resources_idents = &task.args.shared;
         if task_resources_idents.is_empty() {
             return quote!();
@@ -81,9 +200,13 @@ impl SharedResources {
 
         // generate `field_name : proxy_type` to use for populating struct body
         let field_and_proxytype = task_resources_idents.iter().filter_map(|resource_ident| {
-            if let Some(resource) = self.get_field(resource_ident) {
+            if let Some(resource) = shared.iter().find_map(|s| s.get_field(resource_ident)) {
                 let ident = &resource.ident;
-                let proxy_type = utils::get_proxy_name(ident);
+                let proxy_type = if task.reads_only(ident) {
+                    utils::get_read_only_proxy_name(ident)
+                } else {
+                    utils::get_proxy_name(ident)
+                };
                 Some(quote! {#ident: #proxy_type})
             } else {
                 None
@@ -135,4 +258,9 @@ pub mod utils {
     pub fn get_proxy_name(ident: &syn::Ident) -> syn::Ident {
         format_ident!("__{ident}_mutex")
     }
+
+    #[inline(always)]
+    pub fn get_read_only_proxy_name(ident: &syn::Ident) -> syn::Ident {
+        format_ident!("__{ident}_ref")
+    }
 }
@@ -1,17 +1,19 @@
 use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_quote, ImplItemFn, ItemFn};
+use syn::{ImplItemFn, ItemFn, parse_quote};
 
 use crate::{
-    multibin,
+    Analysis, AppArgs, CorePassBackend, PassContext, SubApp, multibin,
     parser::ast::{RticTask, SharedElement},
-    Analysis, AppArgs, CorePassBackend, SubApp,
 };
 
 pub const INTERRUPT_FREE_FN: &str = "__rtic_interrupt_free";
 
-pub(crate) fn get_interrupt_free_fn(implementor: &dyn CorePassBackend) -> ItemFn {
+pub(crate) fn get_interrupt_free_fn(
+    implementor: &dyn CorePassBackend,
+    ctx: &PassContext,
+) -> ItemFn {
     let fn_ident = format_ident!("{INTERRUPT_FREE_FN}");
     let critical_section_fn = parse_quote! {
         #[inline]
@@ -21,12 +23,13 @@ pub(crate) fn get_interrupt_free_fn(implementor: &dyn CorePassBackend) -> ItemFn
            // IMPLEMENTOR RESPONSIBILITY: implement a traditional interrupt critical section
         }
     };
-    implementor.generate_interrupt_free_fn(critical_section_fn)
+    implementor.generate_interrupt_free_fn(ctx, critical_section_fn)
     // TODO: we should validate if the implementor has kept the correct function signature by comparing it to the initial signature
 }
 
 pub(crate) fn get_resource_proxy_lock_fn(
     implementor: &dyn CorePassBackend,
+    ctx: &PassContext,
     app_params: &AppArgs,
     app_info: &SubApp,
     resource: &SharedElement,
@@ -35,7 +38,7 @@ pub(crate) fn get_resource_proxy_lock_fn(
     let ceiling = resource.priority;
     let resource_ident = &resource.ident;
     let lock_fn = parse_quote! {
-        fn lock(&mut self, f: impl FnOnce(&mut Self::ResourceType)) {
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R {
             // `self` refers to the resource proxy struct
 
             const CEILING: u16 = #ceiling; // resource priority ceiling
@@ -43,14 +46,64 @@ pub(crate) fn get_resource_proxy_lock_fn(
             let resource_ptr = unsafe { // get a mut pointer to the resource
                 &mut #static_mut_shared_resources.assume_init_mut().#resource_ident
             } as *mut _;
-            // IMPLEMENTOR RESPONSIBILITY: continue lock implementation here
+            // IMPLEMENTOR RESPONSIBILITY: continue lock implementation here, returning the result of `f`
             // call for example rtic::export::lock(resource_ptr, task_priority, ...., f)
         }
     };
-    implementor.generate_resource_proxy_lock_impl(app_params, app_info, lock_fn)
+    implementor.generate_resource_proxy_lock_impl(ctx, app_params, app_info, lock_fn)
     // TODO: we should validate if the implementor has kept the correct function signature by comparing it to the initial signature
 }
 
+/// Generates the `lock_with_ceiling` function of a resource proxy, which raises the priority to a ceiling known at
+/// runtime only, using the backend lock implementation on a zero-sized resource.
+pub(crate) fn get_resource_proxy_lock_with_ceiling_fn(
+    implementor: &dyn CorePassBackend,
+    ctx: &PassContext,
+    app_params: &AppArgs,
+    app_info: &SubApp,
+) -> ImplItemFn {
+    let lock_fn = parse_quote! {
+        unsafe fn lock_with_ceiling<R>(task_priority: u16, ceiling: u16, f: impl FnOnce() -> R) -> R {
+            #[allow(non_snake_case)]
+            let CEILING = ceiling; // highest ceiling among the locked resources
+            let resource_ptr = core::ptr::NonNull::<()>::dangling().as_ptr(); // the locked resources are accessed by `f`
+            let f = |_: &mut ()| f();
+            // IMPLEMENTOR RESPONSIBILITY: continue lock implementation here, returning the result of `f`
+            // call for example rtic::export::lock(resource_ptr, task_priority, ...., f)
+        }
+    };
+    implementor.generate_resource_proxy_lock_impl(ctx, app_params, app_info, lock_fn)
+}
+
+/// Same as [get_resource_proxy_lock_fn] but for the read-only `lock_ref` function, which uses the reader ceiling of
+/// the resource. The backend lock implementation is reused as is, `f` is only given a shared reference.
+pub(crate) fn get_resource_proxy_lock_ref_fn(
+    implementor: &dyn CorePassBackend,
+    ctx: &PassContext,
+    app_params: &AppArgs,
+    app_info: &SubApp,
+    resource: &SharedElement,
+    static_mut_shared_resources: &syn::Ident,
+) -> ImplItemFn {
+    let ceiling = resource.reader_ceiling;
+    let resource_ident = &resource.ident;
+    let lock_fn = parse_quote! {
+        fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R {
+            // `self` refers to the read-only resource proxy struct
+
+            const CEILING: u16 = #ceiling; // resource reader ceiling
+            let task_priority = self.task_priority; // running task priority
+            let resource_ptr = unsafe { // get a mut pointer to the resource
+                &mut #static_mut_shared_resources.assume_init_mut().#resource_ident
+            } as *mut _;
+            let f = |resource: &mut Self::ResourceType| f(resource); // only give `f` read access
+            // IMPLEMENTOR RESPONSIBILITY: continue lock implementation here, returning the result of `f`
+            // call for example rtic::export::lock(resource_ptr, task_priority, ...., f)
+        }
+    };
+    implementor.generate_resource_proxy_lock_impl(ctx, app_params, app_info, lock_fn)
+}
+
 pub(crate) fn task_trait_check_fn_name(trait_ident: &syn::Ident) -> syn::Ident {
     let trait_lower = trait_ident.to_string().to_snake_case();
     format_ident!("implements_{trait_lower}")
@@ -6,11 +6,18 @@ pub const SWT_TRAIT_TY: &str = "RticSwTask"; // FIXME: add a backend trait metho
 pub const IDLE_TRAIT_TY: &str = "RticIdleTask";
 
 pub const MUTEX_TY: &str = "RticMutex";
+pub const READ_MUTEX_TY: &str = "RticReadMutex";
+pub const MULTI_LOCK_TY: &str = "RticMultiLock";
+
+/// Largest tuple of resource proxies that can be locked at once
+pub const MULTI_LOCK_MAX_ARITY: usize = 6;
 
 pub(crate) fn get_rtic_traits_mod() -> TokenStream2 {
     let hw_task_trait = hw_task_trait();
     let idle_trait = idle_task_trait();
     let mutex_trait = mutex_trait();
+    let read_mutex_trait = read_mutex_trait();
+    let multi_lock_trait = multi_lock_trait();
     quote! {
         /// Module defining rtic traits
         pub use rtic_traits::*;
@@ -18,6 +25,8 @@ pub(crate) fn get_rtic_traits_mod() -> TokenStream2 {
             #hw_task_trait
             #idle_trait
             #mutex_trait
+            #read_mutex_trait
+            #multi_lock_trait
         }
     }
 }
@@ -56,7 +65,58 @@ fn mutex_trait() -> TokenStream2 {
     quote! {
         pub trait #mutex {
             type ResourceType;
-            fn lock(&mut self, f: impl FnOnce(&mut Self::ResourceType));
+            /// Priority ceiling of the resource
+            #[doc(hidden)]
+            const CEILING: u16;
+            fn lock<R>(&mut self, f: impl FnOnce(&mut Self::ResourceType) -> R) -> R;
+            #[doc(hidden)]
+            fn task_priority(&self) -> u16;
+            #[doc(hidden)]
+            fn resource_ptr(&mut self) -> *mut Self::ResourceType;
+            /// Runs `f` with the priority raised to `ceiling`, used to lock several resources at once
+            #[doc(hidden)]
+            unsafe fn lock_with_ceiling<R>(task_priority: u16, ceiling: u16, f: impl FnOnce() -> R) -> R;
+        }
+    }
+}
+
+fn read_mutex_trait() -> TokenStream2 {
+    let read_mutex = format_ident!("{READ_MUTEX_TY}");
+    quote! {
+        /// Read-only access to a shared resource declared as `&resource`
+        pub trait #read_mutex {
+            type ResourceType;
+            fn lock_ref<R>(&mut self, f: impl FnOnce(&Self::ResourceType) -> R) -> R;
+        }
+    }
+}
+
+/// `(a, b, ..).lock(|a, b, ..| ...)` for tuples of resource proxies: the priority is raised once, to the highest
+/// ceiling among the resources
+fn multi_lock_trait() -> TokenStream2 {
+    let mutex = format_ident!("{MUTEX_TY}");
+    let multi_lock = format_ident!("{MULTI_LOCK_TY}");
+    let impls = (2..=MULTI_LOCK_MAX_ARITY).map(|arity| {
+        let types: Vec<_> = (0..arity).map(|i| format_ident!("T{i}")).collect();
+        let ptrs: Vec<_> = (0..arity).map(|i| format_ident!("ptr{i}")).collect();
+        let indices = (0..arity).map(syn::Index::from);
+        let first = &types[0];
+        quote! {
+            impl<#(#types: #mutex,)* F: FnOnce(#(&mut #types::ResourceType),*) -> R, R> #multi_lock<F, R> for (#(#types,)*) {
+                fn lock(&mut self, f: F) -> R {
+                    let ceiling = [#(#types::CEILING),*].into_iter().max().unwrap_or_default();
+                    let task_priority = self.0.task_priority();
+                    let (#(#ptrs,)*) = (#(self.#indices.resource_ptr(),)*);
+                    unsafe { #first::lock_with_ceiling(task_priority, ceiling, || f(#(&mut *#ptrs),*)) }
+                }
+            }
+        }
+    });
+    quote! {
+        /// Locks several resources at once, e.g. `(a, b).lock(|a, b| ...)`
+        pub trait #multi_lock<F, R> {
+            fn lock(&mut self, f: F) -> R;
         }
+        #(#impls)*
     }
 }
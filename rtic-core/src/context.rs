@@ -0,0 +1,169 @@
+//! A typed, per-expansion blackboard shared by the **Compilation Passes** and the **Core Pass** backend.
+//!
+//! Every invocation of the RTIC **app** macro creates a new [PassContext] which is handed to every
+//! [crate::RticPass::run_pass] call, every [crate::RticPostPass::run_post_pass] call and every [crate::CorePassBackend]
+//! method. Passes can publish facts they computed (e.g. a list of dispatchers, or a map of assigned cores) for later
+//! passes and for the backend to consume. Facts are keyed by their type, so a pass usually defines and exports a
+//! dedicated newtype for each fact it publishes:
+//!
+//! ```rust,ignore
+//! /// Interrupts that need to be configured for parallel context stacking
+//! #[derive(Clone)]
+//! pub struct PcsDispatchers(pub Vec<syn::Ident>);
+//!
+//! // in the pass
+//! ctx.insert(PcsDispatchers(dispatchers));
+//!
+//! // in the backend
+//! let dispatchers = ctx.get::<PcsDispatchers>().map(|d| d.0).unwrap_or_default();
+//! ```
+//!
+//! The context also collects the [Diagnostic]s reported during the expansion, see [crate::diagnostics].
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use proc_macro2::Span;
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Level, Phase};
+
+/// Type-keyed storage for facts shared between the passes of a single macro expansion.
+///
+/// The context uses interior mutability so that it can be shared by reference with the backend during code
+/// generation. None of the accessors keep the storage borrowed while calling user closures, so it is fine to access
+/// the context again from within [PassContext::update] or [PassContext::with].
+#[derive(Default)]
+pub struct PassContext {
+    facts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+    diagnostics: RefCell<Diagnostics>,
+}
+
+impl PassContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes `fact`, returning the previously published fact of the same type if any
+    pub fn insert<T: Any>(&self, fact: T) -> Option<T> {
+        self.facts
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(fact))
+            .map(Self::downcast)
+    }
+
+    /// Returns a copy of the published fact of type `T`
+    pub fn get<T: Any + Clone>(&self) -> Option<T> {
+        self.facts
+            .borrow()
+            .get(&TypeId::of::<T>())
+            .and_then(|fact| fact.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Returns true if a fact of type `T` was published
+    pub fn contains<T: Any>(&self) -> bool {
+        self.facts.borrow().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Removes and returns the published fact of type `T`
+    pub fn remove<T: Any>(&self) -> Option<T> {
+        self.facts
+            .borrow_mut()
+            .remove(&TypeId::of::<T>())
+            .map(Self::downcast)
+    }
+
+    /// Calls `f` with a reference to the published fact of type `T`, if any
+    pub fn with<T: Any, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let fact = self.remove::<T>()?;
+        let result = f(&fact);
+        self.insert(fact);
+        Some(result)
+    }
+
+    /// Calls `f` with a mutable reference to the published fact of type `T`. If no such fact was published yet,
+    /// it is first initialized with its default value.
+    pub fn update<T: Any + Default, R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut fact = self.remove::<T>().unwrap_or_default();
+        let result = f(&mut fact);
+        self.insert(fact);
+        result
+    }
+
+    /// Reports an error, attributed to the currently running pass. The expansion stops once the pass finishes.
+    pub fn error(&self, span: Span, message: impl Display) {
+        self.diagnostics
+            .borrow_mut()
+            .push(Level::Error, span, message.to_string());
+    }
+
+    /// Reports a warning, attributed to the currently running pass. Warnings do not stop the expansion.
+    pub fn warning(&self, span: Span, message: impl Display) {
+        self.diagnostics
+            .borrow_mut()
+            .push(Level::Warning, span, message.to_string());
+    }
+
+    /// Reports every error combined in `error` (see [syn::Error::combine]) as a separate diagnostic
+    pub fn report(&self, error: syn::Error) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        for e in error {
+            diagnostics.push(Level::Error, e.span(), e.to_string());
+        }
+    }
+
+    /// Returns the value of `result`, or reports its errors and returns None
+    pub fn ok_or_report<T>(&self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|e| self.report(e)).ok()
+    }
+
+    /// Sets the phase the currently running pass is in. The builder resets it to [Phase::Parse] before running each
+    /// pre-core pass.
+    pub fn set_phase(&self, phase: Phase) {
+        self.diagnostics.borrow_mut().current_phase = phase;
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.diagnostics.borrow().current_phase
+    }
+
+    /// Returns true if any error was reported so far
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .borrow()
+            .reported
+            .iter()
+            .any(|d| d.level == Level::Error)
+    }
+
+    /// Returns a copy of all the diagnostics reported so far
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.borrow().reported.clone()
+    }
+
+    /// Attributes the diagnostics reported from now on to `pass`, starting in `phase`. The builder calls this before
+    /// running each pass, only test harnesses running passes on their own need to call it.
+    pub fn enter_pass(&self, pass: &str, phase: Phase) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        diagnostics.current_pass = pass.to_string();
+        diagnostics.current_phase = phase;
+    }
+
+    fn downcast<T: Any>(fact: Box<dyn Any>) -> T {
+        *fact
+            .downcast::<T>()
+            .expect("facts are always stored under the TypeId of their own type")
+    }
+}
+
+/// Fact published by the core before any pass runs: the default priority given to tasks that do not specify one,
+/// as returned by [crate::CorePassBackend::default_task_priority].
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultTaskPriority(pub u16);
+
+/// Fact published by the core before any pass runs: the priority of the idle task, as returned by
+/// [crate::CorePassBackend::idle_task_priority].
+#[derive(Debug, Clone, Copy)]
+pub struct IdleTaskPriority(pub u16);
@@ -0,0 +1,144 @@
+//! Snapshots of the user application after every stage of the pass pipeline.
+//!
+//! With the `debug_expand` feature enabled, the final expansion of the application of binary `{bin}` is written
+//! (pretty-printed) to `examples/{bin}_expanded.rs` in the project root. In addition, the intermediate stages listed in
+//! the `RTIC_EXPAND_STAGES` environment variable are written to the `examples/{bin}_stages/{app}/` directory (`{app}`
+//! being the name of the application module), one file per stage, numbered in pipeline order:
+//! - `input`: the application as written by the user
+//! - one stage per pre-core pass, named after [crate::RticPass::pass_name], holding the output of that pass
+//! - `core`: the output of the core pass
+//! - one stage per post-core pass, named after [crate::RticPostPass::pass_name]
+//!
+//! `RTIC_EXPAND_STAGES` is either `all` or a comma separated list of stage names, e.g.
+//! `RTIC_EXPAND_STAGES=input,AutoAssign,SoftwareTasks cargo build --bin my_app`. The stages of a previous build of
+//! the same application are removed when at least one stage is selected.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ItemMod;
+
+#[cfg(feature = "debug_expand")]
+use std::{cell::Cell, path::PathBuf};
+
+/// Environment variable used to select the stages to write
+pub const STAGES_ENV_VAR: &str = "RTIC_EXPAND_STAGES";
+
+pub(crate) struct ExpansionSnapshots {
+    #[cfg(feature = "debug_expand")]
+    examples_dir: Option<PathBuf>,
+    #[cfg(feature = "debug_expand")]
+    binary_name: String,
+    #[cfg(feature = "debug_expand")]
+    app_name: String,
+    /// None when all the stages are selected
+    #[cfg(feature = "debug_expand")]
+    stages: Option<Vec<String>>,
+    #[cfg(feature = "debug_expand")]
+    next_index: Cell<usize>,
+}
+
+impl ExpansionSnapshots {
+    #[cfg(feature = "debug_expand")]
+    pub fn from_env(app_name: &str) -> Self {
+        let binary_name = std::env::var("CARGO_BIN_NAME").ok();
+        let examples_dir = binary_name
+            .as_ref()
+            .and_then(|_| project_root::get_project_root().ok())
+            .map(|root| root.join("examples"));
+        let stages = match std::env::var(STAGES_ENV_VAR) {
+            Ok(stages) if stages.trim() == "all" => None,
+            Ok(stages) => Some(stages.split(',').map(|s| s.trim().to_string()).collect()),
+            Err(_) => Some(vec![]),
+        };
+        let snapshots = Self {
+            examples_dir,
+            binary_name: binary_name.unwrap_or_default(),
+            app_name: app_name.to_string(),
+            stages,
+            next_index: Cell::new(0),
+        };
+        // do not leave stale stages of a previous build of this application behind
+        let any_stage = snapshots
+            .stages
+            .as_ref()
+            .is_none_or(|stages| !stages.is_empty());
+        if let Some(dir) = snapshots.stages_dir().filter(|_| any_stage) {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+        snapshots
+    }
+
+    #[cfg(not(feature = "debug_expand"))]
+    pub fn from_env(_app_name: &str) -> Self {
+        Self {}
+    }
+
+    /// Records the application module as it is fed to the next stage, together with the `#[app(...)]` arguments
+    pub fn record_app(&self, stage: &str, args: &TokenStream2, app_mod: &ItemMod) {
+        self.record(stage, || quote!(#[app(#args)] #app_mod));
+    }
+
+    /// Records expanded code
+    pub fn record(&self, stage: &str, code: impl FnOnce() -> TokenStream2) {
+        #[cfg(feature = "debug_expand")]
+        {
+            let index = self.next_index.get();
+            self.next_index.set(index + 1);
+            let selected = match &self.stages {
+                None => true,
+                Some(stages) => stages.iter().any(|s| s == stage),
+            };
+            if let Some(dir) = self.stages_dir().filter(|_| selected) {
+                let file_name: String = stage
+                    .chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() || c == '-' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                let _ = std::fs::create_dir_all(&dir);
+                let _ = std::fs::write(
+                    dir.join(format!("{index:02}_{file_name}.rs")),
+                    pretty_print(code()),
+                );
+            }
+        }
+        #[cfg(not(feature = "debug_expand"))]
+        let _ = (stage, code);
+    }
+
+    /// Records the final expansion
+    pub fn record_final(&self, code: &TokenStream2) {
+        #[cfg(feature = "debug_expand")]
+        if let Some(dir) = self.examples_dir.as_ref() {
+            let _ = std::fs::create_dir_all(dir);
+            let _ = std::fs::write(
+                dir.join(format!("{}_expanded.rs", self.binary_name)),
+                pretty_print(code.clone()),
+            );
+        }
+        #[cfg(not(feature = "debug_expand"))]
+        let _ = code;
+    }
+
+    #[cfg(feature = "debug_expand")]
+    fn stages_dir(&self) -> Option<PathBuf> {
+        let dir = self.examples_dir.as_ref()?;
+        Some(
+            dir.join(format!("{}_stages", self.binary_name))
+                .join(&self.app_name),
+        )
+    }
+}
+
+/// Formats `code` as a Rust source file, falls back to the unformatted tokens if `code` is not a valid file
+#[cfg(feature = "debug_expand")]
+fn pretty_print(code: TokenStream2) -> String {
+    match syn::parse2::<syn::File>(code.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => code.to_string(),
+    }
+}
@@ -0,0 +1,134 @@
+//! Errors and warnings reported by the **Compilation Passes** and the **Core Pass** during a macro expansion.
+//!
+//! Instead of stopping at the first mistake found in the user application, passes can report as many diagnostics as
+//! they find through the [crate::PassContext] (see [crate::PassContext::error], [crate::PassContext::warning] and
+//! [crate::PassContext::report]). Every diagnostic is tagged with the name of the pass that reported it and the
+//! [Phase] that pass was in. Once a pass finishes with errors, the builder stops the expansion and emits all the
+//! collected diagnostics at once: errors as `compile_error!`s and warnings as uses of a `#[deprecated]` item.
+
+use std::fmt::Display;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+
+/// Name used to tag the diagnostics reported by the **Core Pass** and its backend
+pub const CORE_PASS_NAME: &str = "core";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// The step of a pass a diagnostic was reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Phase {
+    /// Parsing and validating the user syntax
+    #[default]
+    Parse,
+    /// Analyzing the parsed application
+    Analysis,
+    /// Generating the expanded code
+    Codegen,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Analysis => write!(f, "analysis"),
+            Phase::Codegen => write!(f, "codegen"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub span: Span,
+    pub message: String,
+    /// [crate::RticPass::pass_name] of the pass that reported the diagnostic
+    pub pass: String,
+    pub phase: Phase,
+}
+
+impl Diagnostic {
+    /// The message shown to the user, including the pass attribution
+    pub fn rendered_message(&self) -> String {
+        format!(
+            "{}\n  = note: reported by the `{}` compilation pass during the {} phase",
+            self.message, self.pass, self.phase
+        )
+    }
+
+    /// Generates the code that makes the compiler show the diagnostic. `index` must be unique among the
+    /// diagnostics emitted by the same macro expansion.
+    pub fn to_token_stream(&self, index: usize) -> TokenStream2 {
+        let message = self.rendered_message();
+        match self.level {
+            Level::Error => syn::Error::new(self.span, message).to_compile_error(),
+            Level::Warning => {
+                // there is no stable way of emitting warnings from proc macros, so we make the compiler
+                // warn about the use of a deprecated item instead
+                let def_ident = format_ident!("__rtic_warning_{index}");
+                let use_ident = format_ident!("__rtic_warning_{index}", span = self.span);
+                let use_stmt = quote_spanned!(self.span=> let _ = #use_ident;);
+                quote! {
+                    const _: () = {
+                        #[deprecated(note = #message)]
+                        #[allow(non_camel_case_types)]
+                        struct #def_ident;
+                        #use_stmt
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Diagnostics collected by the [crate::PassContext] of a macro expansion
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics {
+    pub current_pass: String,
+    pub current_phase: Phase,
+    pub reported: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn push(&mut self, level: Level, span: Span, message: String) {
+        self.reported.push(Diagnostic {
+            level,
+            span,
+            message,
+            pass: self.current_pass.clone(),
+            phase: self.current_phase,
+        })
+    }
+}
+
+/// Combines all the `errors` into a single [syn::Error], returns None if there are no errors
+pub fn combine_errors(errors: impl IntoIterator<Item = syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().reduce(|mut combined, e| {
+        combined.combine(e);
+        combined
+    })
+}
+
+/// Collects all the `Ok` values, or combines all the errors into a single [syn::Error] if at least one of the
+/// results is an error. Use this instead of `?` to report all the mistakes found in a list of items at once.
+pub fn collect_results<T>(
+    results: impl IntoIterator<Item = syn::Result<T>>,
+) -> syn::Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(e) => errors.push(e),
+        }
+    }
+    match combine_errors(errors) {
+        Some(error) => Err(error),
+        None => Ok(values),
+    }
+}
@@ -11,45 +11,60 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use std::sync::atomic::AtomicU16;
-use std::sync::atomic::Ordering;
 
 use proc_macro2::{Ident, TokenStream as TokenStream2};
-use syn::{parse_macro_input, ItemMod};
+use quote::ToTokens;
+use syn::ItemMod;
 
 pub use common_internal::rtic_functions;
 pub use common_internal::rtic_traits;
 
 pub use analysis::{Analysis, SubAnalysis};
+pub use attr_schema::{ArgKind, AttrSchema};
 pub use backend::CorePassBackend;
-pub use codegen::multibin;
+pub use capabilities::BackendCapabilities;
 use codegen::CodeGen;
+pub use codegen::multibin;
+pub use context::PassContext;
+use context::{DefaultTaskPriority, IdleTaskPriority};
+use debug_expand::ExpansionSnapshots;
+use diagnostics::{CORE_PASS_NAME, Diagnostic, Phase};
+use model::{AppModel, INPUT_STAGE, TaskOrigins};
 pub use parser::ast::AppArgs;
 pub use parser::{App, SubApp};
+pub use pass_order::PassDependencies;
 
 mod analysis;
+pub mod attr_schema;
 mod backend;
+pub mod capabilities;
 mod codegen;
 mod common_internal;
+pub mod context;
+pub mod debug_expand;
+pub mod diagnostics;
 pub mod errors;
+pub mod model;
 pub mod parse_utils;
 
 mod parser;
-
-static DEFAULT_TASK_PRIORITY: AtomicU16 = AtomicU16::new(0);
+mod pass_order;
 
 /// A trait that allows defining a **Compilation Pass**.
 ///
 /// A **Compilation Pass** can be thought of as a (partial) proc-macro that expands parts of the user RTIC application
-/// once all the compilation passes provided using [RticMacroBuilder::bind_pre_core_pass] and
-/// [RticMacroBuilder::bind_post_core_pass] are run. The resulting code should be comprised only of *init*, *idle* ,
+/// once all the compilation passes provided using [RticMacroBuilder::bind_pre_core_pass] are run.
+/// The resulting code should be comprised only of *init*, *idle* ,
 /// *shared resources* and *tasks* (that may be bound to interrupts) that share those resources. The **Core Pass**
 /// then will take over from there to generate all the necessary logic and expand the user application further to an
 /// application understandable by the Rust compiler.
 pub trait RticPass {
-    /// Runs the (partial) proc-macro logic that allows extending the basic RTIC syntax
+    /// Runs the (partial) proc-macro logic that allows extending the basic RTIC syntax.
+    ///
+    /// `ctx` is shared with all other passes and the backend of the current macro expansion, see [PassContext].
     fn run_pass(
         &self,
+        ctx: &PassContext,
         args: TokenStream2,
         app_mod: ItemMod,
     ) -> syn::Result<(TokenStream2, ItemMod)>;
@@ -57,6 +72,68 @@ pub trait RticPass {
     /// Returns a human readable name/alias used to identify the pass. This identifier will show np in errors for example
     /// to help knowing exactly which compilation pass has failed in that case.
     fn pass_name(&self) -> &str;
+
+    /// Declares the syntax this pass consumes and produces, and which passes it must run before/after.
+    /// [RticMacroBuilder] uses this information to order the bound passes. See [PassDependencies] for details.
+    ///
+    /// By default a pass declares no dependencies and runs in the order it was bound.
+    fn dependencies(&self) -> PassDependencies {
+        PassDependencies::default()
+    }
+
+    /// Registers the attributes and attribute arguments this pass introduces (e.g. `sw_task` and its `priority`),
+    /// together with the kind of value they accept. Before running the passes, [RticMacroBuilder] rejects the
+    /// arguments of the user application that neither the core pass nor any bound pass registered. See [AttrSchema].
+    ///
+    /// Arguments a pass only reads and that are registered by the pass introducing them don't need to be registered
+    /// again. By default a pass registers nothing.
+    fn attribute_schema(&self) -> AttrSchema {
+        AttrSchema::default()
+    }
+}
+
+/// A trait that allows defining a **Post-Core Compilation Pass**.
+///
+/// Post-core passes run after the **Core Pass** has expanded the user application. They receive the fully expanded
+/// application module together with the parsed [App] and its [Analysis] that were used to generate it, and can
+/// transform the expanded code further. For example to add instrumentation, attach linker section attributes or
+/// report information about the generated application.
+///
+/// Post-core passes are run in the order they were bound using [RticMacroBuilder::bind_post_core_pass].
+pub trait RticPostPass {
+    /// Runs the post-core proc-macro logic on the `expanded` application module
+    fn run_post_pass(
+        &self,
+        ctx: &PassContext,
+        app: &App,
+        analysis: &Analysis,
+        expanded: ItemMod,
+    ) -> syn::Result<ItemMod>;
+
+    /// Returns a human readable name/alias used to identify the pass in errors
+    fn pass_name(&self) -> &str;
+}
+
+/// Result of running all the passes bound to a [RticMacroBuilder] on a user application
+pub struct Expansion {
+    /// The expanded application, None if any pass reported errors
+    pub code: Option<TokenStream2>,
+    /// All the errors and warnings reported during the expansion
+    pub diagnostics: Vec<Diagnostic>,
+    /// The model of the application, None if the expansion stopped before the analysis of the **Core Pass** completed.
+    /// See [model].
+    pub model: Option<AppModel>,
+}
+
+impl Expansion {
+    /// Returns the expanded code followed by the code emitting the diagnostics
+    pub fn to_token_stream(&self) -> TokenStream2 {
+        let mut code = self.code.clone().unwrap_or_default();
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            code.extend(diagnostic.to_token_stream(i));
+        }
+        code
+    }
 }
 
 /// This should be used to compose an **RTIC distribution**. In other words, it allows building the RTIC **app** macro
@@ -65,7 +142,7 @@ pub trait RticPass {
 pub struct RticMacroBuilder {
     core: Box<dyn CorePassBackend>,
     pre_std_passes: Vec<Box<dyn RticPass>>,
-    post_std_passes: Vec<Box<dyn RticPass>>,
+    post_std_passes: Vec<Box<dyn RticPostPass>>,
 }
 
 impl RticMacroBuilder {
@@ -77,14 +154,17 @@ impl RticMacroBuilder {
         }
     }
 
-    /// Binds a **Compilation Pass** that will run before the **Core Pass**
+    /// Binds a **Compilation Pass** that will run before the **Core Pass**.
+    ///
+    /// The execution order of the pre-core passes is derived from their [RticPass::dependencies], binding order
+    /// only matters for passes that do not depend on each other.
     pub fn bind_pre_core_pass<P: RticPass + 'static>(&mut self, pass: P) -> &mut Self {
         self.pre_std_passes.push(Box::new(pass));
         self
     }
 
-    /// Binds a **Compilation Pass** that will run after the **Core Pass**
-    pub fn bind_post_core_pass<P: RticPass + 'static>(&mut self, pass: P) -> &mut Self {
+    /// Binds a **Post-Core Compilation Pass** that will run after the **Core Pass** (in the order of insertion)
+    pub fn bind_post_core_pass<P: RticPostPass + 'static>(&mut self, pass: P) -> &mut Self {
         self.post_std_passes.push(Box::new(pass));
         self
     }
@@ -92,66 +172,138 @@ impl RticMacroBuilder {
     /// Once the **CorePass** low level hardware bindings are provided, and a selection of
     /// **Compilation Passes** are bound too, use this method to run the **app** proc macro logic.
     ///
-    /// Returns a TokenStream of the expanded user application.
+    /// Returns a TokenStream of the expanded user application, followed by the warnings reported by the passes. If any
+    /// pass reported errors, only the collected diagnostics are returned.
     pub fn build_rtic_macro(self, args: TokenStream, input: TokenStream) -> TokenStream {
-        // init statics
-        DEFAULT_TASK_PRIORITY.store(self.core.default_task_priority(), Ordering::Relaxed);
-
-        let mut args = TokenStream2::from(args);
-        let mut app_mod = parse_macro_input!(input as ItemMod);
-
-        // First, run extra passes (in the order of their insertion)
-        for pass in self.pre_std_passes {
-            let (out_args, out_mod) = match pass.run_pass(args, app_mod) {
-                Ok(out) => out,
-                Err(e) => {
-                    eprintln!(
-                        "An error occurred during the `{}` compilation pass",
-                        pass.pass_name()
-                    );
-                    return e.to_compile_error().into();
-                }
-            };
-            app_mod = out_mod;
-            args = out_args;
+        self.expand_app(args.into(), input.into())
+            .to_token_stream()
+            .into()
+    }
+
+    /// Same as [Self::build_rtic_macro], but works with [proc_macro2] token streams and keeps the expanded code
+    /// separate from the diagnostics. This allows running the whole pipeline outside of a proc macro, e.g. in tests.
+    pub fn expand_app(self, args: TokenStream2, input: TokenStream2) -> Expansion {
+        // facts shared by the passes and the backend during this expansion
+        let ctx = PassContext::new();
+        ctx.insert(DefaultTaskPriority(self.core.default_task_priority(&ctx)));
+        ctx.insert(IdleTaskPriority(self.core.idle_task_priority(&ctx)));
+        ctx.insert(self.core.capabilities(&ctx));
+
+        ctx.enter_pass(CORE_PASS_NAME, Phase::Parse);
+        let code = ctx
+            .ok_or_report(syn::parse2::<ItemMod>(input))
+            .and_then(|app_mod| {
+                let snapshots = ExpansionSnapshots::from_env(&app_mod.ident.to_string());
+                let code = self.expand(&ctx, &snapshots, args, app_mod)?;
+                snapshots.record_final(&code);
+                Some(code)
+            });
+
+        Expansion {
+            code,
+            diagnostics: ctx.diagnostics(),
+            model: ctx.remove::<AppModel>(),
         }
+    }
 
-        // parse user application comprised of init, idle, and other tasks and resources
-        let mut parsed_app = match App::parse(args, app_mod) {
-            Ok(parsed) => parsed,
-            Err(e) => {
-                eprintln!("An error occurred during the `core` compilation pass during the user code `parsing` phase.");
-                return e.to_compile_error().into();
+    /// Runs all the passes on the user application. Returns None if any pass reported errors in `ctx`.
+    fn expand(
+        self,
+        ctx: &PassContext,
+        snapshots: &ExpansionSnapshots,
+        mut args: TokenStream2,
+        mut app_mod: ItemMod,
+    ) -> Option<TokenStream2> {
+        snapshots.record_app(INPUT_STAGE, &args, &app_mod);
+        let mut origins = TaskOrigins::default();
+        origins.record(INPUT_STAGE, &app_mod);
+
+        // order the extra passes according to their declared dependencies
+        ctx.enter_pass(CORE_PASS_NAME, Phase::Analysis);
+        let pre_std_passes = ctx.ok_or_report(pass_order::order_passes(self.pre_std_passes))?;
+
+        // reject attribute arguments that no pass in the pipeline understands
+        ctx.set_phase(Phase::Parse);
+        let schema = pre_std_passes
+            .iter()
+            .fold(App::attribute_schema(), |schema, pass| {
+                schema.merge(pass.attribute_schema())
+            });
+        schema.validate(ctx, &args, &app_mod);
+        if ctx.has_errors() {
+            return None;
+        }
+
+        // First, run extra passes
+        let pass_names: Vec<_> = pre_std_passes
+            .iter()
+            .map(|pass| pass.pass_name().to_string())
+            .collect();
+        for pass in pre_std_passes {
+            ctx.enter_pass(pass.pass_name(), Phase::Parse);
+            (args, app_mod) = ctx.ok_or_report(pass.run_pass(ctx, args, app_mod))?;
+            if ctx.has_errors() {
+                return None;
             }
-        };
+            snapshots.record_app(pass.pass_name(), &args, &app_mod);
+            origins.record(pass.pass_name(), &app_mod);
+        }
+
+        // parse user application comprised of init, idle, and other tasks and resources
+        ctx.enter_pass(CORE_PASS_NAME, Phase::Parse);
+        let mut parsed_app = ctx.ok_or_report(App::parse(ctx, args, app_mod))?;
 
         // update resource ceilings and gather more information about the application
-        let analysis = match Analysis::run(&mut parsed_app) {
-            Ok(a) => a,
-            Err(e) => {
-                eprintln!("An error occurred during the `core` compilation pass  during the user code `analysis` phase.");
-                return e.to_compile_error().into();
-            }
-        };
+        ctx.set_phase(Phase::Analysis);
+        let analysis = ctx.ok_or_report(Analysis::run(&mut parsed_app, self.core.as_ref(), ctx))?;
+
+        // reject what the target can not run
+        let capabilities = ctx.get::<BackendCapabilities>().unwrap_or_default();
+        let idle_priority = ctx.get::<IdleTaskPriority>().map_or(0, |p| p.0);
+        ctx.ok_or_report(capabilities.check(&parsed_app, &analysis, &origins, idle_priority))?;
 
         // Before starting code generation, ask distribution for further checks
-        if let Err(e) = self.core.pre_codegen_validation(&parsed_app, &analysis) {
-            return e.to_compile_error().into();
+        let validation = self
+            .core
+            .pre_codegen_validation(ctx, &parsed_app, &analysis);
+        if ctx.ok_or_report(validation).is_none() || ctx.has_errors() {
+            return None;
         }
 
-        let code = CodeGen::new(self.core.as_ref(), &parsed_app, &analysis).run();
+        // describe the analyzed application for external tooling
+        let model = AppModel::new(
+            ctx,
+            self.core.as_ref(),
+            &parsed_app,
+            &analysis,
+            pass_names,
+            &origins,
+        );
+        model::export_from_env(ctx, &model);
+        ctx.insert(model);
 
-        #[cfg(feature = "debug_expand")]
-        if let Ok(binary_name) = std::env::var("CARGO_BIN_NAME") {
-            if let Ok(out) = project_root::get_project_root() {
-                let _ = std::fs::create_dir_all(out.join("examples"));
-                let _ = std::fs::write(
-                    out.join(format!("examples/{binary_name}_expanded.rs")),
-                    code.to_string().as_bytes(),
-                );
+        ctx.set_phase(Phase::Codegen);
+        let mut code = CodeGen::new(self.core.as_ref(), ctx, &parsed_app, &analysis).run();
+        if ctx.has_errors() {
+            return None;
+        }
+        snapshots.record(CORE_PASS_NAME, || code.clone());
+
+        // Finally, run the post-core passes on the expanded application
+        if !self.post_std_passes.is_empty() {
+            let mut expanded_mod = ctx.ok_or_report(syn::parse2::<ItemMod>(code))?;
+            for pass in self.post_std_passes {
+                ctx.enter_pass(pass.pass_name(), Phase::Codegen);
+                let result = pass.run_post_pass(ctx, &parsed_app, &analysis, expanded_mod);
+                expanded_mod = ctx.ok_or_report(result)?;
+                if ctx.has_errors() {
+                    return None;
+                }
+                snapshots.record(pass.pass_name(), || expanded_mod.to_token_stream());
             }
+            code = expanded_mod.to_token_stream();
         }
 
-        code.into()
+        Some(code)
     }
 }
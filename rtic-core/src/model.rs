@@ -0,0 +1,523 @@
+//! Machine-readable description of the expanded application: per-core tasks, priorities, bound interrupts, resource
+//! ceilings and dispatchers, together with the pass that introduced each task.
+//!
+//! The model of the application is built once the **Core Pass** analysis succeeded, and is available in
+//! [crate::Expansion::model]. Setting the `RTIC_MODEL_EXPORT` environment variable additionally writes it as a JSON
+//! document during the macro expansion:
+//! - `RTIC_MODEL_EXPORT=out_dir` (or an empty value) writes to the `OUT_DIR` of the crate, which requires the crate
+//!   to have a build script
+//! - `RTIC_MODEL_EXPORT=path/to/model.json` writes to the given file
+//! - `RTIC_MODEL_EXPORT=path/to/dir` writes to `path/to/dir/{crate}.rtic-model.json`
+//!
+//! Relative paths are relative to the manifest directory of the crate being compiled. Cargo does not track the
+//! variable, so changing it only takes effect once the application is expanded again (e.g. after `cargo clean -p`).
+//!
+//! The document has the following shape:
+//!
+//! ```json
+//! {
+//!   "app": "app",
+//!   "passes": ["AutoAssign", "SoftwareTasks"],
+//!   "cores": [
+//!     {
+//!       "core": 0,
+//!       "init": "init",
+//!       "tasks": [
+//!         { "name": "Uart0Task", "kind": "hardware", "binds": "UART0", "exception": false, "priority": 3,
+//!           "task_trait": "RticTask", "shared": ["counter"], "introduced_by": "input" }
+//!       ],
+//!       "resources": [
+//!         { "name": "counter", "struct": "Shared", "type": "u32", "ceiling": 3, "reader_ceiling": 3,
+//!           "lock_free": true, "users": [{ "task": "Uart0Task", "access": "write" }] }
+//!       ],
+//!       "interrupts": [{ "name": "UART0", "priority": 3, "hardware_priority": 3 }],
+//!       "exceptions": [],
+//!       "dispatchers": [{ "interrupt": "UART1", "priority": 2, "tasks": ["Worker"] }]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `introduced_by` is `input` for the tasks written by the user, otherwise the [crate::RticPass::pass_name] of the
+//! pass whose output first contained the task. Dispatchers are only listed when a pass published them, see
+//! [Dispatchers]. Priorities are logical (see [crate::capabilities]), the `hardware_priority` of the interrupts and
+//! exceptions is the level they are configured with, as given by [crate::CorePassBackend::hardware_priority].
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{Item, ItemMod};
+
+use crate::parser::SubApp;
+use crate::parser::ast::RticTask;
+use crate::{Analysis, App, CorePassBackend, PassContext, SubAnalysis};
+
+/// Environment variable used to enable the export of the model, see the [module level documentation](self)
+pub const MODEL_ENV_VAR: &str = "RTIC_MODEL_EXPORT";
+
+/// Name of the stage holding the application as written by the user
+pub const INPUT_STAGE: &str = "input";
+
+/// Fact published by the passes that run tasks from interrupt handlers they generate (e.g. the software pass), to
+/// have the dispatchers in the model of the application.
+#[derive(Debug, Clone, Default)]
+pub struct Dispatchers(pub Vec<Dispatcher>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dispatcher {
+    pub core: u32,
+    /// The interrupt the dispatcher is bound to
+    pub interrupt: String,
+    pub priority: u16,
+    /// Names of the task structs run by the dispatcher
+    pub tasks: Vec<String>,
+}
+
+/// Remembers which stage of the pipeline introduced each task
+#[derive(Debug, Default)]
+pub(crate) struct TaskOrigins(IndexMap<String, String>);
+
+impl TaskOrigins {
+    /// Attributes the tasks of `app_mod` that are not known yet to `stage`
+    pub fn record(&mut self, stage: &str, app_mod: &ItemMod) {
+        let items = app_mod.content.iter().flat_map(|(_, items)| items.iter());
+        for item in items {
+            let Item::Struct(strct) = item else {
+                continue;
+            };
+            let is_task = strct.attrs.iter().any(|attr| {
+                ["task", "sw_task", "idle"]
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            });
+            if is_task {
+                self.0
+                    .entry(strct.ident.to_string())
+                    .or_insert_with(|| stage.to_string());
+            }
+        }
+    }
+
+    /// Stage the task named `task` was introduced by, the input if unknown
+    pub fn of(&self, task: &str) -> &str {
+        self.0.get(task).map_or(INPUT_STAGE, String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppModel {
+    pub app: String,
+    /// [crate::RticPass::pass_name] of the pre-core passes, in execution order
+    pub passes: Vec<String>,
+    pub cores: Vec<CoreModel>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreModel {
+    pub core: u32,
+    /// Name of the `#[init]` function
+    pub init: String,
+    /// Hardware tasks, software tasks and idle, in declaration order
+    pub tasks: Vec<TaskModel>,
+    pub resources: Vec<ResourceModel>,
+    /// Interrupts bound to tasks
+    pub interrupts: Vec<BindingModel>,
+    /// Exceptions bound to tasks
+    pub exceptions: Vec<BindingModel>,
+    pub dispatchers: Vec<Dispatcher>,
+}
+
+/// An interrupt or an exception bound to a task
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingModel {
+    pub name: String,
+    pub priority: u16,
+    /// Level the interrupt or exception is configured with, see [crate::CorePassBackend::hardware_priority]
+    pub hardware_priority: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskKind {
+    /// Bound to an interrupt or an exception
+    Hardware,
+    /// Not bound, run by a dispatcher generated by some pass
+    Software,
+    Idle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskModel {
+    pub name: String,
+    pub kind: TaskKind,
+    pub binds: Option<String>,
+    /// Whether [Self::binds] is an exception
+    pub exception: bool,
+    pub priority: u16,
+    pub task_trait: String,
+    pub shared: Vec<String>,
+    /// `input`, or the name of the pass that introduced the task
+    pub introduced_by: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceModel {
+    pub name: String,
+    /// The `#[shared]` struct declaring the resource
+    pub shared_struct: String,
+    pub ty: String,
+    /// Ceiling used by `lock`
+    pub ceiling: u16,
+    /// Ceiling used by `lock_ref`
+    pub reader_ceiling: u16,
+    /// Whether the resource is accessed without locking, as all its users run at the same priority
+    pub lock_free: bool,
+    /// Tasks accessing the resource, and whether they only read it
+    pub users: Vec<(String, bool)>,
+}
+
+impl AppModel {
+    pub(crate) fn new(
+        ctx: &PassContext,
+        backend: &dyn CorePassBackend,
+        app: &App,
+        analysis: &Analysis,
+        passes: Vec<String>,
+        origins: &TaskOrigins,
+    ) -> Self {
+        let dispatchers = ctx.get::<Dispatchers>().unwrap_or_default();
+        let cores = app
+            .sub_apps
+            .iter()
+            .zip(analysis.sub_analysis.iter())
+            .map(|(sub_app, sub_analysis)| {
+                CoreModel::new(ctx, backend, sub_app, sub_analysis, &dispatchers, origins)
+            })
+            .collect();
+        Self {
+            app: app.app_name.to_string(),
+            passes,
+            cores,
+        }
+    }
+
+    /// Renders the model as a pretty-printed JSON document
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.json().write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            ("app", Json::from(&self.app)),
+            ("passes", Json::array(self.passes.iter().map(Json::from))),
+            ("cores", Json::array(self.cores.iter().map(CoreModel::json))),
+        ])
+    }
+}
+
+impl CoreModel {
+    fn new(
+        ctx: &PassContext,
+        backend: &dyn CorePassBackend,
+        app: &SubApp,
+        analysis: &SubAnalysis,
+        dispatchers: &Dispatchers,
+        origins: &TaskOrigins,
+    ) -> Self {
+        let tasks: Vec<&RticTask> = app.tasks.iter().chain(app.idle.iter()).collect();
+        let task_models = tasks
+            .iter()
+            .map(|task| {
+                let name = task.name().to_string();
+                let is_idle = app
+                    .idle
+                    .as_ref()
+                    .is_some_and(|idle| idle.name() == task.name());
+                let kind = match &task.args.binds {
+                    _ if is_idle => TaskKind::Idle,
+                    Some(_) => TaskKind::Hardware,
+                    None => TaskKind::Software,
+                };
+                TaskModel {
+                    kind,
+                    binds: task.args.binds.as_ref().map(ToString::to_string),
+                    exception: task
+                        .args
+                        .binds
+                        .as_ref()
+                        .is_some_and(|b| analysis.is_exception(b)),
+                    priority: task.args.priority,
+                    task_trait: task.args.task_trait.to_string(),
+                    shared: task.args.shared.iter().map(ToString::to_string).collect(),
+                    introduced_by: origins.of(&name).to_string(),
+                    name,
+                }
+            })
+            .collect();
+
+        let resources = app
+            .shared
+            .iter()
+            .flat_map(|strct| strct.resources.iter().map(move |r| (strct, r)))
+            .map(|(strct, resource)| ResourceModel {
+                name: resource.ident.to_string(),
+                shared_struct: strct.strct.ident.to_string(),
+                ty: resource.ty.to_token_stream().to_string(),
+                ceiling: resource.priority,
+                reader_ceiling: resource.reader_ceiling,
+                lock_free: resource.lock_free,
+                users: tasks
+                    .iter()
+                    .filter(|task| task.args.shared.contains(&resource.ident))
+                    .map(|task| (task.name().to_string(), task.reads_only(&resource.ident)))
+                    .collect(),
+            })
+            .collect();
+
+        let bindings = |irqs: &[(syn::Ident, u16)]| -> Vec<BindingModel> {
+            irqs.iter()
+                .map(|(irq, priority)| BindingModel {
+                    name: irq.to_string(),
+                    priority: *priority,
+                    hardware_priority: backend.hardware_priority(ctx, app.core, *priority),
+                })
+                .collect()
+        };
+        Self {
+            core: app.core,
+            init: app.init.ident.to_string(),
+            tasks: task_models,
+            resources,
+            interrupts: bindings(&analysis.used_irqs),
+            exceptions: bindings(&analysis.used_exceptions),
+            dispatchers: dispatchers
+                .0
+                .iter()
+                .filter(|dispatcher| dispatcher.core == app.core)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn json(&self) -> Json {
+        let bindings =
+            |bindings: &[BindingModel]| Json::array(bindings.iter().map(BindingModel::json));
+        Json::Object(vec![
+            ("core", Json::Int(self.core as i64)),
+            ("init", Json::from(&self.init)),
+            ("tasks", Json::array(self.tasks.iter().map(TaskModel::json))),
+            (
+                "resources",
+                Json::array(self.resources.iter().map(ResourceModel::json)),
+            ),
+            ("interrupts", bindings(&self.interrupts)),
+            ("exceptions", bindings(&self.exceptions)),
+            (
+                "dispatchers",
+                Json::array(self.dispatchers.iter().map(Dispatcher::json)),
+            ),
+        ])
+    }
+}
+
+impl BindingModel {
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            ("name", Json::from(&self.name)),
+            ("priority", Json::Int(self.priority as i64)),
+            (
+                "hardware_priority",
+                Json::Int(self.hardware_priority as i64),
+            ),
+        ])
+    }
+}
+
+impl TaskModel {
+    fn json(&self) -> Json {
+        let kind = match self.kind {
+            TaskKind::Hardware => "hardware",
+            TaskKind::Software => "software",
+            TaskKind::Idle => "idle",
+        };
+        Json::Object(vec![
+            ("name", Json::from(&self.name)),
+            ("kind", Json::from(kind)),
+            ("binds", self.binds.as_ref().map_or(Json::Null, Json::from)),
+            ("exception", Json::Bool(self.exception)),
+            ("priority", Json::Int(self.priority as i64)),
+            ("task_trait", Json::from(&self.task_trait)),
+            ("shared", Json::array(self.shared.iter().map(Json::from))),
+            ("introduced_by", Json::from(&self.introduced_by)),
+        ])
+    }
+}
+
+impl ResourceModel {
+    fn json(&self) -> Json {
+        let users = self.users.iter().map(|(task, reads_only)| {
+            let access = if *reads_only { "read" } else { "write" };
+            Json::Object(vec![
+                ("task", Json::from(task)),
+                ("access", Json::from(access)),
+            ])
+        });
+        Json::Object(vec![
+            ("name", Json::from(&self.name)),
+            ("struct", Json::from(&self.shared_struct)),
+            ("type", Json::from(&self.ty)),
+            ("ceiling", Json::Int(self.ceiling as i64)),
+            ("reader_ceiling", Json::Int(self.reader_ceiling as i64)),
+            ("lock_free", Json::Bool(self.lock_free)),
+            ("users", Json::array(users)),
+        ])
+    }
+}
+
+impl Dispatcher {
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            ("interrupt", Json::from(&self.interrupt)),
+            ("priority", Json::Int(self.priority as i64)),
+            ("tasks", Json::array(self.tasks.iter().map(Json::from))),
+        ])
+    }
+}
+
+/// Writes `model` to the location selected by [MODEL_ENV_VAR], if any. Failures are reported as warnings, they never
+/// fail the expansion.
+pub(crate) fn export_from_env(ctx: &PassContext, model: &AppModel) {
+    let Ok(target) = std::env::var(MODEL_ENV_VAR) else {
+        return;
+    };
+    let file_name = format!(
+        "{}.rtic-model.json",
+        std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| model.app.clone())
+    );
+    let path = match target.trim() {
+        "" | "out_dir" => match std::env::var("OUT_DIR") {
+            Ok(out_dir) => PathBuf::from(out_dir).join(file_name),
+            Err(_) => {
+                ctx.warning(
+                    Span::call_site(),
+                    format!(
+                        "{MODEL_ENV_VAR} selects OUT_DIR, but OUT_DIR is not set (the crate has no build script). \
+                        The application model was not exported."
+                    ),
+                );
+                return;
+            }
+        },
+        target => {
+            let mut path = PathBuf::from(target);
+            if path.is_relative()
+                && let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR")
+            {
+                path = PathBuf::from(manifest_dir).join(path);
+            }
+            match path.extension() {
+                Some(extension) if extension == "json" => path,
+                _ => path.join(file_name),
+            }
+        }
+    };
+
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, model.to_json()));
+    if let Err(e) = written {
+        ctx.warning(
+            Span::call_site(),
+            format!(
+                "The application model could not be written to {}: {e}",
+                path.display()
+            ),
+        );
+    }
+}
+
+/// Just enough JSON to render the model, the key order is kept
+enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<&String> for Json {
+    fn from(value: &String) -> Self {
+        Self::Str(value.clone())
+    }
+}
+
+impl Json {
+    fn array<T: Into<Json>>(elements: impl IntoIterator<Item = T>) -> Self {
+        Self::Array(elements.into_iter().map(Into::into).collect())
+    }
+
+    fn write(&self, out: &mut String, indent: usize) {
+        let padding = |level: usize| "  ".repeat(level);
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Int(value) => {
+                let _ = write!(out, "{value}");
+            }
+            Json::Str(value) => write_string(out, value),
+            Json::Array(elements) if elements.is_empty() => out.push_str("[]"),
+            Json::Array(elements) => {
+                out.push_str("[\n");
+                for (i, element) in elements.iter().enumerate() {
+                    out.push_str(&padding(indent + 1));
+                    element.write(out, indent + 1);
+                    out.push_str(if i + 1 < elements.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&padding(indent));
+                out.push(']');
+            }
+            Json::Object(fields) if fields.is_empty() => out.push_str("{}"),
+            Json::Object(fields) => {
+                out.push_str("{\n");
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    out.push_str(&padding(indent + 1));
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                    out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&padding(indent));
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,298 @@
+//! An order-preserving model of the `#[app]` module that **Compilation Passes** can parse, modify and re-emit.
+//!
+//! [AppModule::parse] classifies every item of the module by its RTIC attribute (`#[init]`, `#[idle]`, `#[task]`,
+//! `#[sw_task]`, `#[shared]`), or as the implementation of one of the task traits, and leaves everything else
+//! untouched. [AppModule::into_item_mod] puts the module back together in the original order, with the (possibly
+//! modified) RTIC attributes at their original position among the attributes of their item.
+//!
+//! ```rust,ignore
+//! let mut module = AppModule::parse(app_mod)?;
+//! for task in module.tasks_mut() {
+//!     task.attr.elements.insert("core".into(), parse_quote!(0));
+//! }
+//! let app_mod = module.into_item_mod();
+//! ```
+
+use proc_macro2::Ident;
+use quote::format_ident;
+use syn::{
+    Attribute, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, Type, Visibility, spanned::Spanned,
+};
+
+use crate::rtic_traits::{HWT_TRAIT_TY, IDLE_TRAIT_TY, SWT_TRAIT_TY};
+
+use super::RticAttr;
+
+/// A parsed `#[app]` module
+#[derive(Debug, Clone)]
+pub struct AppModule {
+    /// Attributes of the module itself
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    /// Items of the module in their original order
+    pub items: Vec<AppItem>,
+}
+
+/// An item of the `#[app]` module, classified by its RTIC attribute
+#[derive(Debug, Clone)]
+pub enum AppItem {
+    /// `#[init] fn ..`
+    Init(RticItem<ItemFn>),
+    /// `#[idle] struct ..`
+    Idle(RticItem<ItemStruct>),
+    /// `#[task] struct ..`
+    Task(RticItem<ItemStruct>),
+    /// `#[sw_task] struct ..`
+    SwTask(RticItem<ItemStruct>),
+    /// `#[shared] struct ..`
+    Shared(RticItem<ItemStruct>),
+    /// `impl RticTask for ..`, `impl RticSwTask for ..` or `impl RticIdleTask for ..`
+    TaskImpl(TaskImpl),
+    /// Any other item, left untouched
+    Other(Item),
+}
+
+/// An item carrying an RTIC attribute
+#[derive(Debug, Clone)]
+pub struct RticItem<T> {
+    /// The parsed RTIC attribute, re-emitted in place of the original one
+    pub attr: RticAttr,
+    /// The item, without its RTIC attribute
+    pub item: T,
+    /// Position of the RTIC attribute among the attributes of the item
+    attr_idx: usize,
+}
+
+/// Implementation of a task trait for a task struct
+#[derive(Debug, Clone)]
+pub struct TaskImpl {
+    /// Name of the struct implementing the task trait
+    pub implementor: Ident,
+    pub item: ItemImpl,
+}
+
+/// Items that can carry an RTIC attribute
+pub trait AttributedItem {
+    fn attrs_mut(&mut self) -> &mut Vec<Attribute>;
+    fn ident(&self) -> &Ident;
+}
+
+impl AttributedItem for ItemStruct {
+    fn attrs_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attrs
+    }
+
+    fn ident(&self) -> &Ident {
+        &self.ident
+    }
+}
+
+impl AttributedItem for ItemFn {
+    fn attrs_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attrs
+    }
+
+    fn ident(&self) -> &Ident {
+        &self.sig.ident
+    }
+}
+
+impl<T: AttributedItem> RticItem<T> {
+    /// Creates an item that will be emitted with `attr` as its first attribute
+    pub fn new(attr: RticAttr, item: T) -> Self {
+        Self {
+            attr,
+            item,
+            attr_idx: 0,
+        }
+    }
+
+    /// Name of the struct or function
+    pub fn name(&self) -> &Ident {
+        self.item.ident()
+    }
+
+    /// Returns the item with its RTIC attribute re-inserted at its original position
+    pub fn into_item(self) -> T {
+        let mut item = self.item;
+        let attrs = item.attrs_mut();
+        attrs.insert(self.attr_idx.min(attrs.len()), self.attr.to_attribute());
+        item
+    }
+
+    /// Takes the attribute with one of the `names` out of `item`
+    fn extract(mut item: T, names: &[&str]) -> Result<Self, T> {
+        let Some(attr_idx) = item
+            .attrs_mut()
+            .iter()
+            .position(|attr| names.iter().any(|name| attr.path().is_ident(name)))
+        else {
+            return Err(item);
+        };
+        let attribute = item.attrs_mut().remove(attr_idx);
+        match RticAttr::parse_from_attr(&attribute) {
+            Ok(attr) => Ok(Self {
+                attr,
+                item,
+                attr_idx,
+            }),
+            Err(_) => {
+                // leave malformed attributes to the pass that owns them
+                item.attrs_mut().insert(attr_idx, attribute);
+                Err(item)
+            }
+        }
+    }
+}
+
+impl AppItem {
+    /// Puts the item back together
+    pub fn into_item(self) -> Item {
+        match self {
+            AppItem::Init(init) => Item::Fn(init.into_item()),
+            AppItem::Idle(s) | AppItem::Task(s) | AppItem::SwTask(s) | AppItem::Shared(s) => {
+                Item::Struct(s.into_item())
+            }
+            AppItem::TaskImpl(task_impl) => Item::Impl(task_impl.item),
+            AppItem::Other(item) => item,
+        }
+    }
+
+    fn classify(item: Item) -> Self {
+        match item {
+            Item::Fn(function) => match RticItem::extract(function, &["init"]) {
+                Ok(init) => AppItem::Init(init),
+                Err(function) => AppItem::Other(Item::Fn(function)),
+            },
+            Item::Struct(strct) => {
+                let Some(name) = strct
+                    .attrs
+                    .iter()
+                    .filter_map(|attr| attr.path().get_ident())
+                    .map(|ident| ident.to_string())
+                    .find(|name| ["idle", "task", "sw_task", "shared"].contains(&name.as_str()))
+                else {
+                    return AppItem::Other(Item::Struct(strct));
+                };
+                match RticItem::extract(strct, &[&name]) {
+                    Ok(s) => match name.as_str() {
+                        "idle" => AppItem::Idle(s),
+                        "task" => AppItem::Task(s),
+                        "sw_task" => AppItem::SwTask(s),
+                        _ => AppItem::Shared(s),
+                    },
+                    Err(strct) => AppItem::Other(Item::Struct(strct)),
+                }
+            }
+            Item::Impl(item) => match task_implementor(&item) {
+                Some(implementor) => AppItem::TaskImpl(TaskImpl { implementor, item }),
+                None => AppItem::Other(Item::Impl(item)),
+            },
+            other => AppItem::Other(other),
+        }
+    }
+}
+
+/// Returns the name of the struct `item` implements a task trait for
+fn task_implementor(item: &ItemImpl) -> Option<Ident> {
+    let (_, trait_path, _) = item.trait_.as_ref()?;
+    let trait_name = trait_path.segments.last()?.ident.to_string();
+    let is_task_trait = [HWT_TRAIT_TY, SWT_TRAIT_TY, IDLE_TRAIT_TY]
+        .iter()
+        .any(|task_trait| trait_name.ends_with(task_trait));
+    match item.self_ty.as_ref() {
+        Type::Path(struct_type) if is_task_trait => {
+            Some(format_ident!("{}", struct_type.path.segments.last()?.ident))
+        }
+        _ => None,
+    }
+}
+
+macro_rules! rtic_items {
+    ($(#[$doc:meta])* $variant:ident, $ty:ty, $iter:ident, $iter_mut:ident) => {
+        $(#[$doc])*
+        pub fn $iter(&self) -> impl Iterator<Item = &RticItem<$ty>> {
+            self.items.iter().filter_map(|item| match item {
+                AppItem::$variant(i) => Some(i),
+                _ => None,
+            })
+        }
+
+        $(#[$doc])*
+        pub fn $iter_mut(&mut self) -> impl Iterator<Item = &mut RticItem<$ty>> {
+            self.items.iter_mut().filter_map(|item| match item {
+                AppItem::$variant(i) => Some(i),
+                _ => None,
+            })
+        }
+    };
+}
+
+impl AppModule {
+    /// Classifies the items of `app_mod`
+    pub fn parse(app_mod: ItemMod) -> syn::Result<Self> {
+        let span = app_mod.span();
+        let (_, items) = app_mod
+            .content
+            .ok_or_else(|| syn::Error::new(span, "Empty app module."))?;
+        Ok(Self {
+            attrs: app_mod.attrs,
+            vis: app_mod.vis,
+            ident: app_mod.ident,
+            items: items.into_iter().map(AppItem::classify).collect(),
+        })
+    }
+
+    /// Puts the module back together, keeping the order of the items
+    pub fn into_item_mod(self) -> ItemMod {
+        let attrs = self.attrs;
+        let vis = self.vis;
+        let ident = self.ident;
+        let items = self.items.into_iter().map(AppItem::into_item);
+        syn::parse_quote! {
+            #(#attrs)*
+            #vis mod #ident {
+                #(#items)*
+            }
+        }
+    }
+
+    rtic_items!(
+        /// `#[init]` functions
+        Init, ItemFn, inits, inits_mut
+    );
+    rtic_items!(
+        /// `#[idle]` structs
+        Idle, ItemStruct, idles, idles_mut
+    );
+    rtic_items!(
+        /// `#[task]` structs
+        Task, ItemStruct, tasks, tasks_mut
+    );
+    rtic_items!(
+        /// `#[sw_task]` structs
+        SwTask, ItemStruct, sw_tasks, sw_tasks_mut
+    );
+    rtic_items!(
+        /// `#[shared]` structs
+        Shared, ItemStruct, shared, shared_mut
+    );
+
+    /// The implementation of a task trait for the struct named `task`
+    pub fn task_impl(&self, task: &Ident) -> Option<&ItemImpl> {
+        self.items.iter().find_map(|item| match item {
+            AppItem::TaskImpl(task_impl) if &task_impl.implementor == task => Some(&task_impl.item),
+            _ => None,
+        })
+    }
+
+    /// Removes and returns all the items matching `predicate`, keeping the order of the remaining items
+    pub fn extract_items(&mut self, mut predicate: impl FnMut(&AppItem) -> bool) -> Vec<AppItem> {
+        let (extracted, kept) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|item| predicate(item));
+        self.items = kept;
+        extracted
+    }
+}
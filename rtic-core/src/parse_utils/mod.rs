@@ -1,15 +1,21 @@
 //! Provides a utility to streamline parsing and manipulating and reconstructing the tokenstream representation of the #[app(arg1="val1", ...)] attribute
+//! and of the `#[app]` module itself (see [AppModule])
 
+mod app_module;
+
+pub use app_module::{AppItem, AppModule, AttributedItem, RticItem, TaskImpl};
+
+use indexmap::IndexMap;
 use proc_macro2::{Punct, Spacing, TokenStream as TokenStream2};
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
-use std::collections::HashMap;
-use syn::{parse::Parser, parse_quote, Attribute, Meta};
+use quote::{ToTokens, TokenStreamExt, format_ident, quote};
+use syn::{Attribute, Meta, parse::Parser, parse_quote};
 
 #[derive(Debug, Clone)]
 pub struct RticAttr {
     /// E.g., `task`
     pub name: Option<syn::Ident>,
-    pub elements: HashMap<String, syn::Expr>,
+    /// Arguments in the order they were written, so that the reconstructed attribute keeps the user order
+    pub elements: IndexMap<String, syn::Expr>,
 }
 
 impl RticAttr {
@@ -18,19 +24,19 @@ impl RticAttr {
         match attribute.meta {
             Meta::Path(ref path) => {
                 let name = if path.segments.len() == 1 {
-                    Some(format_ident!("{}", path.segments[0].ident))
+                    Some(path.segments[0].ident.clone())
                 } else {
                     None
                 };
 
                 Ok(Self {
                     name,
-                    elements: HashMap::new(),
+                    elements: IndexMap::new(),
                 })
             }
             Meta::List(ref list) => {
                 let name = if list.path.segments.len() == 1 {
-                    Some(format_ident!("{}", list.path.segments[0].ident))
+                    Some(list.path.segments[0].ident.clone())
                 } else {
                     None
                 };
@@ -39,13 +45,16 @@ impl RticAttr {
                 parsed.name = name;
                 Ok(parsed)
             }
-            Meta::NameValue(_) => unreachable!(),
+            Meta::NameValue(ref name_value) => Err(syn::Error::new(
+                name_value.eq_token.span,
+                "expected an attribute of the form `#[name]` or `#[name(arg1 = val1, ...)]`",
+            )),
         }
     }
 
     /// Parse the tokenstream representation of the arguments of an #[app(arg1="val1", ...)] macro attribute
     pub fn parse_from_tokens(tokens: TokenStream2) -> syn::Result<Self> {
-        let mut elements = HashMap::new();
+        let mut elements = IndexMap::new();
         syn::meta::parser(|meta| {
             let value: syn::Expr = meta
                 .value()
@@ -66,17 +75,52 @@ impl RticAttr {
     }
 }
 
-impl ToTokens for RticAttr {
-    /// Reconstruct the tokenstream representation of #[app(arg1="val1", ...)] macro attribute from the internal state of [Self]
-    fn to_tokens(&self, tokens: &mut TokenStream2) {
+impl RticAttr {
+    /// Reconstruct the #[app(arg1="val1", ...)] macro attribute from the internal state of [Self]. Arguments without
+    /// a value (e.g. `fast` in `#[task(binds = UART0, fast)]`) and attributes without arguments are kept as such.
+    pub fn to_attribute(&self) -> Attribute {
         let args = self.elements.iter().map(|(name, value)| {
             let name = format_ident!("{name}");
-            quote!(#name = #value)
+            match value {
+                syn::Expr::Tuple(tuple) if tuple.elems.is_empty() => quote!(#name),
+                value => quote!(#name = #value),
+            }
         });
         let mut args_token_stream = TokenStream2::new();
         args_token_stream.append_separated(args, Punct::new(',', Spacing::Alone));
         let attr_name = self.name.as_ref().unwrap();
-        let attribue: Attribute = parse_quote!(#[#attr_name(#args_token_stream)]);
-        tokens.append_all(attribue.to_token_stream())
+        if self.elements.is_empty() {
+            parse_quote!(#[#attr_name])
+        } else {
+            parse_quote!(#[#attr_name(#args_token_stream)])
+        }
+    }
+}
+
+impl ToTokens for RticAttr {
+    /// Reconstruct the tokenstream representation of #[app(arg1="val1", ...)] macro attribute from the internal state of [Self]
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.to_attribute().to_token_stream())
+    }
+}
+
+/// Parses an element of a `shared = [...]` list: `resource`, or `&resource` for a read-only access.
+///
+/// Returns the name of the resource and whether it is only read.
+pub fn parse_shared_resource(element: &syn::Expr) -> syn::Result<(syn::Ident, bool)> {
+    let (path, read_only) = match element {
+        syn::Expr::Reference(reference) if reference.mutability.is_none() => {
+            (reference.expr.as_ref(), true)
+        }
+        element => (element, false),
+    };
+    match path {
+        syn::Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+            Ok((path.path.get_ident().unwrap().clone(), read_only))
+        }
+        _ => Err(syn::Error::new_spanned(
+            element,
+            "expected the name of a shared resource, or `&name` for a read-only access",
+        )),
     }
 }
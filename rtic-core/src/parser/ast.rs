@@ -1,15 +1,15 @@
-use std::sync::atomic::Ordering;
-
 use heck::ToSnakeCase;
 use proc_macro2::Span;
-use quote::{format_ident, ToTokens};
+use quote::{ToTokens, format_ident};
 use syn::{
-    parse::Parser, parse_quote, spanned::Spanned, Expr, ExprArray, ExprLit, Ident, ItemFn,
-    ItemImpl, ItemStruct, Lit, LitInt, Meta,
+    Expr, ExprArray, ExprLit, Ident, ItemFn, ItemImpl, ItemStruct, Lit, LitInt, Meta,
+    parse::Parser, parse_quote, spanned::Spanned,
 };
 
 use crate::{
-    errors::ParseError, parse_utils::RticAttr, rtic_traits::HWT_TRAIT_TY, DEFAULT_TASK_PRIORITY,
+    errors::ParseError,
+    parse_utils::{RticAttr, parse_shared_resource},
+    rtic_traits::HWT_TRAIT_TY,
 };
 
 #[derive(Debug)]
@@ -55,20 +55,27 @@ pub struct TaskArgs {
     /// Interrupt handler name
     pub binds: Option<syn::Ident>,
     pub priority: u16,
+    /// Span of the `priority` argument, None when the default priority is used
+    pub priority_span: Option<Span>,
     /// Shared resources, stored as a list of [identifiers](`proc_macro2::Ident`)
     pub shared: Vec<Ident>,
+    /// The resources of [Self::shared] that are only read, declared as `&resource`
+    pub shared_ref: Vec<Ident>,
     pub core: u32,
     // tells whether a task is native to this compilation pass or if another compilation pass handles its trait implementation
     pub task_trait: Ident,
 }
 
 impl TaskArgs {
-    pub fn parse(args: Meta) -> syn::Result<Self> {
+    /// Parses the task attribute arguments. `default_priority` is used when no `priority` argument is provided.
+    pub fn parse(args: Meta, default_priority: u16) -> syn::Result<Self> {
         let Meta::List(args) = args else {
             return Ok(TaskArgs {
                 binds: None,
-                priority: DEFAULT_TASK_PRIORITY.load(Ordering::Relaxed),
+                priority: default_priority,
+                priority_span: None,
                 shared: Default::default(),
+                shared_ref: Default::default(),
                 core: 0,
                 task_trait: format_ident!("{HWT_TRAIT_TY}"),
             });
@@ -77,7 +84,7 @@ impl TaskArgs {
         let mut binds: Option<syn::Path> = None;
         let mut task_trait: Option<Ident> = None;
         let mut priority: Option<LitInt> = None;
-        let mut shared: Option<ExprArray> = None;
+        let mut shared_elems: Option<ExprArray> = None;
         let mut core: Option<LitInt> = None;
 
         syn::meta::parser(|meta| {
@@ -86,7 +93,7 @@ impl TaskArgs {
             } else if meta.path.is_ident("priority") {
                 priority = Some(meta.value()?.parse()?);
             } else if meta.path.is_ident("shared") {
-                shared = Some(meta.value()?.parse()?);
+                shared_elems = Some(meta.value()?.parse()?);
             } else if meta.path.is_ident("core") {
                 core = Some(meta.value()?.parse()?);
             } else if meta.path.is_ident("task_trait") {
@@ -109,30 +116,40 @@ impl TaskArgs {
             );
         })?;
 
-        let binds = binds.map(|i| Ident::new(&i.to_token_stream().to_string(), Span::call_site()));
+        let binds = binds.map(|i| Ident::new(&i.to_token_stream().to_string(), i.span()));
 
+        let priority_span = priority.as_ref().map(|p| p.span());
         let priority = priority
             .and_then(|p| p.base10_parse().ok())
-            .unwrap_or(DEFAULT_TASK_PRIORITY.load(Ordering::Relaxed));
+            .unwrap_or(default_priority);
 
         let core = core
             .and_then(|core| core.base10_parse().ok())
             .unwrap_or_default();
         let task_trait = task_trait.unwrap_or(format_ident!("{HWT_TRAIT_TY}"));
 
-        let shared = shared
-            .map(|expr| {
-                expr.elems
-                    .into_iter()
-                    .map(|elem| Ident::new(&elem.to_token_stream().to_string(), Span::call_site()))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let mut shared = Vec::new();
+        let mut shared_ref = Vec::new();
+        for elem in shared_elems.iter().flat_map(|expr| expr.elems.iter()) {
+            let (ident, read_only) = parse_shared_resource(elem)?;
+            if shared.contains(&ident) {
+                return Err(syn::Error::new_spanned(
+                    elem,
+                    format!("The resource `{ident}` is listed more than once."),
+                ));
+            }
+            if read_only {
+                shared_ref.push(ident.clone());
+            }
+            shared.push(ident);
+        }
 
         Ok(Self {
             binds,
             priority,
+            priority_span,
             shared,
+            shared_ref,
             core,
             task_trait,
         })
@@ -158,6 +175,11 @@ impl RticTask {
         &self.task_struct.ident
     }
 
+    /// Whether the task only reads `resource`
+    pub fn reads_only(&self, resource: &Ident) -> bool {
+        self.args.shared_ref.contains(resource)
+    }
+
     /// By convention, this method is used to generate the name of the static task instance
     pub fn name_uppercase(&self) -> Ident {
         let name = self
@@ -179,7 +201,19 @@ impl RticTask {
 pub struct SharedElement {
     pub ident: Ident,
     pub ty: syn::Type,
+    /// Ceiling used by `lock`: the highest priority among all the tasks accessing the resource
     pub priority: u16,
+    /// Ceiling used by `lock_ref`: the highest priority among the tasks that can modify the resource. Readers at or
+    /// above it never need to lock, and a resource nobody but idle modifies after init (ceiling 0) is read without
+    /// locking.
+    pub reader_ceiling: u16,
+    /// Whether some task accesses the resource as `&resource`
+    pub has_readers: bool,
+    /// Whether all the tasks accessing the resource run at the same priority, so that none of them can preempt another
+    /// one while it accesses the resource. This is also the case of a resource with a single user. Idle is never
+    /// among several users, as an interrupt at the priority of idle still preempts it. The proxies of such a resource
+    /// access it directly, without calling the lock of the backend.
+    pub lock_free: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -254,7 +288,7 @@ impl AppArgs {
         let mut args = RticAttr::parse_from_tokens(args.clone())?;
 
         // parse the number of cores
-        let cores = args.elements.remove("cores");
+        let cores = args.elements.shift_remove("cores");
         let cores = match cores {
             Some(Expr::Lit(ExprLit {
                 lit: Lit::Int(lit_int),
@@ -266,7 +300,7 @@ impl AppArgs {
         // parse the path(s) to PAC(s)
         let device = args
             .elements
-            .remove("device")
+            .shift_remove("device")
             .ok_or(ParseError::DeviceArg.to_syn(args_span))?;
 
         let pacs = match device {
@@ -295,9 +329,17 @@ impl AppArgs {
             _ => return Err(ParseError::DeviceNotPath.to_syn(args_span)),
         };
 
+        let peripherals = match args.elements.shift_remove("peripherals") {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Bool(lit_bool),
+                ..
+            })) => lit_bool.value,
+            _ => false,
+        };
+
         Ok(Self {
             pacs,
-            peripherals: false, // TODO: not supported yet
+            peripherals,
             cores,
         })
     }
@@ -1,24 +1,52 @@
 use std::collections::HashMap;
 
-use proc_macro2::Span;
 use quote::format_ident;
-use syn::{spanned::Spanned, Ident, Item, ItemFn, ItemImpl, ItemStruct, ItemUse, Type};
+use syn::{Fields, Ident, Item, ItemFn, ItemImpl, ItemStruct, ItemUse, Type, spanned::Spanned};
 
 use ast::*;
 
+use crate::attr_schema::{APP_ATTR, ArgKind, AttrSchema};
 use crate::common_internal::rtic_traits::{HWT_TRAIT_TY, IDLE_TRAIT_TY, SWT_TRAIT_TY};
+use crate::context::{DefaultTaskPriority, IdleTaskPriority, PassContext};
+use crate::diagnostics::{collect_results, combine_errors};
 
 pub mod ast;
+mod validate;
 
 #[derive(Debug)]
 pub struct SubApp {
     pub core: u32,
-    pub shared: Option<SharedResources>,
+    /// `#[shared]` structs of this core, in declaration order
+    pub shared: Vec<SharedResources>,
     pub init: InitTask,
     pub idle: Option<IdleTask>,
     pub tasks: Vec<HardwareTask>,
 }
 
+/// The items of one core, gathered before building its [SubApp]
+#[derive(Default)]
+struct SubAppParts {
+    core: u32,
+    shared: Vec<SharedResources>,
+    init: Option<InitTask>,
+    idle: Option<IdleTask>,
+    tasks: Vec<HardwareTask>,
+}
+
+impl SubAppParts {
+    fn into_sub_app(self) -> SubApp {
+        SubApp {
+            core: self.core,
+            shared: self.shared,
+            init: self
+                .init
+                .expect("the validation guarantees one #[init] per core"),
+            idle: self.idle,
+            tasks: self.tasks,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct App {
     pub app_name: Ident,
@@ -29,8 +57,20 @@ pub struct App {
 }
 
 impl App {
-    pub fn parse(args: proc_macro2::TokenStream, module: syn::ItemMod) -> syn::Result<Self> {
+    pub fn parse(
+        ctx: &PassContext,
+        args: proc_macro2::TokenStream,
+        module: syn::ItemMod,
+    ) -> syn::Result<Self> {
         let span = module.span();
+        let default_priority = ctx
+            .get::<DefaultTaskPriority>()
+            .map(|p| p.0)
+            .unwrap_or_default();
+        let idle_priority = ctx
+            .get::<IdleTaskPriority>()
+            .map(|p| p.0)
+            .unwrap_or_default();
         let args = AppArgs::parse(args)?;
         let mut shared_resources = Vec::new();
         let mut inits = Vec::with_capacity(1);
@@ -78,24 +118,50 @@ impl App {
             }
         }
 
-        let mut shared = Self::construct_shared_resources(shared_resources)?;
-        let mut inits = Self::construct_inits(inits, span)?;
-        let mut idles = Self::construct_idle_tasks(idles, &task_impls)?;
-        let mut tasks = Self::construct_rtic_tasks(task_structs, &task_impls)?;
+        let shared = Self::construct_shared_resources(shared_resources);
+        let inits = Self::construct_inits(inits);
+        let idles = Self::construct_idle_tasks(idles, &task_impls, idle_priority);
+        let tasks = Self::construct_rtic_tasks(task_structs, &task_impls, default_priority);
+
+        // report the mistakes found in all the parts of the application at once
+        let (shared, inits, idles, tasks) = match (shared, inits, idles, tasks) {
+            (Ok(shared), Ok(inits), Ok(idles), Ok(tasks)) => (shared, inits, idles, tasks),
+            (shared, inits, idles, tasks) => {
+                let errors = [shared.err(), inits.err(), idles.err(), tasks.err()];
+                return Err(combine_errors(errors.into_iter().flatten())
+                    .expect("at least one part of the application failed to parse"));
+            }
+        };
+
+        validate::validate_app(&args, span, &shared, &inits, &idles, &tasks)?;
 
         // partition into sub_applications
-        let mut sub_apps = Vec::with_capacity(args.cores as usize);
-        for core in 0..args.cores {
-            sub_apps.push(SubApp {
+        let mut sub_apps: Vec<_> = (0..args.cores)
+            .map(|core| SubAppParts {
                 core,
-                shared: shared.remove(&core),
-                init: inits
-                    .remove(&core)
-                    .unwrap_or_else(|| panic!("No init found for core {core}")),
-                idle: idles.remove(&core),
-                tasks: tasks.remove(&core).unwrap_or_default(),
+                ..Default::default()
             })
+            .collect();
+        for shared in shared {
+            let core = shared.args.core as usize;
+            sub_apps[core].shared.push(shared);
         }
+        for init in inits {
+            let core = init.args.core as usize;
+            sub_apps[core].init = Some(init);
+        }
+        for idle in idles {
+            let core = idle.args.core as usize;
+            sub_apps[core].idle = Some(idle);
+        }
+        for task in tasks {
+            let core = task.args.core as usize;
+            sub_apps[core].tasks.push(task);
+        }
+        let sub_apps = sub_apps
+            .into_iter()
+            .map(SubAppParts::into_sub_app)
+            .collect();
 
         Ok(Self {
             app_name: module.ident,
@@ -106,6 +172,27 @@ impl App {
         })
     }
 
+    /// The attribute arguments understood by the **Core Pass**
+    pub fn attribute_schema() -> AttrSchema {
+        AttrSchema::new()
+            .arg(
+                APP_ATTR,
+                "device",
+                ArgKind::OneOf(vec![ArgKind::Path, ArgKind::list(ArgKind::Path)]),
+            )
+            .arg(APP_ATTR, "cores", ArgKind::Int)
+            .arg(APP_ATTR, "peripherals", ArgKind::Bool)
+            .arg("init", "core", ArgKind::Int)
+            .arg("idle", "core", ArgKind::Int)
+            .arg("idle", "shared", ArgKind::shared_resources())
+            .arg("shared", "core", ArgKind::Int)
+            .arg("task", "binds", ArgKind::Ident)
+            .arg("task", "priority", ArgKind::Int)
+            .arg("task", "shared", ArgKind::shared_resources())
+            .arg("task", "core", ArgKind::Int)
+            .arg("task", "task_trait", ArgKind::Ident)
+    }
+
     fn is_init(function: &ItemFn) -> Option<usize> {
         for (i, attr) in function.attrs.iter().enumerate() {
             let path = attr.meta.path();
@@ -139,11 +226,9 @@ impl App {
             let is_idle = path.segments[0].ident.to_string().ends_with(IDLE_TRAIT_TY);
             let is_task = is_hw_task || is_sw_task || is_idle;
 
-            if is_task {
-                if let Type::Path(struct_type) = impl_item.self_ty.as_ref() {
-                    let implementor_name = struct_type.path.segments[0].ident.to_string();
-                    return Some(implementor_name);
-                }
+            if is_task && let Type::Path(struct_type) = impl_item.self_ty.as_ref() {
+                let implementor_name = struct_type.path.segments[0].ident.to_string();
+                return Some(implementor_name);
             }
         }
         None
@@ -151,35 +236,40 @@ impl App {
 
     fn construct_shared_resources(
         shared_resources: Vec<(ItemStruct, usize)>,
-    ) -> syn::Result<HashMap<u32, SharedResources>> {
-        shared_resources
-            .into_iter()
-            .map(|(mut strct, attr_idx)| {
-                // remove the #[shared] attribute
-                let attr = strct.attrs.remove(attr_idx);
-                let args = SharedResourcesArgs::parse(attr.meta)?;
-                let parsed_elements = strct
-                    .fields
-                    .iter()
-                    .map(|f| SharedElement {
-                        ident: f
-                            .ident
-                            .clone()
-                            .expect("unnamed struct is not supported for shared resources"),
-                        ty: f.ty.clone(),
-                        priority: 0,
-                    })
-                    .collect();
-                Ok((
-                    args.core,
-                    SharedResources {
-                        args,
-                        strct,
-                        resources: parsed_elements,
-                    },
-                ))
+    ) -> syn::Result<Vec<SharedResources>> {
+        let shared_resources = shared_resources.into_iter().map(|(mut strct, attr_idx)| {
+            // remove the #[shared] attribute
+            let attr = strct.attrs.remove(attr_idx);
+            let args = SharedResourcesArgs::parse(attr.meta)?;
+            let Fields::Named(fields) = &strct.fields else {
+                return Err(syn::Error::new(
+                    strct.ident.span(),
+                    format!(
+                        "The #[shared] struct `{}` must have named fields, one per shared resource.",
+                        strct.ident
+                    ),
+                ));
+            };
+            let parsed_elements = fields
+                .named
+                .iter()
+                .map(|f| SharedElement {
+                    ident: f.ident.clone().expect("named fields have an identifier"),
+                    ty: f.ty.clone(),
+                    priority: 0,
+                    reader_ceiling: 0,
+                    has_readers: false,
+                    lock_free: false,
+                })
+                .collect();
+            Ok(SharedResources {
+                args,
+                strct,
+                resources: parsed_elements,
             })
-            .collect::<Result<HashMap<_, _>, syn::Error>>()
+        });
+
+        collect_results(shared_resources)
     }
 
     /// links the tasks struct definitions with their implementation part and generates a RticTask struct of it.
@@ -187,17 +277,16 @@ impl App {
     fn construct_rtic_tasks(
         task_structs: Vec<(ItemStruct, usize)>,
         task_impls: &HashMap<String, ItemImpl>,
-    ) -> syn::Result<HashMap<u32, Vec<RticTask>>> {
-        let mut out = HashMap::new();
-        for (mut task_struct, attr_idx) in task_structs {
+        default_priority: u16,
+    ) -> syn::Result<Vec<RticTask>> {
+        let tasks = task_structs.into_iter().map(|(mut task_struct, attr_idx)| {
             // parse the task attribute args
             let attr = task_struct.attrs.remove(attr_idx);
-            let args = TaskArgs::parse(attr.meta)?;
+            let args = TaskArgs::parse(attr.meta, default_priority)?;
 
             // find the task struct impl
             let struct_impl = task_impls.get(&task_struct.ident.to_string());
 
-            let tasks = out.entry(args.core).or_insert_with(Vec::new);
             let mut task = RticTask {
                 args,
                 task_struct,
@@ -205,76 +294,60 @@ impl App {
                 user_initializable: false, //initially this is false.
             };
             task.adjust_task_impl_initialization()?; // adjust the init method and args type of the task trait implementation
-            tasks.push(task);
-        }
-        Ok(out)
+            Ok(task)
+        });
+        collect_results(tasks)
     }
 
     fn construct_idle_tasks(
         idles: Vec<(ItemStruct, usize)>,
         task_impls: &HashMap<String, ItemImpl>,
-    ) -> syn::Result<HashMap<u32, IdleTask>> {
-        idles
-            .into_iter()
-            .map(|(mut idle_struct, init_attr_idx)| {
-                // find the task struct impl
-                let struct_impl = task_impls.get(&idle_struct.ident.to_string());
+        idle_priority: u16,
+    ) -> syn::Result<Vec<IdleTask>> {
+        let idles = idles.into_iter().map(|(mut idle_struct, init_attr_idx)| {
+            // find the task struct impl
+            let struct_impl = task_impls.get(&idle_struct.ident.to_string());
 
-                // remove the #[idle]
-                let attrs = idle_struct.attrs.remove(init_attr_idx);
-                let mut args = TaskArgs::parse(attrs.meta)?;
-                args.task_trait = format_ident!("{IDLE_TRAIT_TY}"); // correct the trait type for idle
-                let core = args.core;
-                let mut task = IdleTask {
-                    args,
-                    task_struct: idle_struct,
-                    struct_impl: struct_impl.cloned(),
-                    user_initializable: false,
-                };
-                task.adjust_task_impl_initialization()?; // adjust the init method and args type of the task trait implementation
-                Ok((core, task))
-            })
-            .collect::<Result<HashMap<_, _>, syn::Error>>()
+            // remove the #[idle]
+            let attrs = idle_struct.attrs.remove(init_attr_idx);
+            let mut args = TaskArgs::parse(attrs.meta, idle_priority)?;
+            args.task_trait = format_ident!("{IDLE_TRAIT_TY}"); // correct the trait type for idle
+            args.priority = idle_priority; // idle always runs at the lowest priority
+            let mut task = IdleTask {
+                args,
+                task_struct: idle_struct,
+                struct_impl: struct_impl.cloned(),
+                user_initializable: false,
+            };
+            task.adjust_task_impl_initialization()?; // adjust the init method and args type of the task trait implementation
+            Ok(task)
+        });
+        collect_results(idles)
     }
 
-    fn construct_inits(
-        inits: Vec<(ItemFn, usize)>,
-        module_span: Span,
-    ) -> syn::Result<HashMap<u32, InitTask>> {
-        if inits.is_empty() {
-            Err(syn::Error::new(
-                module_span,
-                "No function with #[init] attribute was found in this module.",
-            ))
-        } else {
-            inits
-                .into_iter()
-                .map(|(mut init_fn, init_attr_idx)| {
-                    // // check return type
-                    // let expected_ret = format!("-> {}", shared_resources.strct.ident);
-                    // let found_ret = format!("{}", init_fn.sig.output.to_token_stream());
-                    // if found_ret != expected_ret {
-                    //     return Err(syn::Error::new(
-                    //         init_fn.span(),
-                    //         format!(
-                    //             "Expected function return type to be {expected_ret}, found {found_ret}."
-                    //         ),
-                    //     ));
-                    // }
+    fn construct_inits(inits: Vec<(ItemFn, usize)>) -> syn::Result<Vec<InitTask>> {
+        let inits = inits.into_iter().map(|(mut init_fn, init_attr_idx)| {
+            // // check return type
+            // let expected_ret = format!("-> {}", shared_resources.strct.ident);
+            // let found_ret = format!("{}", init_fn.sig.output.to_token_stream());
+            // if found_ret != expected_ret {
+            //     return Err(syn::Error::new(
+            //         init_fn.span(),
+            //         format!(
+            //             "Expected function return type to be {expected_ret}, found {found_ret}."
+            //         ),
+            //     ));
+            // }
 
-                    // remove the [#init]
-                    let attr = init_fn.attrs.remove(init_attr_idx);
-                    let args = InitTaskArgs::parse(attr.meta)?;
-                    Ok((
-                        args.core,
-                        InitTask {
-                            args,
-                            ident: init_fn.sig.ident.clone(),
-                            body: init_fn,
-                        },
-                    ))
-                })
-                .collect::<Result<HashMap<_, _>, syn::Error>>()
-        }
+            // remove the [#init]
+            let attr = init_fn.attrs.remove(init_attr_idx);
+            let args = InitTaskArgs::parse(attr.meta)?;
+            Ok(InitTask {
+                args,
+                ident: init_fn.sig.ident.clone(),
+                body: init_fn,
+            })
+        });
+        collect_results(inits)
     }
 }
@@ -0,0 +1,171 @@
+//! Checks on the parsed application that can not be done while parsing a single item: the number of `#[init]` and
+//! `#[idle]` per core, the `core = N` arguments, the uniqueness of the shared resource names and of the interrupt
+//! bindings, and the signature of `#[init]`. All the mistakes are reported at once, each one pointing at the offending
+//! item.
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{Ident, spanned::Spanned};
+
+use super::ast::{AppArgs, IdleTask, InitTask, RticTask, SharedResources};
+use crate::diagnostics::combine_errors;
+
+pub(crate) fn validate_app(
+    args: &AppArgs,
+    module_span: Span,
+    shared: &[SharedResources],
+    inits: &[InitTask],
+    idles: &[IdleTask],
+    tasks: &[RticTask],
+) -> syn::Result<()> {
+    let mut errors = Vec::new();
+
+    // every item must belong to one of the cores of the application
+    let cores = args.cores;
+    let items = shared
+        .iter()
+        .map(|s| ("#[shared] struct", &s.strct.ident, s.args.core))
+        .chain(
+            inits
+                .iter()
+                .map(|i| ("#[init] function", &i.ident, i.args.core)),
+        )
+        .chain(
+            idles
+                .iter()
+                .map(|i| ("#[idle] task", i.name(), i.args.core)),
+        )
+        .chain(tasks.iter().map(|t| ("task", t.name(), t.args.core)));
+    for (kind, ident, core) in items {
+        if core >= cores {
+            errors.push(syn::Error::new(
+                ident.span(),
+                format!(
+                    "The {kind} `{ident}` is assigned to core {core}, but the application only has {cores} core(s). \
+                    Use `core = N` with N below {cores}, or increase `cores` in #[app(...)]."
+                ),
+            ));
+        }
+    }
+
+    errors.extend(
+        inits
+            .iter()
+            .filter_map(|init| validate_init_signature(args, init)),
+    );
+
+    for core in 0..cores {
+        // exactly one init per core
+        let mut core_inits = inits.iter().filter(|i| i.args.core == core);
+        match core_inits.next() {
+            None => errors.push(syn::Error::new(
+                module_span,
+                format!("No function with #[init] attribute was found for core {core}."),
+            )),
+            Some(first) => {
+                errors.extend(core_inits.map(|init| {
+                    duplicate_error(&init.ident, "#[init] function", &first.ident, core)
+                }))
+            }
+        }
+
+        // at most one idle per core
+        let mut core_idles = idles.iter().filter(|i| i.args.core == core);
+        if let Some(first) = core_idles.next() {
+            errors.extend(
+                core_idles
+                    .map(|idle| duplicate_error(idle.name(), "#[idle] task", first.name(), core)),
+            );
+        }
+
+        // resources of the same core are accessed by name, so names must be unique across the structs of a core
+        let core_shared: Vec<_> = shared.iter().filter(|s| s.args.core == core).collect();
+        for (i, strct) in core_shared.iter().enumerate() {
+            for element in strct.resources.iter() {
+                let previous = core_shared[..i]
+                    .iter()
+                    .find(|previous| previous.get_field(&element.ident).is_some());
+                if let Some(previous) = previous {
+                    errors.push(syn::Error::new(
+                        element.ident.span(),
+                        format!(
+                            "The resource `{}` is already declared in `{}`, resource names must be unique across \
+                            the #[shared] structs of core {core}.",
+                            element.ident, previous.strct.ident
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // an interrupt can only be bound to one task per core
+        let mut bound: Vec<(&Ident, &RticTask)> = Vec::new();
+        for task in tasks.iter().filter(|t| t.args.core == core) {
+            let Some(irq) = task.args.binds.as_ref() else {
+                continue;
+            };
+            match bound.iter().find(|(bound_irq, _)| *bound_irq == irq) {
+                Some((_, other)) => errors.push(syn::Error::new(
+                    irq.span(),
+                    format!(
+                        "The interrupt `{irq}` is already bound to the task `{}` on core {core}.",
+                        other.name()
+                    ),
+                )),
+                None => bound.push((irq, task)),
+            }
+        }
+    }
+
+    match combine_errors(errors) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// `#[init]` takes the device peripherals of its core when `peripherals = true`, and nothing otherwise
+fn validate_init_signature(args: &AppArgs, init: &InitTask) -> Option<syn::Error> {
+    let ident = &init.ident;
+    let inputs = &init.body.sig.inputs;
+    let pac = args
+        .pacs
+        .get(init.args.core as usize)
+        .map(|pac| pac.to_token_stream().to_string().replace(' ', ""))
+        .unwrap_or_else(|| String::from("pac"));
+    if args.peripherals {
+        match inputs.len() {
+            1 => None,
+            0 => Some(syn::Error::new(
+                init.body.sig.paren_token.span.join(),
+                format!(
+                    "With `peripherals = true`, the #[init] function `{ident}` must take the device peripherals \
+                    as its only argument: `fn {ident}(device: {pac}::Peripherals)`."
+                ),
+            )),
+            _ => Some(syn::Error::new(
+                inputs.span(),
+                format!(
+                    "The #[init] function `{ident}` must take the device peripherals as its only argument: \
+                    `fn {ident}(device: {pac}::Peripherals)`."
+                ),
+            )),
+        }
+    } else if !inputs.is_empty() {
+        Some(syn::Error::new(
+            inputs.span(),
+            format!(
+                "The #[init] function `{ident}` takes no arguments. Use `peripherals = true` in #[app(...)] to \
+                receive the device peripherals (`{pac}::Peripherals`) as argument."
+            ),
+        ))
+    } else {
+        None
+    }
+}
+
+fn duplicate_error(ident: &Ident, kind: &str, first: &Ident, core: u32) -> syn::Error {
+    syn::Error::new(
+        ident.span(),
+        format!("Core {core} can only have one {kind}, but `{first}` is already assigned to it."),
+    )
+}
@@ -0,0 +1,211 @@
+//! Dependency declarations of **Compilation Passes** and the logic used by [crate::RticMacroBuilder] to order them.
+
+use std::collections::BTreeSet;
+
+use proc_macro2::Span;
+
+use crate::RticPass;
+
+/// Describes the syntax a **Compilation Pass** works on and its ordering constraints relative to other passes.
+///
+/// Syntax is identified by plain strings naming either an attribute (e.g. `"sw_task"`) or an argument of an
+/// attribute (e.g. `"sw_task.priority"`). Names are matched exactly, so a pass that only passes through some syntax
+/// it did not introduce should not list it in [Self::produces].
+///
+/// The builder derives the execution order of the bound pre-core passes from these declarations:
+/// - a pass that `produces` some syntax runs before every pass that `consumes` the same syntax.
+/// - a pass runs after every pass listed in its `run_after` (by [RticPass::pass_name]). Those passes provide
+///   output this pass relies on, so binding this pass without them is an error.
+/// - a pass runs before every pass listed in its `run_before`. Passes that are not bound are ignored.
+///
+/// Passes without any ordering constraint between them keep their binding order.
+#[derive(Debug, Default, Clone)]
+pub struct PassDependencies {
+    /// Syntax read by the pass
+    pub consumes: Vec<String>,
+    /// Syntax emitted by the pass
+    pub produces: Vec<String>,
+    /// Names of the passes that must run after this pass (if they are bound)
+    pub run_before: Vec<String>,
+    /// Names of the passes that must have run before this pass
+    pub run_after: Vec<String>,
+}
+
+impl PassDependencies {
+    /// No syntax and no ordering constraint
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds syntax read by the pass, see [Self::consumes](field@Self::consumes)
+    pub fn consumes<S: Into<String>>(mut self, syntax: impl IntoIterator<Item = S>) -> Self {
+        self.consumes.extend(syntax.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds syntax emitted by the pass, see [Self::produces](field@Self::produces)
+    pub fn produces<S: Into<String>>(mut self, syntax: impl IntoIterator<Item = S>) -> Self {
+        self.produces.extend(syntax.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds passes that must run after this pass when they are bound
+    pub fn run_before<S: Into<String>>(mut self, passes: impl IntoIterator<Item = S>) -> Self {
+        self.run_before.extend(passes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds passes that must be bound and run before this pass
+    pub fn run_after<S: Into<String>>(mut self, passes: impl IntoIterator<Item = S>) -> Self {
+        self.run_after.extend(passes.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// An edge `from -> to` of the pass dependency graph, together with a human readable reason for it
+struct Edge {
+    from: usize,
+    to: usize,
+    reason: String,
+}
+
+/// Sorts the passes topologically according to their [PassDependencies]. Passes that are not constrained relative
+/// to each other keep their relative binding order.
+pub(crate) fn order_passes(passes: Vec<Box<dyn RticPass>>) -> syn::Result<Vec<Box<dyn RticPass>>> {
+    let names: Vec<String> = passes.iter().map(|p| p.pass_name().to_string()).collect();
+    let deps: Vec<PassDependencies> = passes.iter().map(|p| p.dependencies()).collect();
+    let edges = collect_edges(&names, &deps)?;
+
+    // Kahn's algorithm, always picking the ready pass that was bound first
+    let mut in_degree = vec![0_usize; passes.len()];
+    for edge in edges.iter() {
+        in_degree[edge.to] += 1;
+    }
+    let mut ready: BTreeSet<usize> = (0..passes.len()).filter(|i| in_degree[*i] == 0).collect();
+    let mut order = Vec::with_capacity(passes.len());
+    while let Some(current) = ready.pop_first() {
+        order.push(current);
+        for edge in edges.iter().filter(|e| e.from == current) {
+            in_degree[edge.to] -= 1;
+            if in_degree[edge.to] == 0 {
+                ready.insert(edge.to);
+            }
+        }
+    }
+
+    if order.len() != passes.len() {
+        let remaining: Vec<usize> = (0..passes.len()).filter(|i| in_degree[*i] > 0).collect();
+        return Err(cycle_error(&names, &edges, &remaining));
+    }
+
+    let mut passes: Vec<Option<Box<dyn RticPass>>> = passes.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .map(|i| {
+            passes[i]
+                .take()
+                .expect("every pass is ordered exactly once")
+        })
+        .collect())
+}
+
+fn collect_edges(names: &[String], deps: &[PassDependencies]) -> syn::Result<Vec<Edge>> {
+    let mut edges = Vec::new();
+    for (producer, producer_deps) in deps.iter().enumerate() {
+        for (consumer, consumer_deps) in deps.iter().enumerate() {
+            if producer == consumer {
+                continue;
+            }
+            if let Some(syntax) = producer_deps
+                .produces
+                .iter()
+                .find(|syntax| consumer_deps.consumes.contains(syntax))
+            {
+                edges.push(Edge {
+                    from: producer,
+                    to: consumer,
+                    reason: format!(
+                        "`{}` produces `{syntax}` which is consumed by `{}`",
+                        names[producer], names[consumer]
+                    ),
+                });
+            }
+        }
+    }
+
+    for (pass, pass_deps) in deps.iter().enumerate() {
+        for required in pass_deps.run_after.iter() {
+            let mut found = false;
+            for (other, _) in names.iter().enumerate().filter(|(_, n)| *n == required) {
+                found = true;
+                edges.push(Edge {
+                    from: other,
+                    to: pass,
+                    reason: format!("`{}` must run after `{required}`", names[pass]),
+                });
+            }
+            if !found {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!(
+                        "The `{}` compilation pass must run after the `{required}` compilation pass, but no pass \
+                        with that name was bound to the RTIC macro builder.",
+                        names[pass]
+                    ),
+                ));
+            }
+        }
+
+        for before in pass_deps.run_before.iter() {
+            for (other, _) in names.iter().enumerate().filter(|(_, n)| *n == before) {
+                edges.push(Edge {
+                    from: pass,
+                    to: other,
+                    reason: format!("`{}` must run before `{before}`", names[pass]),
+                });
+            }
+        }
+    }
+
+    Ok(edges)
+}
+
+/// Finds one cycle among the passes that could not be ordered and turns it into a readable error
+fn cycle_error(names: &[String], edges: &[Edge], remaining: &[usize]) -> syn::Error {
+    // every remaining pass has at least one incoming edge from another remaining pass,
+    // so walking those edges backwards must eventually visit some pass twice.
+    let mut path = vec![remaining[0]];
+    let cycle_start = loop {
+        let current = *path.last().unwrap();
+        let previous = edges
+            .iter()
+            .find(|e| e.to == current && remaining.contains(&e.from))
+            .map(|e| e.from)
+            .expect("a pass left unordered must have an unordered predecessor");
+        if let Some(pos) = path.iter().position(|p| *p == previous) {
+            break pos;
+        }
+        path.push(previous);
+    };
+
+    // `path` was built backwards, reverse it to follow the direction of the edges
+    let mut cycle: Vec<usize> = path[cycle_start..].to_vec();
+    cycle.reverse();
+    cycle.push(cycle[0]);
+
+    let chain = cycle
+        .iter()
+        .map(|i| format!("`{}`", names[*i]))
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    let reasons = cycle
+        .windows(2)
+        .filter_map(|pair| edges.iter().find(|e| e.from == pair[0] && e.to == pair[1]))
+        .map(|e| format!("\n  - {}", e.reason))
+        .collect::<String>();
+
+    syn::Error::new(
+        Span::call_site(),
+        format!("The bound compilation passes have cyclic dependencies: {chain}{reasons}"),
+    )
+}
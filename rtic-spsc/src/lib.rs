@@ -78,6 +78,12 @@ impl<T: Clone, const DEPTH: usize> Queue<T, DEPTH> {
     }
 }
 
+impl<T: Clone, const DEPTH: usize> Default for Queue<T, DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct Producer<'a, T: Clone, const DEPTH: usize> {
     q: &'a mut Queue<T, DEPTH>,
 }
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+/// Set this environment variable (to any value) to overwrite the golden files with the actual output
+pub const BLESS_ENV_VAR: &str = "RTIC_BLESS";
+
+/// Compares `actual` against the golden file `tests/golden/{name}.rs` of the crate under test, or
+/// `tests/golden/{name}` if `name` has an extension.
+///
+/// Panics with both outputs if they differ, or if the golden file doesn't exist. With the [BLESS_ENV_VAR] environment
+/// variable set, the golden file is (re-)written instead.
+pub fn assert_golden(name: &str, actual: &str) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .expect("golden files can only be located when running through `cargo test`");
+    let mut path = PathBuf::from(manifest_dir)
+        .join("tests")
+        .join("golden")
+        .join(name);
+    if path.extension().is_none() {
+        path.set_extension("rs");
+    }
+
+    if std::env::var_os(BLESS_ENV_VAR).is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual)
+            .unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "golden file {} doesn't exist, run the test with {BLESS_ENV_VAR}=1 to create it",
+            path.display()
+        )
+    });
+    if normalize(&expected) != normalize(actual) {
+        let first_difference = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e.trim_end() != a.trim_end())
+            .unwrap_or(expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "output differs from the golden file {} (first difference at line {}), run the test with {BLESS_ENV_VAR}=1 to update it\n\
+            ======== expected ========\n{expected}\n======== actual ========\n{actual}",
+            path.display(),
+            first_difference + 1,
+        );
+    }
+}
+
+/// Ignores trailing whitespace and line ending differences
+fn normalize(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
@@ -0,0 +1,118 @@
+//! Host-side test harness for **Compilation Passes** and **RTIC distributions**.
+//!
+//! The testkit feeds the source of an `#[app]` module and the `#[app(...)]` arguments to a single [RticPass] (see
+//! [expand_pass]) or to a whole [RticMacroBuilder] (see [expand_app]), then renders the result as pretty-printed
+//! Rust followed by the reported diagnostics. The rendered output is meant to be compared against a golden file
+//! checked in next to the tests with [assert_golden]:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn single_core_sw_tasks() {
+//!     let output = rtic_testkit::expand_pass(
+//!         &SoftwarePass::new(MockSwBackend::default()),
+//!         "device = pac, dispatchers = [UART0]",
+//!         include_str!("apps/single_core.rs"),
+//!     );
+//!     rtic_testkit::assert_golden("single_core_sw_tasks", &output);
+//! }
+//! ```
+//!
+//! Golden files are Rust files unless the name passed to [assert_golden] has an extension, e.g.
+//! `assert_golden("model.json", &rtic_testkit::expand_app_model(builder, args, app))`.
+//!
+//! When the output changes on purpose, run the tests with `RTIC_BLESS=1` to overwrite the golden files with the new
+//! output, then review the diff of the golden files before committing them.
+
+mod golden;
+mod mock;
+
+use std::fmt::Write;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use rtic_core::diagnostics::{Diagnostic, Level, Phase};
+use rtic_core::{PassContext, RticMacroBuilder, RticPass};
+use syn::ItemMod;
+
+pub use golden::{BLESS_ENV_VAR, assert_golden};
+pub use mock::{MockBackend, MockSwBackend};
+
+/// Runs `pass` alone on the `app` module source, with `args` as the `#[app(...)]` arguments.
+///
+/// Returns the module produced by the pass, with its arguments re-attached as an `#[app(...)]` attribute, followed by
+/// the diagnostics reported by the pass.
+pub fn expand_pass(pass: &dyn RticPass, args: &str, app: &str) -> String {
+    let ctx = PassContext::new();
+    expand_pass_with_context(pass, &ctx, args, app)
+}
+
+/// Same as [expand_pass], but uses the provided context. Use this to publish the facts the pass expects from the
+/// passes that normally run before it, or to inspect the facts published by the pass.
+pub fn expand_pass_with_context(
+    pass: &dyn RticPass,
+    ctx: &PassContext,
+    args: &str,
+    app: &str,
+) -> String {
+    let (args, app_mod) = parse_input(args, app);
+    ctx.enter_pass(pass.pass_name(), Phase::Parse);
+    let code = ctx
+        .ok_or_report(pass.run_pass(ctx, args, app_mod))
+        .map(|(args, app_mod)| quote!(#[app(#args)] #app_mod));
+    render(code, &ctx.diagnostics())
+}
+
+/// Runs all the passes bound to `builder` on the `app` module source, with `args` as the `#[app(...)]` arguments.
+///
+/// Returns the expanded application followed by all the diagnostics reported during the expansion.
+pub fn expand_app(builder: RticMacroBuilder, args: &str, app: &str) -> String {
+    let (args, app_mod) = parse_input(args, app);
+    let expansion = builder.expand_app(args, quote!(#app_mod));
+    render(expansion.code, &expansion.diagnostics)
+}
+
+/// Same as [expand_app], but returns the application model as JSON (see [rtic_core::model]) instead of the expanded
+/// code. The diagnostics are rendered instead when the expansion stopped before the model was built.
+pub fn expand_app_model(builder: RticMacroBuilder, args: &str, app: &str) -> String {
+    let (args, app_mod) = parse_input(args, app);
+    let expansion = builder.expand_app(args, quote!(#app_mod));
+    match expansion.model {
+        Some(model) => model.to_json(),
+        None => render(None, &expansion.diagnostics),
+    }
+}
+
+fn parse_input(args: &str, app: &str) -> (TokenStream2, ItemMod) {
+    let args = args
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid #[app] arguments `{args}`: {e}"));
+    let app_mod = syn::parse_str(app).unwrap_or_else(|e| panic!("invalid #[app] module: {e}"));
+    (args, app_mod)
+}
+
+/// Renders `code` as pretty-printed Rust (when it is a valid file) followed by one comment block per diagnostic
+pub fn render(code: Option<TokenStream2>, diagnostics: &[Diagnostic]) -> String {
+    let mut out = match code {
+        Some(code) => match syn::parse2::<syn::File>(code.clone()) {
+            Ok(file) => prettyplease::unparse(&file),
+            Err(_) => format!("{code}\n"),
+        },
+        None => String::from("// no code was generated\n"),
+    };
+    for diagnostic in diagnostics {
+        let level = match diagnostic.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        };
+        let start = diagnostic.span.start();
+        let _ = writeln!(
+            out,
+            "\n// {level}[{}, {}] at {}:{}",
+            diagnostic.pass, diagnostic.phase, start.line, start.column
+        );
+        for line in diagnostic.message.lines() {
+            let _ = writeln!(out, "//   {line}");
+        }
+    }
+    out
+}
@@ -0,0 +1,177 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use rtic_core::{AppArgs, BackendCapabilities, CorePassBackend, PassContext, SubAnalysis, SubApp};
+use rtic_sw_pass::SwPassBackend;
+use syn::parse_quote;
+
+/// A [CorePassBackend] that generates simple, target independent calls into an imaginary `rtic::export` module.
+///
+/// The generated code is not meant to be compiled, only to make the output of the **Core Pass** readable and stable
+/// in golden files.
+pub struct MockBackend {
+    /// Value returned by [CorePassBackend::default_task_priority]
+    pub default_task_priority: u16,
+    /// Bindings reported as exceptions by [CorePassBackend::is_exception]
+    pub exceptions: Vec<&'static str>,
+    /// Whether the generated locks can mask exceptions. When `false`, the tasks bound to an exception that access
+    /// shared resources are rejected, see [rtic_core::Analysis::reject_exception_resource_users].
+    pub lock_masks_exceptions: bool,
+    /// Value returned by [CorePassBackend::capabilities]
+    pub capabilities: BackendCapabilities,
+    /// When set, the hardware has this many priority levels with level 0 being the most urgent (like the NVIC), and
+    /// [CorePassBackend::hardware_priority] maps the logical priority `p` to `hardware_levels - p`. Otherwise, the
+    /// logical priorities are the hardware levels.
+    pub hardware_levels: Option<u16>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self {
+            default_task_priority: 1,
+            exceptions: Vec::new(),
+            lock_masks_exceptions: true,
+            capabilities: BackendCapabilities::default(),
+            hardware_levels: None,
+        }
+    }
+}
+
+impl CorePassBackend for MockBackend {
+    fn post_init(
+        &self,
+        ctx: &PassContext,
+        app_args: &AppArgs,
+        app_info: &SubApp,
+        app_analysis: &SubAnalysis,
+    ) -> Option<TokenStream2> {
+        let pac = &app_args.pacs[app_info.core as usize];
+        let level = |priority: &u16| self.hardware_priority(ctx, app_info.core, *priority);
+        let irqs = app_analysis.used_irqs.iter().map(|(irq, priority)| {
+            let level = level(priority);
+            quote! { rtic::export::enable(#pac::Interrupt::#irq, #level); }
+        });
+        let exceptions = app_analysis.used_exceptions.iter().map(|(exception, priority)| {
+            let level = level(priority);
+            quote! { rtic::export::set_exception_priority(rtic::export::Exception::#exception, #level); }
+        });
+        Some(quote!(#(#irqs)* #(#exceptions)*))
+    }
+
+    fn generate_resource_proxy_lock_impl(
+        &self,
+        _ctx: &PassContext,
+        _app_args: &AppArgs,
+        _app_info: &SubApp,
+        incomplete_lock_fn: syn::ImplItemFn,
+    ) -> syn::ImplItemFn {
+        let lock_impl: syn::Block = parse_quote! {
+            { unsafe { rtic::export::lock(resource_ptr, task_priority, CEILING, f) } }
+        };
+        let mut completed_lock_fn = incomplete_lock_fn;
+        completed_lock_fn.block.stmts.extend(lock_impl.stmts);
+        completed_lock_fn
+    }
+
+    fn generate_global_definitions(
+        &self,
+        _ctx: &PassContext,
+        _app_args: &AppArgs,
+        _app_info: &SubApp,
+        _app_analysis: &SubAnalysis,
+    ) -> Option<TokenStream2> {
+        None
+    }
+
+    fn wrap_task_execution(
+        &self,
+        _ctx: &PassContext,
+        _task_prio: u16,
+        _dispatch_task_call: TokenStream2,
+    ) -> Option<TokenStream2> {
+        None
+    }
+
+    fn entry_name(&self, _ctx: &PassContext, core: u32) -> Ident {
+        match core {
+            0 => format_ident!("main"),
+            _ => format_ident!("core{core}_entry"),
+        }
+    }
+
+    fn populate_idle_loop(&self, _ctx: &PassContext) -> Option<TokenStream2> {
+        Some(quote!(rtic::export::wfi();))
+    }
+
+    fn generate_interrupt_free_fn(
+        &self,
+        _ctx: &PassContext,
+        mut empty_body_fn: syn::ItemFn,
+    ) -> syn::ItemFn {
+        empty_body_fn.block = parse_quote!({ rtic::export::interrupt_free(f) });
+        empty_body_fn
+    }
+
+    #[cfg(feature = "multibin")]
+    fn multibin_shared_macro_path(&self, _ctx: &PassContext) -> syn::Path {
+        parse_quote!(rtic::export::microamp::shared)
+    }
+
+    fn pre_codegen_validation(
+        &self,
+        _ctx: &PassContext,
+        app: &rtic_core::App,
+        analysis: &rtic_core::Analysis,
+    ) -> syn::Result<()> {
+        if self.lock_masks_exceptions {
+            Ok(())
+        } else {
+            analysis.reject_exception_resource_users(app, |_| false)
+        }
+    }
+
+    fn default_task_priority(&self, _ctx: &PassContext) -> u16 {
+        self.default_task_priority
+    }
+
+    fn capabilities(&self, _ctx: &PassContext) -> BackendCapabilities {
+        self.capabilities.clone()
+    }
+
+    fn hardware_priority(&self, _ctx: &PassContext, _core: u32, logical: u16) -> u16 {
+        match self.hardware_levels {
+            Some(levels) => levels - logical,
+            None => logical,
+        }
+    }
+
+    fn is_exception(&self, _ctx: &PassContext, _core: u32, binds: &Ident) -> bool {
+        self.exceptions.iter().any(|exception| binds == exception)
+    }
+}
+
+/// A [SwPassBackend] that pends the dispatchers through the same imaginary `rtic::export` module as [MockBackend].
+#[derive(Default)]
+pub struct MockSwBackend {
+    /// Value returned by [SwPassBackend::free_interrupts], for every core
+    pub free_interrupts: Vec<syn::Path>,
+}
+
+impl SwPassBackend for MockSwBackend {
+    fn generate_local_pend_fn(&self, mut empty_body_fn: syn::ItemFn) -> syn::ItemFn {
+        empty_body_fn.block = parse_quote!({
+            rtic::export::pend(irq_nbr);
+        });
+        empty_body_fn
+    }
+
+    fn generate_cross_pend_fn(&self, mut empty_body_fn: syn::ItemFn) -> Option<syn::ItemFn> {
+        empty_body_fn.block = parse_quote!({
+            rtic::export::cross_core_pend(irq_nbr);
+        });
+        Some(empty_body_fn)
+    }
+
+    fn free_interrupts(&self, _core: u32) -> Vec<syn::Path> {
+        self.free_interrupts.clone()
+    }
+}
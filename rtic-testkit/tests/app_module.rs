@@ -0,0 +1,57 @@
+use quote::ToTokens;
+use rtic_core::parse_utils::{AppItem, AppModule};
+use rtic_testkit::{assert_golden, render};
+use syn::{ItemMod, parse_quote};
+
+const APP: &str = include_str!("apps/mixed_items.rs");
+
+#[test]
+fn round_trip_is_lossless() {
+    let app_mod: ItemMod = syn::parse_str(APP).unwrap();
+    let module = AppModule::parse(app_mod.clone()).unwrap();
+    assert_eq!(
+        module.into_item_mod().to_token_stream().to_string(),
+        app_mod.to_token_stream().to_string()
+    );
+}
+
+#[test]
+fn classifies_and_modifies_items() {
+    let mut module = AppModule::parse(syn::parse_str(APP).unwrap()).unwrap();
+    let kinds: Vec<_> = module
+        .items
+        .iter()
+        .map(|item| match item {
+            AppItem::Init(_) => "init",
+            AppItem::Idle(_) => "idle",
+            AppItem::Task(_) => "task",
+            AppItem::SwTask(_) => "sw_task",
+            AppItem::Shared(_) => "shared",
+            AppItem::TaskImpl(_) => "task impl",
+            AppItem::Other(_) => "other",
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "other",
+            "shared",
+            "init",
+            "task",
+            "task impl",
+            "other",
+            "sw_task",
+            "idle",
+            "other"
+        ]
+    );
+
+    for task in module.tasks_mut() {
+        task.attr.elements.insert("core".into(), parse_quote!(1));
+    }
+    for shared in module.shared_mut() {
+        shared.attr.elements.insert("core".into(), parse_quote!(1));
+    }
+    let output = render(Some(module.into_item_mod().to_token_stream()), &[]);
+    assert_golden("modified_app_module", &output);
+}
@@ -0,0 +1,17 @@
+mod app {
+    #[shared]
+    struct Sensors {
+        temperature: i32,
+    }
+
+    #[shared]
+    struct Logging {
+        temperature: i32,
+        log: Logger,
+    }
+
+    #[init]
+    fn init() -> (Sensors, Logging) {
+        todo!()
+    }
+}
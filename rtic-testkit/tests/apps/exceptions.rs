@@ -0,0 +1,37 @@
+mod app {
+    #[shared]
+    struct Shared {
+        ticks: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { ticks: 0 }
+    }
+
+    #[task(binds = SysTick, priority = 2, shared = [ticks])]
+    struct Tick;
+
+    impl RticTask for Tick {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().ticks.lock(|ticks| *ticks += 1);
+        }
+    }
+
+    #[task(binds = UART0, priority = 1, shared = [ticks])]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let _ticks = self.shared().ticks.lock(|ticks| *ticks);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+mod app {
+    #[shared]
+    struct Shared {
+        state: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { state: 0 }
+    }
+
+    #[idle(shared = [state])]
+    struct MyIdle;
+
+    impl RticIdleTask for MyIdle {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) -> ! {
+            loop {
+                self.shared().state.lock(|state| *state += 1);
+            }
+        }
+    }
+
+    #[task(binds = UART0, priority = 0, shared = [state])]
+    struct UartTask;
+
+    impl RticTask for UartTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().state.lock(|state| *state = 0);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+mod app {
+    #[shared(cor = 0)]
+    struct Shared {
+        counter: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    #[task(binds = UART0, prority = 2, shared = [counter])]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    #[task(binds = TIMER0, priority = high, shared = counter, deadline = 10, deadline = 20)]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    #[task(binds = TIMER1, stack_size = 1024)]
+    struct OtherTask;
+
+    impl RticTask for OtherTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+}
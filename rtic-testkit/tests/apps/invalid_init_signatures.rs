@@ -0,0 +1,7 @@
+mod app {
+    #[init(core = 0)]
+    fn init0() {}
+
+    #[init(core = 1)]
+    fn init1(device: pac::Peripherals, uart: pac::UART0) {}
+}
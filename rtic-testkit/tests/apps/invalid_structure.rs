@@ -0,0 +1,39 @@
+mod app {
+    #[shared]
+    struct Shared {
+        counter: u32,
+    }
+
+    #[shared(core = 2)]
+    struct Remote {
+        flag: bool,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    #[init]
+    fn init_again() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    #[task(binds = UART0, shared = [counter])]
+    struct Receiver;
+
+    #[task(binds = UART0, priority = 2)]
+    struct Logger;
+
+    #[task(binds = UART0, core = 1)]
+    struct OtherCore;
+
+    #[task(binds = TIMER0, core = 3)]
+    struct Misplaced;
+
+    #[idle]
+    struct Idle;
+
+    #[idle]
+    struct Background;
+}
@@ -0,0 +1,74 @@
+mod app {
+    #[shared]
+    struct Shared {
+        buffer: [u8; 16],
+        counter: u32,
+        log: Log,
+        unused: bool,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared {
+            buffer: [0; 16],
+            counter: 0,
+            log: Log::new(),
+            unused: false,
+        }
+    }
+
+    #[idle(shared = [log])]
+    struct MyIdle;
+
+    impl RticIdleTask for MyIdle {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) -> ! {
+            loop {
+                self.shared().log.lock(|log| log.flush());
+            }
+        }
+    }
+
+    #[task(binds = UART0, priority = 2, shared = [buffer, counter])]
+    struct UartTask;
+
+    impl RticTask for UartTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().buffer.lock(|buffer| buffer[0] = receive());
+            self.shared().counter.lock(|counter| *counter += 1);
+        }
+    }
+
+    #[task(binds = UART1, priority = 2, shared = [&buffer])]
+    struct EchoTask;
+
+    impl RticTask for EchoTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().buffer.lock_ref(|buffer| send(buffer[0]));
+        }
+    }
+
+    #[task(binds = TIMER0, priority = 1, shared = [counter])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().counter.lock(|counter| *counter = 0);
+        }
+    }
+}
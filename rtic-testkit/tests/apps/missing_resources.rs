@@ -0,0 +1,46 @@
+mod app {
+    #[shared]
+    struct Shared {
+        counter: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    #[task(binds = UART0, priority = 2, shared = [counter, buffer])]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    #[task(binds = TIMER0, shared = [log])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    #[idle(shared = [counter, display])]
+    struct Idle;
+
+    impl RticIdleTask for Idle {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) -> ! {
+            loop {}
+        }
+    }
+}
@@ -0,0 +1,39 @@
+mod app {
+    use core::fmt::Write;
+
+    /// Resources of core 0
+    #[shared]
+    struct Shared {
+        counter: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared { counter: 0 }
+    }
+
+    /// The UART task
+    #[task(binds = UART0, priority = 2, shared = [counter])]
+    #[allow(dead_code)]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+
+    impl Uart0Task {
+        fn helper(&self) {}
+    }
+
+    #[sw_task(priority = 1)]
+    struct Worker;
+
+    #[idle]
+    struct Idle;
+
+    const LIMIT: u32 = 10;
+}
@@ -0,0 +1,53 @@
+mod app {
+    #[shared]
+    struct Sensors {
+        temperature: i32,
+        humidity: u32,
+    }
+
+    #[shared]
+    struct Logging {
+        log: Logger,
+    }
+
+    #[init]
+    fn init() -> (Sensors, Logging) {
+        (
+            Sensors {
+                temperature: 0,
+                humidity: 0,
+            },
+            Logging { log: Logger::new() },
+        )
+    }
+
+    #[task(binds = ADC, priority = 2, shared = [temperature, log])]
+    struct AdcTask;
+
+    impl RticTask for AdcTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let shared = self.shared();
+            (shared.temperature, shared.log).lock(|t, log| {
+                *t += 1;
+                log.write(b"adc");
+            });
+        }
+    }
+
+    #[task(binds = TIMER0, shared = [humidity, log])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().log.lock(|log| log.write(b"tick"));
+        }
+    }
+}
@@ -0,0 +1,24 @@
+mod app {
+    #[shared]
+    struct Shared {
+        uart: pac::UART0,
+    }
+
+    #[init]
+    fn init(device: pac::Peripherals) -> Shared {
+        Shared { uart: device.UART0 }
+    }
+
+    #[task(binds = UART0, shared = [uart])]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().uart.lock(|uart| uart.clear());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+mod app {
+    #[shared]
+    struct Shared {
+        config: Config,
+        samples: u32,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared {
+            config: Config::default(),
+            samples: 0,
+        }
+    }
+
+    #[task(binds = ADC, priority = 3, shared = [&config, &samples])]
+    struct AdcTask;
+
+    impl RticTask for AdcTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            let gain = self.shared().config.read().gain;
+            self.shared().samples.lock_ref(|samples| report(*samples * gain));
+        }
+    }
+
+    #[task(binds = TIMER0, priority = 2, shared = [&config, samples])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().samples.lock(|samples| *samples += 1);
+        }
+    }
+
+    #[task(binds = UART0, priority = 1, shared = [&samples])]
+    struct UartTask;
+
+    impl RticTask for UartTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().samples.lock_ref(|samples| report(*samples));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+mod app {
+    use core::fmt::Write;
+
+    #[shared]
+    struct Shared {
+        counter: u32,
+        log: Logger,
+    }
+
+    #[init]
+    fn init() -> Shared {
+        Shared {
+            counter: 0,
+            log: Logger::new(),
+        }
+    }
+
+    #[task(binds = UART0, priority = 2, shared = [counter, log])]
+    struct Uart0Task {
+        received: u32,
+    }
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self { received: 0 }
+        }
+
+        fn exec(&mut self) {
+            self.received = self.shared().counter.lock(|counter| {
+                *counter += 1;
+                *counter
+            });
+        }
+    }
+
+    #[task(binds = TIMER0, shared = [log])]
+    struct TimerTask;
+
+    impl RticTask for TimerTask {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {
+            self.shared().log.lock(|log| writeln!(log, "tick").ok());
+        }
+    }
+
+    #[idle(shared = [counter, log])]
+    struct Idle;
+
+    impl RticIdleTask for Idle {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) -> ! {
+            loop {
+                let counter = self.shared().counter.lock(|counter| *counter);
+                self.shared().log.lock(|log| writeln!(log, "{counter}").ok());
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+mod app {
+    #[init(core = 0)]
+    fn init0() {}
+
+    #[init(core = 1)]
+    fn init1() {}
+
+    #[task(binds = UART0, core = 1)]
+    struct Uart0Task;
+
+    impl RticTask for Uart0Task {
+        fn init() -> Self {
+            Self
+        }
+
+        fn exec(&mut self) {}
+    }
+}
@@ -0,0 +1,9 @@
+mod app {
+    #[shared]
+    struct Shared(u32, bool);
+
+    #[init]
+    fn init() -> Shared {
+        Shared(0, false)
+    }
+}
@@ -0,0 +1,321 @@
+use proc_macro2::TokenStream;
+use rtic_core::{
+    Analysis, App, ArgKind, AttrSchema, BackendCapabilities, PassContext, PassDependencies,
+    RticMacroBuilder, RticPass,
+};
+use rtic_testkit::{MockBackend, assert_golden, expand_app, expand_app_model};
+use syn::ItemMod;
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn single_core_app() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(builder, "device = pac", include_str!("apps/single_core.rs"));
+    assert_golden("single_core_app", &output);
+}
+
+#[test]
+fn reports_all_missing_resources() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/missing_resources.rs"),
+    );
+    assert_golden("missing_resources", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn multiple_shared_structs() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/multiple_shared.rs"),
+    );
+    assert_golden("multiple_shared", &output);
+}
+
+#[test]
+fn rejects_resources_declared_in_several_shared_structs() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/duplicate_shared.rs"),
+    );
+    assert_golden("duplicate_shared", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn read_only_resources() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/read_only_resources.rs"),
+    );
+    assert_golden("read_only_resources", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn elides_the_locks_of_resources_whose_users_can_not_preempt_each_other() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/lock_free_resources.rs"),
+    );
+    assert_golden("lock_free_resources", &output);
+}
+
+#[test]
+fn locks_resources_shared_by_idle_and_a_task_at_its_priority() {
+    // the priority check of the core pass rejects such a task, run the analysis on its own to reach this case
+    let ctx = PassContext::new();
+    let module = syn::parse_str(include_str!("apps/idle_shared_resources.rs")).unwrap();
+    let mut app = App::parse(&ctx, "device = pac".parse().unwrap(), module).unwrap();
+    Analysis::run(&mut app, &MockBackend::default(), &ctx).unwrap();
+
+    let state = &app.sub_apps[0].shared[0].resources[0];
+    assert_eq!(state.ident, "state");
+    assert_eq!(state.priority, 0);
+    // an interrupt at the priority of idle still preempts it
+    assert!(!state.lock_free);
+}
+
+#[test]
+fn reports_all_structural_mistakes() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac, cores = 2",
+        include_str!("apps/invalid_structure.rs"),
+    );
+    assert_golden("invalid_structure", &output);
+}
+
+#[test]
+fn rejects_shared_structs_without_named_fields() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac",
+        include_str!("apps/unnamed_shared_fields.rs"),
+    );
+    assert_golden("unnamed_shared_fields", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn passes_device_peripherals_to_init() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac, peripherals = true",
+        include_str!("apps/peripherals.rs"),
+    );
+    assert_golden("peripherals", &output);
+}
+
+#[test]
+fn rejects_init_arguments_without_peripherals() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac, peripherals = false",
+        include_str!("apps/peripherals.rs"),
+    );
+    assert_golden("init_arguments_without_peripherals", &output);
+}
+
+#[test]
+fn rejects_init_without_the_device_peripherals() {
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let output = expand_app(
+        builder,
+        "device = pac, cores = 2, peripherals = true",
+        include_str!("apps/invalid_init_signatures.rs"),
+    );
+    assert_golden("invalid_init_signatures", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn binds_tasks_to_exceptions() {
+    let builder = RticMacroBuilder::new(MockBackend {
+        exceptions: vec!["SysTick"],
+        ..Default::default()
+    });
+    let output = expand_app(builder, "device = pac", include_str!("apps/exceptions.rs"));
+    assert_golden("exceptions", &output);
+}
+
+#[test]
+fn rejects_exception_tasks_sharing_resources_when_locks_can_not_mask_them() {
+    let builder = RticMacroBuilder::new(MockBackend {
+        exceptions: vec!["SysTick"],
+        lock_masks_exceptions: false,
+        ..Default::default()
+    });
+    let output = expand_app(builder, "device = pac", include_str!("apps/exceptions.rs"));
+    assert_golden("unmaskable_exception_resources", &output);
+}
+
+#[test]
+fn rejects_applications_beyond_the_target_capabilities() {
+    let builder = RticMacroBuilder::new(MockBackend {
+        exceptions: vec!["SysTick"],
+        capabilities: BackendCapabilities {
+            priorities: 0..=1,
+            exceptions: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let output = expand_app(builder, "device = pac", include_str!("apps/exceptions.rs"));
+    assert_golden("unsupported_priorities_and_exceptions", &output);
+
+    let builder = RticMacroBuilder::new(MockBackend {
+        capabilities: BackendCapabilities {
+            max_cores: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let output = expand_app(
+        builder,
+        "device = pac, cores = 2",
+        include_str!("apps/two_cores.rs"),
+    );
+    assert_golden("too_many_cores", &output);
+}
+
+#[test]
+fn rejects_tasks_at_the_priority_of_idle() {
+    // tasks without a `priority` get the priority of idle
+    let builder = RticMacroBuilder::new(MockBackend {
+        default_task_priority: 0,
+        ..Default::default()
+    });
+    let output = expand_app(builder, "device = pac", include_str!("apps/single_core.rs"));
+    assert_golden("task_at_idle_priority", &output);
+}
+
+#[test]
+#[cfg_attr(
+    feature = "multibin",
+    ignore = "the golden file is generated without the multibin feature"
+)]
+fn maps_logical_priorities_to_hardware_levels() {
+    let builder = RticMacroBuilder::new(MockBackend {
+        exceptions: vec!["SysTick"],
+        hardware_levels: Some(8),
+        ..Default::default()
+    });
+    let output = expand_app(builder, "device = pac", include_str!("apps/exceptions.rs"));
+    assert_golden("hardware_priorities", &output);
+}
+
+#[test]
+fn exports_the_application_model() {
+    let builder = RticMacroBuilder::new(MockBackend {
+        exceptions: vec!["SysTick"],
+        ..Default::default()
+    });
+    let model = expand_app_model(builder, "device = pac", include_str!("apps/exceptions.rs"));
+    assert_golden("exceptions_model.json", &model);
+
+    let builder = RticMacroBuilder::new(MockBackend::default());
+    let model = expand_app_model(
+        builder,
+        "device = pac",
+        include_str!("apps/read_only_resources.rs"),
+    );
+    assert_golden("read_only_resources_model.json", &model);
+}
+
+/// A pass that leaves the application untouched and only declares dependencies and attribute arguments
+struct NopPass {
+    name: &'static str,
+    dependencies: PassDependencies,
+    schema: AttrSchema,
+}
+
+impl RticPass for NopPass {
+    fn run_pass(
+        &self,
+        _ctx: &PassContext,
+        args: TokenStream,
+        app_mod: ItemMod,
+    ) -> syn::Result<(TokenStream, ItemMod)> {
+        Ok((args, app_mod))
+    }
+
+    fn pass_name(&self) -> &str {
+        self.name
+    }
+
+    fn dependencies(&self) -> PassDependencies {
+        self.dependencies.clone()
+    }
+
+    fn attribute_schema(&self) -> AttrSchema {
+        self.schema.clone()
+    }
+}
+
+#[test]
+fn rejects_cyclic_pass_dependencies() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder
+        .bind_pre_core_pass(NopPass {
+            name: "first",
+            dependencies: PassDependencies::new().consumes(["b"]).produces(["a"]),
+            schema: AttrSchema::new(),
+        })
+        .bind_pre_core_pass(NopPass {
+            name: "second",
+            dependencies: PassDependencies::new().consumes(["a"]).produces(["b"]),
+            schema: AttrSchema::new(),
+        });
+    let output = expand_app(builder, "device = pac", include_str!("apps/single_core.rs"));
+    assert_golden("cyclic_pass_dependencies", &output);
+}
+
+#[test]
+fn rejects_unknown_and_ill_typed_arguments() {
+    let mut builder = RticMacroBuilder::new(MockBackend::default());
+    builder.bind_pre_core_pass(NopPass {
+        name: "deadlines",
+        dependencies: PassDependencies::new(),
+        schema: AttrSchema::new().arg("task", "deadline", ArgKind::Int),
+    });
+    let output = expand_app(
+        builder,
+        "device = pac, peripherals = yes, core = 2",
+        include_str!("apps/invalid_arguments.rs"),
+    );
+    assert_golden("invalid_arguments", &output);
+}
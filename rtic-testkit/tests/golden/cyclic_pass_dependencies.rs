@@ -0,0 +1,6 @@
+// no code was generated
+
+// error[core, analysis] at 1:0
+//   The bound compilation passes have cyclic dependencies: `second` -> `first` -> `second`
+//     - `second` produces `b` which is consumed by `first`
+//     - `first` produces `a` which is consumed by `second`
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, parse] at 9:8
+//   The resource `temperature` is already declared in `Sensors`, resource names must be unique across the #[shared] structs of core 0.
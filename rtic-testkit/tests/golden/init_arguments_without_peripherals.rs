@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, parse] at 8:12
+//   The #[init] function `init` takes no arguments. Use `peripherals = true` in #[app(...)] to receive the device peripherals (`pac::Peripherals`) as argument.
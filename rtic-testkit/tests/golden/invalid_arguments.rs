@@ -0,0 +1,25 @@
+// no code was generated
+
+// error[core, parse] at 1:28
+//   argument `peripherals` expects `true` or `false`
+
+// error[core, parse] at 1:33
+//   unknown argument `core` for `#[app]`, did you mean `cores`?
+
+// error[core, parse] at 2:13
+//   unknown argument `cor` for `#[shared]`, did you mean `core`?
+
+// error[core, parse] at 12:26
+//   unknown argument `prority` for `#[task]`, did you mean `priority`?
+
+// error[core, parse] at 23:38
+//   argument `priority` expects an integer literal
+
+// error[core, parse] at 23:53
+//   argument `shared` expects a list of identifiers or references to identifiers
+
+// error[core, parse] at 23:77
+//   duplicate argument `deadline`
+
+// error[core, parse] at 34:27
+//   unknown argument `stack_size` for `#[task]`, expected one of: `binds`, `priority`, `shared`, `core`, `task_trait`, `deadline`
@@ -0,0 +1,7 @@
+// no code was generated
+
+// error[core, parse] at 3:12
+//   With `peripherals = true`, the #[init] function `init0` must take the device peripherals as its only argument: `fn init0(device: pac::Peripherals)`.
+
+// error[core, parse] at 6:13
+//   The #[init] function `init1` must take the device peripherals as its only argument: `fn init1(device: pac::Peripherals)`.
@@ -0,0 +1,19 @@
+// no code was generated
+
+// error[core, parse] at 8:11
+//   The #[shared] struct `Remote` is assigned to core 2, but the application only has 2 core(s). Use `core = N` with N below 2, or increase `cores` in #[app(...)].
+
+// error[core, parse] at 32:11
+//   The task `Misplaced` is assigned to core 3, but the application only has 2 core(s). Use `core = N` with N below 2, or increase `cores` in #[app(...)].
+
+// error[core, parse] at 18:7
+//   Core 0 can only have one #[init] function, but `init` is already assigned to it.
+
+// error[core, parse] at 38:11
+//   Core 0 can only have one #[idle] task, but `Idle` is already assigned to it.
+
+// error[core, parse] at 25:19
+//   The interrupt `UART0` is already bound to the task `Receiver` on core 0.
+
+// error[core, parse] at 1:0
+//   No function with #[init] attribute was found for core 1.
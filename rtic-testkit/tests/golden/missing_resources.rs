@@ -0,0 +1,10 @@
+// no code was generated
+
+// error[core, analysis] at 13:4
+//   The resource `buffer` was not found in `Shared`
+
+// error[core, analysis] at 24:4
+//   The resource `log` was not found in `Shared`
+
+// error[core, analysis] at 35:4
+//   The resource `display` was not found in `Shared`
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, analysis] at 37:11
+//   The task `TimerTask` has priority 0, but the tasks of this target must have a higher priority than idle (0).
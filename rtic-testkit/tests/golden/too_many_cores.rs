@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, analysis] at 1:4
+//   The application has 2 cores, but this target has at most 1.
@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, analysis] at 12:19
+//   The task `Tick` is bound to the exception `SysTick` and accesses the shared resource(s) `ticks`, but `SysTick` can not be masked by the locks of this target. Bind the task to an interrupt, or move the resource accesses to a task bound to an interrupt.
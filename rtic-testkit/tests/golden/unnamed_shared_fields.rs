@@ -0,0 +1,4 @@
+// no code was generated
+
+// error[core, parse] at 3:11
+//   The #[shared] struct `Shared` must have named fields, one per shared resource.
@@ -0,0 +1,7 @@
+// no code was generated
+
+// error[core, analysis] at 12:39
+//   The task `Tick` has priority 2, but the priorities of this target range from 0 (lowest) to 1 (highest).
+
+// error[core, analysis] at 12:19
+//   `SysTick` is an exception, and this target does not support binding tasks to exceptions.